@@ -0,0 +1,14 @@
+-- 0002_create_tasks.sql
+
+-- Создание таблицы задач
+CREATE TABLE IF NOT EXISTS tasks (
+    id SERIAL PRIMARY KEY,
+    title VARCHAR(100) NOT NULL,
+    description VARCHAR(500),
+    user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP
+);
+
+-- Индекс для выборки задач пользователя
+CREATE INDEX IF NOT EXISTS idx_tasks_user_id ON tasks (user_id);
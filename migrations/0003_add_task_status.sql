@@ -0,0 +1,17 @@
+-- 0003_add_task_status.sql
+
+-- Статусы задачи (порядок значений задаёт порядок сортировки)
+DO $$
+BEGIN
+    CREATE TYPE task_status AS ENUM ('todo', 'in_progress', 'blocked', 'done', 'cancelled');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+-- Статус задачи и дата завершения
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS status task_status NOT NULL DEFAULT 'todo',
+    ADD COLUMN IF NOT EXISTS completed_at TIMESTAMP;
+
+-- Индекс для фильтрации задач пользователя по статусу
+CREATE INDEX IF NOT EXISTS idx_tasks_user_id_status ON tasks (user_id, status);
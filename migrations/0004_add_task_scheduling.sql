@@ -0,0 +1,18 @@
+-- 0004_add_task_scheduling.sql
+
+-- Приоритеты задачи (порядок значений задаёт порядок сортировки)
+DO $$
+BEGIN
+    CREATE TYPE task_priority AS ENUM ('low', 'medium', 'high', 'urgent');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+-- Срок выполнения, приоритет и оценка трудозатрат
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS due_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS priority task_priority NOT NULL DEFAULT 'medium',
+    ADD COLUMN IF NOT EXISTS estimated_minutes INTEGER CHECK (estimated_minutes > 0);
+
+-- Индекс для выборок просроченных и предстоящих задач
+CREATE INDEX IF NOT EXISTS idx_tasks_user_id_due_at ON tasks (user_id, due_at);
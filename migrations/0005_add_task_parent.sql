@@ -0,0 +1,9 @@
+-- 0005_add_task_parent.sql
+
+-- Иерархия задач: родительская задача (подзадачи удаляются вместе с родителем)
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS parent_id INTEGER REFERENCES tasks (id) ON DELETE CASCADE
+        CHECK (parent_id <> id);
+
+-- Индекс для выборки подзадач
+CREATE INDEX IF NOT EXISTS idx_tasks_parent_id ON tasks (parent_id);
@@ -0,0 +1,24 @@
+-- 0007_create_labels.sql
+
+-- Метки пользователя
+CREATE TABLE IF NOT EXISTS labels (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    colour CHAR(7) NOT NULL DEFAULT '#808080',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP
+);
+
+-- Имя метки уникально в пределах пользователя без учёта регистра
+CREATE UNIQUE INDEX IF NOT EXISTS idx_labels_user_id_name ON labels (user_id, LOWER(name));
+
+-- Связь задач и меток (многие ко многим)
+CREATE TABLE IF NOT EXISTS task_labels (
+    task_id INTEGER NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    label_id INTEGER NOT NULL REFERENCES labels (id) ON DELETE CASCADE,
+    PRIMARY KEY (task_id, label_id)
+);
+
+-- Индекс для выборки задач по метке
+CREATE INDEX IF NOT EXISTS idx_task_labels_label_id ON task_labels (label_id);
@@ -0,0 +1,27 @@
+-- 0008_create_task_comments.sql
+
+-- Комментарии к задачам (с ответами на другие комментарии)
+CREATE TABLE IF NOT EXISTS task_comments (
+    id SERIAL PRIMARY KEY,
+    task_id INTEGER NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    parent_id INTEGER REFERENCES task_comments (id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP,
+    deleted_at TIMESTAMP
+);
+
+-- Индексы для выборки корневых комментариев задачи и ответов
+CREATE INDEX IF NOT EXISTS idx_task_comments_task_id ON task_comments (task_id, id) WHERE parent_id IS NULL;
+CREATE INDEX IF NOT EXISTS idx_task_comments_parent_id ON task_comments (parent_id);
+
+-- История правок: прежний текст комментария до каждого изменения
+CREATE TABLE IF NOT EXISTS task_comment_revisions (
+    id SERIAL PRIMARY KEY,
+    comment_id INTEGER NOT NULL REFERENCES task_comments (id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_comment_revisions_comment_id ON task_comment_revisions (comment_id);
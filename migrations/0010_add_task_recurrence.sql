@@ -0,0 +1,17 @@
+-- 0010_add_task_recurrence.sql
+
+-- Серии повторяющихся задач: правило, начало расписания и граница уже созданных вхождений
+CREATE TABLE IF NOT EXISTS task_series (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    recurrence VARCHAR(255) NOT NULL,
+    starts_at TIMESTAMPTZ NOT NULL,
+    materialized_until TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS recurrence VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS series_id INTEGER REFERENCES task_series(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_series_id_due_at ON tasks (series_id, due_at);
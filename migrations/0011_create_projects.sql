@@ -0,0 +1,36 @@
+-- 0011_create_projects.sql
+
+-- Роли участников проекта
+DO $$
+BEGIN
+    CREATE TYPE project_role AS ENUM ('owner', 'editor', 'viewer');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+-- Проекты (общие доски задач)
+CREATE TABLE IF NOT EXISTS projects (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP
+);
+
+-- Участники проекта и их роли
+CREATE TABLE IF NOT EXISTS project_members (
+    project_id INTEGER NOT NULL REFERENCES projects (id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    role project_role NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (project_id, user_id)
+);
+
+-- Индекс для выборки проектов пользователя
+CREATE INDEX IF NOT EXISTS idx_project_members_user_id ON project_members (user_id);
+
+-- Задача может принадлежать проекту; без проекта — личная задача владельца
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS project_id INTEGER REFERENCES projects (id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_project_id ON tasks (project_id);
@@ -0,0 +1,21 @@
+-- 0012_create_board_columns.sql
+
+-- Колонки доски проекта; порядок задаётся лексикографическим ключом `rank`
+CREATE TABLE IF NOT EXISTS board_columns (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects (id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    rank VARCHAR(255) COLLATE "C" NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP,
+    -- Откладываемое ограничение позволяет перенумеровать ключи одной командой
+    CONSTRAINT board_columns_project_id_rank_key UNIQUE (project_id, rank) DEFERRABLE INITIALLY IMMEDIATE
+);
+
+-- Положение задачи на доске: колонка и ключ порядка внутри колонки
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS column_id INTEGER REFERENCES board_columns (id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS rank VARCHAR(255) COLLATE "C";
+
+ALTER TABLE tasks
+    ADD CONSTRAINT tasks_column_id_rank_key UNIQUE (column_id, rank) DEFERRABLE INITIALLY IMMEDIATE;
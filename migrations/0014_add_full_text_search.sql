@@ -0,0 +1,19 @@
+-- 0014_add_full_text_search.sql
+
+-- Поисковый вектор задачи: заголовок (вес A) и описание (вес B).
+-- Конфигурация `simple` не зависит от языка и сохраняет слова без стемминга.
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', title), 'A')
+            || setweight(to_tsvector('simple', COALESCE(description, '')), 'B')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_search_vector ON tasks USING GIN (search_vector);
+
+-- Поисковый вектор комментария
+ALTER TABLE task_comments
+    ADD COLUMN IF NOT EXISTS search_vector tsvector GENERATED ALWAYS AS (
+        to_tsvector('simple', body)
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_task_comments_search_vector ON task_comments USING GIN (search_vector);
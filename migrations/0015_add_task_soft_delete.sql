@@ -0,0 +1,8 @@
+-- 0015_add_task_soft_delete.sql
+
+-- Корзина: удалённые задачи скрываются и окончательно удаляются по истечении срока хранения
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
+
+-- Индекс для выборки корзины и фоновой очистки
+CREATE INDEX IF NOT EXISTS idx_tasks_deleted_at ON tasks (deleted_at) WHERE deleted_at IS NOT NULL;
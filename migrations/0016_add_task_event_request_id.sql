@@ -0,0 +1,5 @@
+-- 0016_add_task_event_request_id.sql
+
+-- Идентификатор HTTP-запроса, в рамках которого произошло изменение
+ALTER TABLE task_events
+    ADD COLUMN IF NOT EXISTS request_id VARCHAR(64);
@@ -0,0 +1,9 @@
+-- 0017_create_calendar_feeds.sql
+
+-- Закрытые календарные ленты: доступ по секретному токену в URL, хранится только SHA-256 токена
+CREATE TABLE IF NOT EXISTS calendar_feeds (
+    user_id INTEGER PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_accessed_at TIMESTAMP
+);
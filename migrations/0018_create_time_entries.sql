@@ -0,0 +1,21 @@
+-- 0018_create_time_entries.sql
+
+-- Учёт времени по задачам: таймеры (ended_at IS NULL, пока запущен) и ручные записи
+CREATE TABLE IF NOT EXISTS time_entries (
+    id SERIAL PRIMARY KEY,
+    task_id INTEGER NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    started_at TIMESTAMPTZ NOT NULL,
+    ended_at TIMESTAMPTZ,
+    note VARCHAR(500),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP,
+    CHECK (ended_at IS NULL OR ended_at > started_at)
+);
+
+-- Не больше одного запущенного таймера у пользователя
+CREATE UNIQUE INDEX IF NOT EXISTS idx_time_entries_running ON time_entries (user_id) WHERE ended_at IS NULL;
+
+-- Индексы для суммы времени по задаче и отчёта пользователя за период
+CREATE INDEX IF NOT EXISTS idx_time_entries_task_id ON time_entries (task_id);
+CREATE INDEX IF NOT EXISTS idx_time_entries_user_started ON time_entries (user_id, started_at);
@@ -0,0 +1,22 @@
+-- 0020_create_revoked_tokens.sql
+
+-- Отозванные JWT (по claim `jti`); строки нужны только до истечения срока токена
+CREATE TABLE IF NOT EXISTS revoked_tokens (
+    jti UUID PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Индекс для очистки истёкших записей
+CREATE INDEX IF NOT EXISTS idx_revoked_tokens_expires_at ON revoked_tokens (expires_at);
+
+-- Выход со всех устройств: JWT, выпущенные раньше этого момента, недействительны
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS tokens_revoked_at TIMESTAMPTZ;
+
+-- JWT, выданный вместе с refresh-токеном: выход отзывает и цепочку refresh-токенов
+ALTER TABLE refresh_tokens
+    ADD COLUMN IF NOT EXISTS access_jti UUID;
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_access_jti ON refresh_tokens (access_jti);
@@ -0,0 +1,14 @@
+-- 0023_create_email_verification_tokens.sql
+
+-- Токены подтверждения email: новые пользователи неактивны до перехода по ссылке из письма,
+-- хранится только SHA-256 токена
+CREATE TABLE IF NOT EXISTS email_verification_tokens (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Индекс для ограничения частоты повторной отправки и удаления токенов пользователя
+CREATE INDEX IF NOT EXISTS idx_email_verification_tokens_user_id ON email_verification_tokens (user_id);
@@ -0,0 +1,5 @@
+-- 0024_add_task_series_finished_at.sql
+
+-- Серии без оставшихся вхождений помечаются завершёнными и не просматриваются материализатором
+ALTER TABLE task_series
+    ADD COLUMN IF NOT EXISTS finished_at TIMESTAMPTZ;
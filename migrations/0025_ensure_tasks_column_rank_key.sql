@@ -0,0 +1,10 @@
+-- 0025_ensure_tasks_column_rank_key.sql
+
+-- Уникальность ключа порядка задач в колонке; повторный запуск не падает, если ограничение уже есть
+DO $$
+BEGIN
+    ALTER TABLE tasks
+        ADD CONSTRAINT tasks_column_id_rank_key UNIQUE (column_id, rank) DEFERRABLE INITIALLY IMMEDIATE;
+EXCEPTION
+    WHEN duplicate_object OR duplicate_table THEN NULL;
+END $$;
@@ -0,0 +1,39 @@
+use crate::entities::attachment::Attachment;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// DTO для представления вложения в ответе от сервера.
+///
+/// - `id` — уникальный идентификатор вложения.
+/// - `task_id` — задача.
+/// - `uploaded_by` — ID пользователя, загрузившего файл.
+/// - `file_name` — исходное имя файла.
+/// - `content_type` — MIME-тип.
+/// - `size_bytes` — размер в байтах.
+/// - `checksum_sha256` — SHA-256 содержимого (hex).
+/// - `created_at` — дата загрузки.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AttachmentReadDto {
+    pub id: i32,
+    pub task_id: i32,
+    pub uploaded_by: i32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub checksum_sha256: String,
+    pub created_at: NaiveDateTime,
+}
+impl AttachmentReadDto {
+    pub fn from(model: Attachment) -> AttachmentReadDto {
+        Self {
+            id: model.id,
+            task_id: model.task_id,
+            uploaded_by: model.user_id,
+            file_name: model.file_name,
+            content_type: model.content_type,
+            size_bytes: model.size_bytes,
+            checksum_sha256: model.checksum_sha256,
+            created_at: model.created_at,
+        }
+    }
+}
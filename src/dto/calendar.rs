@@ -0,0 +1,49 @@
+use crate::entities::calendar::CalendarFeed;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Компонент, которым задачи выводятся в календарную ленту.
+///
+/// - `Vtodo` — задачи (`VTODO`), по умолчанию.
+/// - `Vevent` — события (`VEVENT`) для календарей, которые не показывают задачи.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarComponent {
+    #[default]
+    Vtodo,
+    Vevent,
+}
+
+/// Параметры календарной ленты (`/calendar/:token/tasks.ics`).
+///
+/// - `component` — `vtodo` (по умолчанию) или `vevent`.
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct CalendarFeedQuery {
+    #[serde(default)]
+    pub component: CalendarComponent,
+}
+
+/// DTO календарной ленты пользователя.
+///
+/// Токен хранится только в виде хеша, поэтому адрес ленты возвращается
+/// один раз — при выпуске нового токена.
+///
+/// - `url` — адрес ленты с секретным токеном (только при выпуске).
+/// - `created_at` — дата выпуска текущего токена.
+/// - `last_accessed_at` — дата последнего обращения к ленте.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CalendarFeedReadDto {
+    pub url: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_accessed_at: Option<NaiveDateTime>,
+}
+impl CalendarFeedReadDto {
+    pub fn from(model: CalendarFeed) -> CalendarFeedReadDto {
+        Self {
+            url: None,
+            created_at: model.created_at,
+            last_accessed_at: model.last_accessed_at,
+        }
+    }
+}
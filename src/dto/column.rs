@@ -0,0 +1,79 @@
+use crate::dto::task::{present, TaskReadDto};
+use crate::entities::column::BoardColumn;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// DTO для создания колонки доски.
+///
+/// Новая колонка добавляется в конец доски.
+///
+/// - `name` — название колонки (от 1 до 50 символов).
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct ColumnCreateDto {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Name must be between 1 and 50 characters"
+    ))]
+    pub name: String,
+}
+
+/// DTO для изменения колонки доски.
+///
+/// Отсутствующие поля не изменяются.
+///
+/// - `name` — новое название колонки.
+/// - `after_id` — колонка, после которой встаёт данная; `null` — в начало доски.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct ColumnUpdateDto {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Name must be between 1 and 50 characters"
+    ))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    pub after_id: Option<Option<i32>>,
+}
+
+/// DTO для представления колонки в ответе от сервера.
+///
+/// - `id` — уникальный идентификатор колонки.
+/// - `project_id` — проект доски.
+/// - `name` — название колонки.
+/// - `rank` — ключ порядка колонки (колонки сортируются по нему как строки).
+/// - `created_at` — дата создания колонки.
+/// - `updated_at` — дата последнего обновления.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ColumnReadDto {
+    pub id: i32,
+    pub project_id: i32,
+    pub name: String,
+    pub rank: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+impl ColumnReadDto {
+    pub fn from(model: BoardColumn) -> ColumnReadDto {
+        Self {
+            id: model.id,
+            project_id: model.project_id,
+            name: model.name,
+            rank: model.rank,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// DTO колонки доски вместе с задачами (`GET /projects/:id/board`).
+///
+/// - `column` — поля колонки (на верхнем уровне JSON).
+/// - `tasks` — задачи колонки в порядке `rank`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BoardColumnDto {
+    #[serde(flatten)]
+    pub column: ColumnReadDto,
+    pub tasks: Vec<TaskReadDto>,
+}
@@ -0,0 +1,217 @@
+use crate::entities::comment::{CommentRevision, CommentWithAuthor};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use validator::Validate;
+
+/// DTO для создания комментария.
+///
+/// - `body` — текст в формате Markdown (от 1 до 10000 символов).
+/// - `parent_id` — комментарий той же задачи, на который дан ответ (необязательное поле).
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct CommentCreateDto {
+    #[validate(length(
+        min = 1,
+        max = 10000,
+        message = "Body must be between 1 and 10000 characters"
+    ))]
+    pub body: String,
+    pub parent_id: Option<i32>,
+}
+
+/// DTO для изменения текста комментария.
+///
+/// Прежний текст сохраняется в истории правок.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct CommentUpdateDto {
+    #[validate(length(
+        min = 1,
+        max = 10000,
+        message = "Body must be between 1 and 10000 characters"
+    ))]
+    pub body: String,
+}
+
+/// DTO для представления комментария в ответе от сервера.
+///
+/// - `id` — уникальный идентификатор комментария.
+/// - `task_id` — задача.
+/// - `parent_id` — комментарий, на который дан ответ.
+/// - `author_id` / `author_name` — автор комментария.
+/// - `body` — текст в формате Markdown (`None` для удалённого комментария).
+/// - `is_edited` — текст изменялся после создания.
+/// - `is_deleted` — комментарий удалён; остаётся в ветке, если на него есть ответы.
+/// - `created_at` — дата создания.
+/// - `updated_at` — дата последнего изменения.
+/// - `replies` — ответы на комментарий в порядке создания.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CommentReadDto {
+    pub id: i32,
+    pub task_id: i32,
+    pub parent_id: Option<i32>,
+    pub author_id: i32,
+    pub author_name: String,
+    pub body: Option<String>,
+    pub is_edited: bool,
+    pub is_deleted: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+    pub replies: Vec<CommentReadDto>,
+}
+impl CommentReadDto {
+    pub fn from(model: CommentWithAuthor) -> CommentReadDto {
+        let comment = model.comment;
+        let is_deleted = comment.deleted_at.is_some();
+        Self {
+            id: comment.id,
+            task_id: comment.task_id,
+            parent_id: comment.parent_id,
+            author_id: comment.user_id,
+            author_name: model.author_name,
+            body: if is_deleted { None } else { Some(comment.body) },
+            is_edited: comment.updated_at.is_some(),
+            is_deleted,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+            replies: Vec::new(),
+        }
+    }
+
+    /// Сборка веток комментариев из корневых комментариев и ответов на них.
+    ///
+    /// Удалённый комментарий остаётся в ветке только если у него есть видимые ответы.
+    ///
+    /// :param roots: корневые комментарии в порядке вывода.
+    /// :param replies: ответы любой глубины в порядке создания.
+    /// :return: корневые DTO с вложенными `replies`.
+    pub fn threads(
+        roots: Vec<CommentWithAuthor>,
+        replies: Vec<CommentWithAuthor>,
+    ) -> Vec<CommentReadDto> {
+        let mut children: HashMap<i32, Vec<CommentReadDto>> = HashMap::new();
+        for reply in replies {
+            if let Some(parent_id) = reply.comment.parent_id {
+                children
+                    .entry(parent_id)
+                    .or_default()
+                    .push(CommentReadDto::from(reply));
+            }
+        }
+
+        fn build(
+            mut comment: CommentReadDto,
+            children: &mut HashMap<i32, Vec<CommentReadDto>>,
+        ) -> Option<CommentReadDto> {
+            let nodes = children.remove(&comment.id).unwrap_or_default();
+            comment.replies = nodes
+                .into_iter()
+                .filter_map(|child| build(child, children))
+                .collect();
+
+            // Удалённый комментарий без ответов в ветке не показывается
+            if comment.is_deleted && comment.replies.is_empty() {
+                return None;
+            }
+            Some(comment)
+        }
+
+        roots
+            .into_iter()
+            .filter_map(|root| build(CommentReadDto::from(root), &mut children))
+            .collect()
+    }
+}
+
+/// DTO версии комментария из истории правок.
+///
+/// - `body` — текст до изменения.
+/// - `created_at` — момент изменения.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CommentRevisionDto {
+    pub body: String,
+    pub created_at: NaiveDateTime,
+}
+impl CommentRevisionDto {
+    pub fn from(model: CommentRevision) -> CommentRevisionDto {
+        Self {
+            body: model.body,
+            created_at: model.created_at,
+        }
+    }
+}
+
+/// Параметры запроса списка комментариев (`GET /tasks/:id/comments`).
+///
+/// Пагинация выполняется по корневым комментариям, ответы возвращаются целиком.
+///
+/// - `limit` — количество корневых комментариев на странице (от 1 до 100, по умолчанию 20).
+/// - `cursor` — непрозрачный курсор из `next_cursor`/`prev_cursor`.
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct CommentListQuery {
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub limit: i64,
+    pub cursor: Option<String>,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::comment::Comment;
+
+    fn comment(id: i32, parent_id: Option<i32>, deleted: bool) -> CommentWithAuthor {
+        let created_at = NaiveDateTime::default();
+        CommentWithAuthor {
+            comment: Comment {
+                id,
+                task_id: 1,
+                user_id: 1,
+                parent_id,
+                body: format!("comment {}", id),
+                created_at,
+                updated_at: None,
+                deleted_at: deleted.then_some(created_at),
+            },
+            author_name: "author".to_string(),
+        }
+    }
+
+    fn ids(comments: &[CommentReadDto]) -> Vec<i32> {
+        comments.iter().map(|comment| comment.id).collect()
+    }
+
+    #[test]
+    fn nests_replies_under_parents() {
+        let threads = CommentReadDto::threads(
+            vec![comment(1, None, false), comment(2, None, false)],
+            vec![
+                comment(3, Some(1), false),
+                comment(4, Some(3), false),
+                comment(5, Some(1), false),
+            ],
+        );
+
+        assert_eq!(ids(&threads), vec![1, 2]);
+        assert_eq!(ids(&threads[0].replies), vec![3, 5]);
+        assert_eq!(ids(&threads[0].replies[0].replies), vec![4]);
+        assert!(threads[1].replies.is_empty());
+    }
+
+    #[test]
+    fn keeps_deleted_comment_only_with_visible_replies() {
+        let threads = CommentReadDto::threads(
+            vec![comment(1, None, true), comment(2, None, true)],
+            vec![comment(3, Some(1), false), comment(4, Some(2), true)],
+        );
+
+        assert_eq!(ids(&threads), vec![1]);
+        assert!(threads[0].is_deleted);
+        assert_eq!(threads[0].body, None);
+        assert_eq!(ids(&threads[0].replies), vec![3]);
+        assert_eq!(threads[0].replies[0].body.as_deref(), Some("comment 3"));
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Параметры подтверждения email (`GET /auth/verify?token=`).
+///
+/// - `token` — токен из письма.
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct EmailVerifyQuery {
+    #[validate(length(min = 1, max = 128, message = "Token is required"))]
+    pub token: String,
+}
+
+/// DTO повторной отправки письма подтверждения (`POST /auth/verify/resend`).
+///
+/// - `email` — адрес, указанный при регистрации.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate)]
+pub struct EmailVerificationResendDto {
+    #[validate(email(message = "Email is not valid"))]
+    pub email: String,
+}
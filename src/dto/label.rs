@@ -0,0 +1,107 @@
+use crate::entities::label::Label;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// DTO для создания и изменения метки.
+///
+/// Используется в POST- и PUT-запросах.
+///
+/// - `name` — название метки (от 1 до 50 символов, уникально у пользователя).
+/// - `colour` — цвет в формате `#rrggbb` (по умолчанию `#808080`).
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct LabelWriteDto {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Name must be between 1 and 50 characters"
+    ))]
+    pub name: String,
+    #[serde(default = "default_colour")]
+    #[validate(custom(function = "validate_colour"))]
+    pub colour: String,
+}
+
+fn default_colour() -> String {
+    "#808080".to_string()
+}
+
+/// DTO для представления метки в ответе от сервера.
+///
+/// - `id` — уникальный идентификатор метки.
+/// - `name` — название метки.
+/// - `colour` — цвет в формате `#rrggbb`.
+/// - `created_at` — дата создания метки.
+/// - `updated_at` — дата последнего обновления.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LabelReadDto {
+    pub id: i32,
+    pub name: String,
+    pub colour: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+impl LabelReadDto {
+    pub fn from(model: Label) -> LabelReadDto {
+        Self {
+            id: model.id,
+            name: model.name,
+            colour: model.colour,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// Проверка цвета метки (`#rrggbb`).
+fn validate_colour(colour: &str) -> Result<(), ValidationError> {
+    let valid = colour.len() == 7
+        && colour.starts_with('#')
+        && colour[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(ValidationError::new("invalid_colour")
+            .with_message("Colour must be in #rrggbb format".into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_hex_colours() {
+        for colour in ["#808080", "#00ff7F", "#ABCDEF"] {
+            assert!(
+                validate_colour(colour).is_ok(),
+                "`{}` must be accepted",
+                colour
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_colours() {
+        for colour in [
+            "", "808080", "#80808", "#8080800", "#gggggg", "#ffé00", "red",
+        ] {
+            assert!(
+                validate_colour(colour).is_err(),
+                "`{}` must be rejected",
+                colour
+            );
+        }
+    }
+
+    #[test]
+    fn colour_defaults_to_grey() {
+        let label: LabelWriteDto = serde_json::from_str(r#"{"name": "work"}"#).unwrap();
+        assert_eq!(label.colour, "#808080");
+        assert!(label.validate().is_ok());
+
+        let label: LabelWriteDto =
+            serde_json::from_str(r##"{"name": "", "colour": "#fff"}"##).unwrap();
+        assert!(label.validate().is_err());
+    }
+}
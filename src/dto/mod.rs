@@ -1,3 +1,16 @@
 pub mod user;
 pub mod token;
-pub mod task;
\ No newline at end of file
+pub mod task;
+pub mod pagination;
+pub mod label;
+pub mod comment;
+pub mod attachment;
+pub mod project;
+pub mod column;
+pub mod search;
+pub mod transfer;
+pub mod calendar;
+pub mod time_entry;
+pub mod session;
+pub mod password_reset;
+pub mod email_verification;
\ No newline at end of file
@@ -0,0 +1,206 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Направление сортировки.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Ключ сортировки: поле и направление.
+///
+/// - `field` — поле сортировки (например, `TaskSortField`).
+/// - `direction` — направление сортировки.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SortKey<F> {
+    pub field: F,
+    pub direction: SortDirection,
+}
+
+/// Разбор строки сортировки вида `-created_at,title`.
+///
+/// Поля перечисляются через запятую, префикс `-` означает сортировку по убыванию.
+/// Повторяющиеся поля считаются ошибкой.
+///
+/// **<u>:param raw</u>**: строка сортировки из query string.
+/// **<u>:return</u>**: список ключей сортировки или описание ошибки.
+pub fn parse_sort<F>(raw: &str) -> Result<Vec<SortKey<F>>, String>
+where
+    F: FromStr + PartialEq + Copy,
+{
+    let mut keys: Vec<SortKey<F>> = Vec::new();
+
+    for part in raw.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let (direction, name) = match part.strip_prefix('-') {
+            Some(name) => (SortDirection::Desc, name),
+            None => (SortDirection::Asc, part.strip_prefix('+').unwrap_or(part)),
+        };
+        let field = F::from_str(name).map_err(|_| format!("unknown sort field `{}`", name))?;
+
+        if keys.iter().any(|key| key.field == field) {
+            return Err(format!("duplicate sort field `{}`", name));
+        }
+        keys.push(SortKey { field, direction });
+    }
+
+    Ok(keys)
+}
+
+/// Направление перехода по курсору.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CursorDirection {
+    #[serde(rename = "next")]
+    Next,
+    #[serde(rename = "prev")]
+    Prev,
+}
+
+/// Курсор keyset-пагинации.
+///
+/// Для клиента курсор непрозрачен: это base64url-кодированный JSON.
+///
+/// - `direction` — страница после (`next`) или до (`prev`) записи курсора.
+/// - `sort` — нормализованная строка сортировки, для которой выдан курсор.
+/// - `values` — значения ключей сортировки граничной записи.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    #[serde(rename = "d")]
+    pub direction: CursorDirection,
+    #[serde(rename = "s")]
+    pub sort: String,
+    #[serde(rename = "v")]
+    pub values: Vec<String>,
+}
+
+impl Cursor {
+    /// Кодирование курсора в непрозрачную строку.
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    /// Декодирование курсора из строки запроса.
+    ///
+    /// :return: `None`, если строка повреждена.
+    pub fn decode(raw: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(raw).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+/// Нормализованная строка сортировки (обратная операция к `parse_sort`).
+///
+/// Используется для привязки курсора к порядку сортировки.
+pub fn sort_to_string<F: ToString>(keys: &[SortKey<F>]) -> String {
+    keys.iter()
+        .map(|key| match key.direction {
+            SortDirection::Asc => key.field.to_string(),
+            SortDirection::Desc => format!("-{}", key.field.to_string()),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Страница результатов списка.
+///
+/// - `items` — элементы текущей страницы.
+/// - `total` — общее количество элементов, подходящих под фильтры.
+/// - `next_cursor` — курсор следующей страницы.
+/// - `prev_cursor` — курсор предыдущей страницы.
+#[derive(Clone, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Field {
+        Id,
+        Title,
+    }
+
+    impl FromStr for Field {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "id" => Ok(Field::Id),
+                "title" => Ok(Field::Title),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl fmt::Display for Field {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(match self {
+                Field::Id => "id",
+                Field::Title => "title",
+            })
+        }
+    }
+
+    #[test]
+    fn parses_sort_directions() {
+        let keys = parse_sort::<Field>(" -title, +id ").unwrap();
+
+        assert_eq!(
+            keys,
+            vec![
+                SortKey { field: Field::Title, direction: SortDirection::Desc },
+                SortKey { field: Field::Id, direction: SortDirection::Asc },
+            ]
+        );
+        assert_eq!(sort_to_string(&keys), "-title,id");
+        assert!(parse_sort::<Field>(",,").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_sort() {
+        assert_eq!(parse_sort::<Field>("title,-title").unwrap_err(), "duplicate sort field `title`");
+        assert_eq!(parse_sort::<Field>("-").unwrap_err(), "unknown sort field ``");
+        assert_eq!(parse_sort::<Field>("--id").unwrap_err(), "unknown sort field `-id`");
+        assert_eq!(parse_sort::<Field>("Title").unwrap_err(), "unknown sort field `Title`");
+        assert_eq!(parse_sort::<Field>("назв").unwrap_err(), "unknown sort field `назв`");
+    }
+
+    #[test]
+    fn cursor_round_trip() {
+        let cursor = Cursor {
+            direction: CursorDirection::Prev,
+            sort: "-title,id".to_string(),
+            values: vec!["Задача «№1» 🚀".to_string(), "42".to_string()],
+        };
+
+        let encoded = cursor.encode();
+
+        assert!(encoded.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_'));
+        assert_eq!(Cursor::decode(&encoded), Some(cursor));
+    }
+
+    #[test]
+    fn rejects_malformed_cursor() {
+        let valid = Cursor {
+            direction: CursorDirection::Next,
+            sort: "id".to_string(),
+            values: vec!["1".to_string()],
+        }
+            .encode();
+
+        assert_eq!(Cursor::decode(""), None);
+        assert_eq!(Cursor::decode("курсор"), None);
+        assert_eq!(Cursor::decode(&format!("{}==", valid)), None);
+        assert_eq!(Cursor::decode(&valid[..valid.len() - 2]), None);
+        assert_eq!(Cursor::decode(&URL_SAFE_NO_PAD.encode("not json")), None);
+        assert_eq!(Cursor::decode(&URL_SAFE_NO_PAD.encode(r#"{"d":"up","s":"id","v":[]}"#)), None);
+        assert_eq!(Cursor::decode(&URL_SAFE_NO_PAD.encode([0xff, 0xfe])), None);
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// DTO запроса на сброс пароля (`POST /auth/password/forgot`).
+///
+/// - `email` — адрес, на который отправляется ссылка для сброса.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate)]
+pub struct PasswordForgotDto {
+    #[validate(email(message = "Email is not valid"))]
+    pub email: String,
+}
+
+/// DTO установки нового пароля (`POST /auth/password/reset`).
+///
+/// - `token` — токен сброса из письма.
+/// - `password` — новый пароль.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct PasswordResetDto {
+    #[validate(length(min = 1, max = 128, message = "Token is required"))]
+    pub token: String,
+    #[validate(length(
+        min = 8,
+        max = 20,
+        message = "Password must be between 8 and 20 characters"
+    ))]
+    pub password: String,
+}
+
+// Ограниченный Debug для PasswordResetDto — не выводим токен и пароль
+impl std::fmt::Debug for PasswordResetDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PasswordResetDto").finish_non_exhaustive()
+    }
+}
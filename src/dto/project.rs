@@ -0,0 +1,95 @@
+use crate::entities::project::{ProjectMember, ProjectRole, ProjectWithRole};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// DTO для создания и изменения проекта.
+///
+/// Используется в POST- и PUT-запросах.
+///
+/// - `name` — название проекта (от 1 до 100 символов).
+/// - `description` — описание проекта (необязательное поле).
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct ProjectWriteDto {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(length(max = 2000, message = "Description must be at most 2000 characters"))]
+    pub description: Option<String>,
+}
+
+/// DTO для представления проекта в ответе от сервера.
+///
+/// - `id` — уникальный идентификатор проекта.
+/// - `name` — название проекта.
+/// - `description` — описание проекта.
+/// - `role` — роль текущего пользователя в проекте.
+/// - `created_at` — дата создания проекта.
+/// - `updated_at` — дата последнего обновления.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProjectReadDto {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub role: ProjectRole,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+impl ProjectReadDto {
+    pub fn from(model: ProjectWithRole) -> ProjectReadDto {
+        Self {
+            id: model.project.id,
+            name: model.project.name,
+            description: model.project.description,
+            role: model.role,
+            created_at: model.project.created_at,
+            updated_at: model.project.updated_at,
+        }
+    }
+}
+
+/// DTO для добавления участника в проект.
+///
+/// - `email` — email зарегистрированного пользователя.
+/// - `role` — роль в проекте.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct ProjectMemberCreateDto {
+    #[validate(email(message = "Email is not valid"))]
+    pub email: String,
+    pub role: ProjectRole,
+}
+
+/// DTO для смены роли участника проекта.
+///
+/// - `role` — новая роль.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct ProjectMemberUpdateDto {
+    pub role: ProjectRole,
+}
+
+/// DTO для представления участника проекта в ответе от сервера.
+///
+/// - `user_id` — идентификатор пользователя.
+/// - `user_name` — имя пользователя.
+/// - `role` — роль в проекте.
+/// - `created_at` — дата добавления в проект.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProjectMemberReadDto {
+    pub user_id: i32,
+    pub user_name: String,
+    pub role: ProjectRole,
+    pub created_at: NaiveDateTime,
+}
+impl ProjectMemberReadDto {
+    pub fn from(model: ProjectMember) -> ProjectMemberReadDto {
+        Self {
+            user_id: model.user_id,
+            user_name: model.user_name,
+            role: model.role,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::entities::search::SearchHit;
+use crate::services::search::SearchExpression;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Параметры полнотекстового поиска (`GET /search`).
+///
+/// Синтаксис запроса `q`:
+/// - слова через пробел — должны встретиться все (`release notes`);
+/// - `"..."` — точная фраза (`"release notes"`);
+/// - `слово*` — поиск по префиксу (`deploy*`);
+/// - `OR` между словами — хотя бы одно из них (`bug OR issue`);
+/// - `-слово` — исключение (`deploy -staging`).
+///
+/// - `q` — поисковый запрос (от 1 до 200 символов).
+/// - `limit` — количество результатов (от 1 до 100, по умолчанию 20).
+/// - `offset` — смещение.
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct SearchQuery {
+    #[validate(
+        length(min = 1, max = 200, message = "Query must be between 1 and 200 characters"),
+        custom(function = "validate_search")
+    )]
+    pub q: String,
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub limit: i64,
+    #[serde(default)]
+    #[validate(range(min = 0, message = "Offset must not be negative"))]
+    pub offset: i64,
+}
+
+/// Тип найденного объекта.
+///
+/// - `Task` — совпадение в заголовке или описании задачи.
+/// - `Comment` — совпадение в комментарии к задаче.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchHitKind {
+    Task,
+    Comment,
+}
+
+/// DTO результата поиска.
+///
+/// - `kind` — тип найденного объекта.
+/// - `task_id` — задача (для комментария — задача, к которой он относится).
+/// - `comment_id` — комментарий (`null` для задач).
+/// - `title` — заголовок задачи.
+/// - `snippet` — фрагмент текста; совпадения обёрнуты в `<mark>…</mark>`,
+///   остальной текст не экранируется.
+/// - `rank` — релевантность (чем больше, тем выше в выдаче).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SearchResultDto {
+    pub kind: SearchHitKind,
+    pub task_id: i32,
+    pub comment_id: Option<i32>,
+    pub title: String,
+    pub snippet: String,
+    pub rank: f32,
+}
+impl SearchResultDto {
+    pub fn from(model: SearchHit) -> SearchResultDto {
+        Self {
+            kind: match model.comment_id {
+                Some(_) => SearchHitKind::Comment,
+                None => SearchHitKind::Task,
+            },
+            task_id: model.task_id,
+            comment_id: model.comment_id,
+            title: model.title,
+            snippet: model.snippet,
+            rank: model.rank,
+        }
+    }
+}
+
+/// Размер выдачи по умолчанию.
+fn default_limit() -> i64 {
+    20
+}
+
+/// Проверка синтаксиса поискового запроса.
+fn validate_search(q: &str) -> Result<(), ValidationError> {
+    q.parse::<SearchExpression>().map_err(|e| {
+        ValidationError::new("invalid_search").with_message(format!("Invalid search query: {}", e).into())
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::entities::session::Session;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Данные клиента, сохраняемые в сессии при входе.
+///
+/// - `user_agent` — заголовок `User-Agent`.
+/// - `ip_address` — IP-адрес клиента.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SessionClientDto {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// DTO для представления сессии в ответе от сервера.
+///
+/// - `id` — идентификатор сессии.
+/// - `user_agent` — клиент, с которого выполнен вход.
+/// - `ip_address` — IP-адрес при входе.
+/// - `created_at` — дата входа.
+/// - `last_seen_at` — дата последнего запроса (с точностью около минуты).
+/// - `expires_at` — когда сессия завершится без обновления токенов.
+/// - `is_current` — сессия, из которой выполнен запрос.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SessionReadDto {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+    pub expires_at: DateTime<Utc>,
+    pub is_current: bool,
+}
+impl SessionReadDto {
+    pub fn from(model: Session) -> SessionReadDto {
+        Self {
+            id: model.id,
+            user_agent: model.user_agent,
+            ip_address: model.ip_address,
+            created_at: model.created_at,
+            last_seen_at: model.last_seen_at,
+            expires_at: model.expires_at,
+            is_current: false,
+        }
+    }
+}
@@ -1,6 +1,12 @@
-use crate::entities::task::Task;
-use serde::{Serialize, Deserialize};
-use validator::Validate;
+use crate::dto::label::LabelReadDto;
+use crate::entities::task::{Task, TaskEvent, TaskPriority, TaskStatus, TaskWatcher};
+use crate::services::recurrence::RecurrenceRule;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use validator::{Validate, ValidationError};
 
 /// DTO для создания новой задачи.
 ///
@@ -8,6 +14,14 @@ use validator::Validate;
 ///
 /// - `title` — заголовок задачи (обязательное поле, от 3 до 100 символов).
 /// - `description` — описание задачи (необязательное поле, до 500 символов).
+/// - `due_at` — срок выполнения (необязательное поле, не может быть в прошлом).
+/// - `priority` — приоритет (по умолчанию `medium`).
+/// - `estimated_minutes` — оценка трудозатрат в минутах (необязательное поле, больше 0).
+/// - `parent_id` — родительская задача (необязательное поле, должна быть доступна пользователю).
+/// - `recurrence` — правило повторения RRULE (например, `FREQ=WEEKLY;BYDAY=MO`);
+///   требует `due_at`, от которого отсчитывается расписание.
+/// - `project_id` — проект задачи (необязательное поле, нужна роль `owner` или `editor`);
+///   подзадача по умолчанию попадает в проект родителя.
 #[derive(Clone, Serialize, Deserialize, Validate)]
 pub struct TaskCreateDto {
     #[validate(length(
@@ -21,6 +35,81 @@ pub struct TaskCreateDto {
         message = "Description must not exceed 500 characters"
     ))]
     pub description: Option<String>,
+    #[validate(custom(function = "validate_not_in_past"))]
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[validate(range(min = 1, message = "Estimated minutes must be positive"))]
+    pub estimated_minutes: Option<i32>,
+    pub parent_id: Option<i32>,
+    #[validate(custom(function = "validate_recurrence"))]
+    pub recurrence: Option<String>,
+    pub project_id: Option<i32>,
+}
+
+/// DTO для полной замены задачи.
+///
+/// Используется в PUT-запросе: все поля перезаписываются,
+/// отсутствующие необязательные поля очищаются, `priority` сбрасывается в `medium`.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskUpdateDto {
+    #[validate(length(
+        min = 3,
+        max = 100,
+        message = "Title must be between 3 and 100 characters"
+    ))]
+    pub title: String,
+    #[validate(length(
+        max = 500,
+        message = "Description must not exceed 500 characters"
+    ))]
+    pub description: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[validate(range(min = 1, message = "Estimated minutes must be positive"))]
+    pub estimated_minutes: Option<i32>,
+    pub parent_id: Option<i32>,
+    #[validate(custom(function = "validate_recurrence"))]
+    pub recurrence: Option<String>,
+}
+
+/// DTO для частичного обновления задачи.
+///
+/// Используется в PATCH-запросе: изменяются только переданные поля.
+///
+/// - `title` — новый заголовок (если передан).
+/// - `description` — новое описание; `null` очищает описание, отсутствие поля оставляет его без изменений.
+/// - `due_at` — новый срок; `null` снимает срок.
+/// - `priority` — новый приоритет.
+/// - `estimated_minutes` — новая оценка; `null` очищает оценку.
+/// - `parent_id` — новая родительская задача; `null` делает задачу корневой.
+/// - `recurrence` — новое правило повторения; `null` прекращает повторение.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskPatchDto {
+    #[validate(length(
+        min = 3,
+        max = 100,
+        message = "Title must be between 3 and 100 characters"
+    ))]
+    pub title: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(length(
+        max = 500,
+        message = "Description must not exceed 500 characters"
+    ))]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    pub priority: Option<TaskPriority>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(range(min = 1, message = "Estimated minutes must be positive"))]
+    pub estimated_minutes: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "present")]
+    pub parent_id: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(custom(function = "validate_recurrence"))]
+    pub recurrence: Option<Option<String>>,
 }
 
 /// DTO для представления задачи в ответе от сервера.
@@ -31,12 +120,52 @@ pub struct TaskCreateDto {
 /// - `title` — заголовок задачи.
 /// - `description` — описание задачи (может быть `None`).
 /// - `user_id` — ID пользователя, владельца задачи.
+/// - `created_at` — дата создания задачи.
+/// - `updated_at` — дата последнего обновления.
+/// - `status` — текущий статус задачи.
+/// - `completed_at` — дата выполнения задачи.
+/// - `due_at` — срок выполнения.
+/// - `priority` — приоритет задачи.
+/// - `estimated_minutes` — оценка трудозатрат в минутах.
+/// - `parent_id` — идентификатор родительской задачи.
+/// - `progress` — процент выполненных подзадач (`None`, если подзадач нет;
+///   отменённые подзадачи не учитываются).
+/// - `is_blocked` — есть ли незавершённые задачи, от которых зависит данная.
+/// - `labels` — назначенные метки.
+/// - `recurrence` — правило повторения RRULE.
+/// - `series_id` — серия повторяющейся задачи.
+/// - `project_id` — проект задачи (`null` — личная задача).
+/// - `column_id` — колонка доски (`null` — задача не размещена на доске).
+/// - `rank` — ключ порядка внутри колонки (задачи сортируются по нему как строки).
+/// - `assignee_ids` — исполнители задачи.
+/// - `deleted_at` — дата перемещения в корзину (только для задач из корзины).
+/// - `tracked_seconds` — учтённое время всех пользователей в секундах
+///   (запущенные таймеры — до текущего момента).
 #[derive(Clone, Serialize, Deserialize)]
 pub struct TaskReadDto {
     pub id: i32,
     pub title: String,
     pub description: Option<String>,
     pub user_id: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+    pub status: TaskStatus,
+    pub completed_at: Option<NaiveDateTime>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: TaskPriority,
+    pub estimated_minutes: Option<i32>,
+    pub parent_id: Option<i32>,
+    pub progress: Option<i32>,
+    pub is_blocked: bool,
+    pub labels: Vec<LabelReadDto>,
+    pub recurrence: Option<String>,
+    pub series_id: Option<i32>,
+    pub project_id: Option<i32>,
+    pub column_id: Option<i32>,
+    pub rank: Option<String>,
+    pub assignee_ids: Vec<i32>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub tracked_seconds: i64,
 }
 impl TaskReadDto {
     pub fn from(model: Task) -> TaskReadDto {
@@ -45,6 +174,600 @@ impl TaskReadDto {
             title: model.title,
             description: model.description,
             user_id: model.user_id,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            status: model.status,
+            completed_at: model.completed_at,
+            due_at: model.due_at,
+            priority: model.priority,
+            estimated_minutes: model.estimated_minutes,
+            parent_id: model.parent_id,
+            progress: None,
+            is_blocked: false,
+            labels: Vec::new(),
+            recurrence: model.recurrence,
+            series_id: model.series_id,
+            project_id: model.project_id,
+            column_id: model.column_id,
+            rank: model.rank,
+            assignee_ids: Vec::new(),
+            deleted_at: model.deleted_at,
+            tracked_seconds: 0,
+        }
+    }
+}
+
+/// DTO для перемещения задачи на доске (`POST /tasks/:id/move`).
+///
+/// Положение задаётся соседней задачей, а не индексом, поэтому одновременные
+/// перемещения других карточек не сдвигают результат.
+///
+/// - `column_id` — целевая колонка (из проекта задачи).
+/// - `after_id` — задача, после которой встаёт карточка; `null` — в начало колонки.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskMoveDto {
+    pub column_id: i32,
+    pub after_id: Option<i32>,
+}
+
+/// DTO для добавления зависимости (`POST /tasks/:id/dependencies`).
+///
+/// - `depends_on_id` — задача, которая блокирует текущую до своего завершения.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskDependencyCreateDto {
+    pub depends_on_id: i32,
+}
+
+/// DTO для назначения метки задаче (`POST /tasks/:id/labels`).
+///
+/// - `label_id` — метка текущего пользователя.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskLabelCreateDto {
+    pub label_id: i32,
+}
+
+/// DTO для назначения исполнителя (`POST /tasks/:id/assignees`).
+///
+/// - `user_id` — пользователь с доступом к задаче.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskAssigneeCreateDto {
+    pub user_id: i32,
+}
+
+/// DTO для добавления наблюдателя (`POST /tasks/:id/watchers`).
+///
+/// - `user_id` — пользователь с доступом к задаче (обычно текущий).
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskWatcherCreateDto {
+    pub user_id: i32,
+}
+
+/// DTO наблюдателя задачи.
+///
+/// - `user_id` — наблюдатель.
+/// - `user_name` — имя наблюдателя.
+/// - `created_at` — дата подписки.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TaskWatcherReadDto {
+    pub user_id: i32,
+    pub user_name: String,
+    pub created_at: NaiveDateTime,
+}
+impl TaskWatcherReadDto {
+    pub fn from(model: TaskWatcher) -> TaskWatcherReadDto {
+        Self {
+            user_id: model.user_id,
+            user_name: model.user_name,
+            created_at: model.created_at,
+        }
+    }
+}
+
+/// DTO события истории задачи (`GET /tasks/:id/history`).
+///
+/// - `id` — порядковый номер события.
+/// - `actor_id` — автор изменения (`None` — фоновая задача или удалённый пользователь).
+/// - `actor_name` — имя автора.
+/// - `action` — тип события.
+/// - `changes` — подробности изменения.
+/// - `request_id` — идентификатор HTTP-запроса.
+/// - `created_at` — дата события.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TaskEventReadDto {
+    pub id: i64,
+    pub actor_id: Option<i32>,
+    pub actor_name: Option<String>,
+    pub action: String,
+    pub changes: serde_json::Value,
+    pub request_id: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+impl TaskEventReadDto {
+    pub fn from(model: TaskEvent) -> TaskEventReadDto {
+        Self {
+            id: model.id,
+            actor_id: model.actor_id,
+            actor_name: model.actor_name,
+            action: model.action,
+            changes: model.changes,
+            request_id: model.request_id,
+            created_at: model.created_at,
+        }
+    }
+}
+
+/// DTO дерева задачи (`GET /tasks/:id/tree`).
+///
+/// - `task` — поля задачи (на верхнем уровне JSON).
+/// - `children` — подзадачи, рекурсивно.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TaskTreeDto {
+    #[serde(flatten)]
+    pub task: TaskReadDto,
+    pub children: Vec<TaskTreeDto>,
+}
+impl TaskTreeDto {
+    /// Сборка дерева из корневой задачи и всех её потомков.
+    ///
+    /// :param root: корневая задача.
+    /// :param descendants: подзадачи любой глубины (порядок внутри одного родителя сохраняется).
+    /// :return: дерево задачи; потомки, чей родитель не попал в выборку, отбрасываются.
+    pub fn build(root: TaskReadDto, descendants: Vec<TaskReadDto>) -> Self {
+        let mut children: HashMap<i32, Vec<TaskReadDto>> = HashMap::new();
+        for task in descendants {
+            if let Some(parent_id) = task.parent_id {
+                children.entry(parent_id).or_default().push(task);
+            }
         }
+
+        fn attach(task: TaskReadDto, children: &mut HashMap<i32, Vec<TaskReadDto>>) -> TaskTreeDto {
+            let nodes = children.remove(&task.id).unwrap_or_default();
+            TaskTreeDto {
+                task,
+                children: nodes.into_iter().map(|child| attach(child, children)).collect(),
+            }
+        }
+
+        attach(root, &mut children)
+    }
+}
+
+/// Параметры удаления задачи (`DELETE /tasks/:id`).
+///
+/// - `keep_children` — если `true`, подзадачи переносятся к родителю удаляемой задачи,
+///   иначе удаляются вместе с ней.
+#[derive(Clone, Debug, Default, Deserialize, Validate)]
+pub struct TaskDeleteQuery {
+    #[serde(default)]
+    pub keep_children: bool,
+}
+
+/// DTO для смены статуса задачи (`POST /tasks/:id/transition`).
+///
+/// - `status` — целевой статус; переход проверяется правилами `TaskWorkflow`.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskTransitionDto {
+    pub status: TaskStatus,
+}
+
+/// Режим обработки ошибок массовой операции.
+///
+/// - `AllOrNothing` — первая ошибка отменяет все операции, остальные пропускаются (по умолчанию).
+/// - `PerItem` — отменяются только неудачные операции, остальные применяются.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskBulkMode {
+    #[default]
+    AllOrNothing,
+    PerItem,
+}
+
+/// DTO массовой обработки задач (`POST /tasks/bulk`).
+///
+/// Элементы `operations` разбираются и валидируются по отдельности, чтобы
+/// ошибка в одном элементе попадала в его результат, а не отклоняла весь запрос.
+///
+/// - `mode` — режим обработки ошибок.
+/// - `operations` — операции (от 1 до 100), каждая — `TaskBulkOperationDto`
+///   вместе с полями DTO соответствующего действия.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskBulkDto {
+    #[serde(default)]
+    pub mode: TaskBulkMode,
+    #[validate(length(min = 1, max = 100))]
+    pub operations: Vec<serde_json::Value>,
+}
+
+/// Действие элемента массовой обработки.
+///
+/// - `UpdateStatus` — смена статуса (поля `TaskTransitionDto`).
+/// - `AddLabel` — назначение метки (поля `TaskLabelCreateDto`).
+/// - `Move` — перемещение на доске (поля `TaskMoveDto`).
+/// - `Delete` — удаление в корзину (поля `TaskDeleteQuery`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskBulkAction {
+    UpdateStatus,
+    AddLabel,
+    Move,
+    Delete,
+}
+
+/// Общие поля элемента массовой обработки.
+///
+/// - `action` — действие.
+/// - `task_id` — задача, к которой применяется действие.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TaskBulkOperationDto {
+    pub action: TaskBulkAction,
+    pub task_id: i32,
+}
+
+/// Итог элемента массовой обработки.
+///
+/// - `Applied` — операция применена.
+/// - `RolledBack` — операция выполнилась, но отменена вместе с транзакцией (`AllOrNothing`).
+/// - `Failed` — операция завершилась ошибкой и отменена.
+/// - `Skipped` — операция не выполнялась после ошибки (`AllOrNothing`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskBulkItemStatus {
+    Applied,
+    RolledBack,
+    Failed,
+    Skipped,
+}
+
+/// Результат элемента массовой обработки.
+///
+/// - `index` — позиция элемента в `operations`.
+/// - `task_id` — задача (если удалось прочитать из элемента).
+/// - `status` — итог операции.
+/// - `code` — HTTP-код ошибки (для `Failed`).
+/// - `message` — текст ошибки (для `Failed`).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TaskBulkItemResultDto {
+    pub index: usize,
+    pub task_id: Option<i32>,
+    pub status: TaskBulkItemStatus,
+    pub code: Option<u16>,
+    pub message: Option<String>,
+}
+
+/// DTO результата массовой обработки.
+///
+/// - `committed` — изменения сохранены (`false` — транзакция отменена).
+/// - `results` — результаты элементов в порядке `operations`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TaskBulkResultDto {
+    pub committed: bool,
+    pub results: Vec<TaskBulkItemResultDto>,
+}
+
+/// Параметры запроса списка задач (`GET /tasks`).
+///
+/// - `limit` — размер страницы (от 1 до 100, по умолчанию 20).
+/// - `offset` — смещение для offset-пагинации (несовместимо с `cursor`).
+/// - `cursor` — непрозрачный курсор keyset-пагинации из `next_cursor`/`prev_cursor`.
+/// - `sort` — поля сортировки через запятую, `-` для убывания (например, `-created_at,title`).
+/// - `title` — фильтр по подстроке заголовка (без учёта регистра).
+/// - `status` — фильтр по статусам через запятую (например, `todo,in_progress`).
+/// - `due_after` / `due_before` — диапазон срока выполнения (RFC 3339, включительно).
+/// - `parent_id` — только подзадачи указанной задачи.
+/// - `labels` — фильтр по ID меток через запятую (например, `1,4`).
+/// - `labels_match` — `any` (хотя бы одна из меток, по умолчанию) или `all` (все метки).
+/// - `project_id` — только задачи указанного проекта.
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct TaskListQuery {
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub limit: i64,
+    #[validate(range(min = 0, message = "Offset must not be negative"))]
+    pub offset: Option<i64>,
+    pub cursor: Option<String>,
+    pub sort: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Title filter must be between 1 and 100 characters"
+    ))]
+    pub title: Option<String>,
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub status: Option<Vec<TaskStatus>>,
+    pub due_after: Option<DateTime<Utc>>,
+    pub due_before: Option<DateTime<Utc>>,
+    pub parent_id: Option<i32>,
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub labels: Option<Vec<i32>>,
+    #[serde(default)]
+    pub labels_match: LabelMatch,
+    pub project_id: Option<i32>,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+/// Режим фильтра по меткам.
+///
+/// - `Any` — у задачи есть хотя бы одна из меток.
+/// - `All` — у задачи есть все перечисленные метки.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelMatch {
+    #[default]
+    Any,
+    All,
+}
+
+/// Параметры запроса предстоящих задач (`GET /tasks/upcoming`).
+///
+/// - `within` — горизонт в формате `<число><единица>`: `m` — минуты, `h` — часы,
+///   `d` — дни, `w` — недели (например, `7d`). По умолчанию `7d`, не более `365d`.
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct TaskUpcomingQuery {
+    #[serde(default = "default_within", deserialize_with = "period")]
+    #[validate(custom(function = "validate_within"))]
+    pub within: Duration,
+}
+
+fn default_within() -> Duration {
+    Duration::days(7)
+}
+
+/// Поля, по которым допускается сортировка списка задач.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskSortField {
+    Id,
+    Title,
+    CreatedAt,
+    UpdatedAt,
+    Status,
+    DueAt,
+    Priority,
+}
+
+impl TaskSortField {
+    /// Значение поля задачи для курсора keyset-пагинации.
+    ///
+    /// Формат значения должен приводиться в SQL к типу соответствующей колонки.
+    pub fn cursor_value(&self, task: &Task) -> String {
+        match self {
+            TaskSortField::Id => task.id.to_string(),
+            TaskSortField::Title => task.title.clone(),
+            TaskSortField::CreatedAt => task.created_at.to_string(),
+            TaskSortField::UpdatedAt => task.updated_at.unwrap_or(task.created_at).to_string(),
+            TaskSortField::Status => task.status.to_string(),
+            TaskSortField::DueAt => task
+                .due_at
+                .map(|due_at| due_at.to_rfc3339())
+                .unwrap_or_else(|| "infinity".to_string()),
+            TaskSortField::Priority => task.priority.to_string(),
+        }
+    }
+}
+
+impl FromStr for TaskSortField {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "id" => Ok(TaskSortField::Id),
+            "title" => Ok(TaskSortField::Title),
+            "created_at" => Ok(TaskSortField::CreatedAt),
+            "updated_at" => Ok(TaskSortField::UpdatedAt),
+            "status" => Ok(TaskSortField::Status),
+            "due_at" => Ok(TaskSortField::DueAt),
+            "priority" => Ok(TaskSortField::Priority),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for TaskSortField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TaskSortField::Id => "id",
+            TaskSortField::Title => "title",
+            TaskSortField::CreatedAt => "created_at",
+            TaskSortField::UpdatedAt => "updated_at",
+            TaskSortField::Status => "status",
+            TaskSortField::DueAt => "due_at",
+            TaskSortField::Priority => "priority",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Десериализация поля, различающая `null` и отсутствие значения.
+///
+/// Вызывается только если поле присутствует в JSON, поэтому результат всегда `Some(..)`:
+/// `Some(None)` для `null`, `Some(Some(value))` для значения.
+pub(crate) fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Десериализация списка значений, перечисленных через запятую (`a,b,c`).
+///
+/// Используется для фильтров в query string, где повторяющиеся ключи не поддерживаются.
+pub(crate) fn comma_separated<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let raw = Option::<String>::deserialize(deserializer)?;
+
+    raw.map(|raw| {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<T>().map_err(serde::de::Error::custom))
+            .collect()
+    })
+    .transpose()
+}
+
+/// Разбор длительности вида `7d`, `12h`, `30m`, `2w`.
+fn period<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    let raw = raw.trim();
+    let invalid = || serde::de::Error::custom(format!("invalid period `{}`", raw));
+
+    // Единица — последний символ (по границе символа, а не байта)
+    let (split, _) = raw.char_indices().last().ok_or_else(invalid)?;
+    let (amount, unit) = raw.split_at(split);
+    let amount = amount.parse::<i64>().map_err(|_| invalid())?;
+
+    match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+/// Проверка, что срок выполнения не находится в прошлом.
+fn validate_not_in_past(due_at: &DateTime<Utc>) -> Result<(), ValidationError> {
+    if *due_at < Utc::now() {
+        return Err(ValidationError::new("due_at_in_past")
+            .with_message("Due date must not be in the past".into()));
+    }
+
+    Ok(())
+}
+
+/// Проверка правила повторения (RRULE).
+pub(crate) fn validate_recurrence(recurrence: &str) -> Result<(), ValidationError> {
+    recurrence.parse::<RecurrenceRule>().map_err(|e| {
+        ValidationError::new("invalid_recurrence").with_message(format!("Invalid recurrence rule: {}", e).into())
+    })?;
+
+    Ok(())
+}
+
+/// Проверка горизонта выборки предстоящих задач (от 1 минуты до 365 дней).
+fn validate_within(within: &Duration) -> Result<(), ValidationError> {
+    if *within < Duration::minutes(1) || *within > Duration::days(365) {
+        return Err(ValidationError::new("within_out_of_range")
+            .with_message("Period must be between 1m and 365d".into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::value::{Error as DeError, StrDeserializer};
+
+    fn parse_period(raw: &str) -> Result<Duration, DeError> {
+        period(StrDeserializer::<DeError>::new(raw))
+    }
+
+    #[test]
+    fn parses_period_units() {
+        assert_eq!(parse_period("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_period("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_period(" 7d ").unwrap(), Duration::days(7));
+        assert_eq!(parse_period("2w").unwrap(), Duration::weeks(2));
+    }
+
+    #[test]
+    fn rejects_malformed_period() {
+        for raw in ["", "d", "7", "7y", "7D", "-", "1.5d", "9999999999999999999d", "99999999999999w"] {
+            assert!(parse_period(raw).is_err(), "`{}` must be rejected", raw);
+        }
+    }
+
+    #[test]
+    fn rejects_non_ascii_period_without_panicking() {
+        for raw in ["é", "7é", "éd", "７d", "7д"] {
+            assert!(parse_period(raw).is_err(), "`{}` must be rejected", raw);
+        }
+    }
+
+    #[test]
+    fn upcoming_query_limits_horizon() {
+        let query: TaskUpcomingQuery = serde_json::from_str(r#"{"within": "400d"}"#).unwrap();
+        assert!(query.validate().is_err());
+
+        let query: TaskUpcomingQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query.within, Duration::days(7));
+        assert!(query.validate().is_ok());
+    }
+
+    fn task(id: i32, parent_id: Option<i32>) -> TaskReadDto {
+        let task: Task = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": format!("Task {}", id),
+            "user_id": 1,
+            "created_at": "2024-01-01T00:00:00",
+            "status": "todo",
+            "priority": "medium",
+            "parent_id": parent_id,
+        }))
+        .unwrap();
+        TaskReadDto::from(task)
+    }
+
+    fn ids(tree: &TaskTreeDto) -> Vec<i32> {
+        tree.children.iter().map(|child| child.task.id).collect()
+    }
+
+    #[test]
+    fn builds_nested_tree_preserving_order() {
+        let tree = TaskTreeDto::build(
+            task(1, None),
+            vec![task(3, Some(1)), task(2, Some(1)), task(4, Some(3)), task(5, Some(4))],
+        );
+
+        assert_eq!(tree.task.id, 1);
+        assert_eq!(ids(&tree), vec![3, 2]);
+        assert_eq!(ids(&tree.children[0]), vec![4]);
+        assert_eq!(ids(&tree.children[0].children[0]), vec![5]);
+        assert!(tree.children[1].children.is_empty());
+    }
+
+    #[test]
+    fn drops_descendants_detached_from_root() {
+        let tree = TaskTreeDto::build(task(1, None), vec![task(2, Some(1)), task(7, Some(6)), task(8, None)]);
+        assert_eq!(ids(&tree), vec![2]);
+        assert!(tree.children[0].children.is_empty());
+    }
+
+    #[test]
+    fn patch_distinguishes_null_parent_from_absent() {
+        let patch: TaskPatchDto = serde_json::from_str("{}").unwrap();
+        assert_eq!(patch.parent_id, None);
+
+        let patch: TaskPatchDto = serde_json::from_str(r#"{"parent_id": null}"#).unwrap();
+        assert_eq!(patch.parent_id, Some(None));
+
+        let patch: TaskPatchDto = serde_json::from_str(r#"{"parent_id": 5}"#).unwrap();
+        assert_eq!(patch.parent_id, Some(Some(5)));
+    }
+
+    #[test]
+    fn parses_label_filter() {
+        let query: TaskListQuery = serde_json::from_str(r#"{"labels": "1, 4,,7"}"#).unwrap();
+        assert_eq!(query.labels, Some(vec![1, 4, 7]));
+        assert_eq!(query.labels_match, LabelMatch::Any);
+
+        let query: TaskListQuery =
+            serde_json::from_str(r#"{"labels": "2", "labels_match": "all"}"#).unwrap();
+        assert_eq!(query.labels_match, LabelMatch::All);
+
+        assert!(serde_json::from_str::<TaskListQuery>(r#"{"labels": "1,x"}"#).is_err());
+        assert!(serde_json::from_str::<TaskListQuery>(r#"{"labels_match": "some"}"#).is_err());
     }
 }
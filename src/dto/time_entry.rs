@@ -0,0 +1,165 @@
+use crate::entities::time_entry::{TimeEntry, TimeReportRow};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// DTO для ручной записи времени и изменения записи.
+///
+/// Интервал должен быть завершён: `ended_at` позже `started_at` и не в будущем.
+///
+/// - `started_at` — начало интервала.
+/// - `ended_at` — конец интервала.
+/// - `note` — комментарий (не более 500 символов, необязательное поле).
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TimeEntryWriteDto {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    #[validate(length(max = 500, message = "Note must not exceed 500 characters"))]
+    pub note: Option<String>,
+}
+
+/// DTO для представления записи времени в ответе от сервера.
+///
+/// - `id` — уникальный идентификатор записи.
+/// - `task_id` — задача.
+/// - `user_id` — владелец записи.
+/// - `started_at` — начало интервала.
+/// - `ended_at` — конец интервала (`null`, пока таймер запущен).
+/// - `duration_seconds` — длительность; для запущенного таймера — до текущего момента.
+/// - `is_running` — таймер запущен.
+/// - `note` — комментарий.
+/// - `created_at` — дата создания.
+/// - `updated_at` — дата последнего изменения.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TimeEntryReadDto {
+    pub id: i32,
+    pub task_id: i32,
+    pub user_id: i32,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: i64,
+    pub is_running: bool,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+impl TimeEntryReadDto {
+    pub fn from(model: TimeEntry) -> TimeEntryReadDto {
+        Self {
+            id: model.id,
+            task_id: model.task_id,
+            user_id: model.user_id,
+            started_at: model.started_at,
+            ended_at: model.ended_at,
+            duration_seconds: (model.ended_at.unwrap_or_else(Utc::now) - model.started_at).num_seconds(),
+            is_running: model.ended_at.is_none(),
+            note: model.note,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// Группировка отчёта по учтённому времени.
+///
+/// - `Day` — по дням (по умолчанию).
+/// - `Week` — по неделям (с понедельника).
+/// - `Project` — по проектам задач.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeReportGroup {
+    #[default]
+    Day,
+    Week,
+    Project,
+}
+
+/// Параметры отчёта по учтённому времени (`GET /time-entries/report`).
+///
+/// Даты задаются в UTC, обе границы включаются в период (не длиннее 366 дней).
+///
+/// - `from` — первый день периода.
+/// - `to` — последний день периода.
+/// - `group_by` — `day` (по умолчанию), `week` или `project`.
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct TimeReportQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    #[serde(default)]
+    pub group_by: TimeReportGroup,
+}
+
+/// Строка отчёта по учтённому времени.
+///
+/// - `period` — первый день периода (при группировке по дням и неделям).
+/// - `project_id` — проект (при группировке по проектам; `null` — личные задачи).
+/// - `tracked_seconds` — учтённое время в секундах.
+/// - `entries` — количество записей.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TimeReportRowDto {
+    pub period: Option<NaiveDate>,
+    pub project_id: Option<i32>,
+    pub tracked_seconds: i64,
+    pub entries: i64,
+}
+impl TimeReportRowDto {
+    pub fn from(model: TimeReportRow) -> TimeReportRowDto {
+        Self {
+            period: model.period,
+            project_id: model.project_id,
+            tracked_seconds: model.seconds,
+            entries: model.entries,
+        }
+    }
+}
+
+/// DTO отчёта по учтённому времени.
+///
+/// - `from` / `to` — период отчёта.
+/// - `group_by` — группировка строк.
+/// - `total_seconds` — всего учтено за период.
+/// - `rows` — строки в порядке периодов или проектов.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TimeReportDto {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub group_by: TimeReportGroup,
+    pub total_seconds: i64,
+    pub rows: Vec<TimeReportRowDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry(started_at: DateTime<Utc>, ended_at: Option<DateTime<Utc>>) -> TimeEntry {
+        TimeEntry {
+            id: 1,
+            task_id: 2,
+            user_id: 3,
+            started_at,
+            ended_at,
+            note: None,
+            created_at: started_at.naive_utc(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn stopped_entry_reports_its_duration() {
+        let started_at = Utc::now() - Duration::days(1);
+        let dto = TimeEntryReadDto::from(entry(started_at, Some(started_at + Duration::minutes(90))));
+
+        assert!(!dto.is_running);
+        assert_eq!(dto.duration_seconds, 90 * 60);
+    }
+
+    #[test]
+    fn running_entry_counts_up_to_now() {
+        let dto = TimeEntryReadDto::from(entry(Utc::now() - Duration::minutes(5), None));
+
+        assert!(dto.is_running);
+        assert!((5 * 60..6 * 60).contains(&dto.duration_seconds));
+    }
+}
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
 
 /// DTO для представления JWT-токена (например, при ответе клиенту).
 ///
@@ -7,11 +9,28 @@ use serde::{Deserialize, Serialize};
 /// - `token` — строка токена (JWT).
 /// - `iat` — время выпуска токена (issued at, Unix timestamp).
 /// - `exp` — срок действия токена (expiration time, Unix timestamp).
+/// - `refresh_token` — непрозрачный refresh-токен для `POST /auth/refresh`.
+/// - `refresh_exp` — срок действия refresh-токена (Unix timestamp).
 #[derive(Clone, Serialize, Deserialize)]
 pub struct TokenReadDto {
     pub token: String,
     pub iat: i64,
     pub exp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_exp: Option<i64>,
+}
+
+/// DTO для обновления токенов (`POST /auth/refresh`).
+///
+/// Предъявленный refresh-токен становится недействительным: в ответ выдаётся новая пара.
+///
+/// - `refresh_token` — refresh-токен из предыдущего ответа.
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct TokenRefreshDto {
+    #[validate(length(min = 1, max = 128, message = "Refresh token must be between 1 and 128 characters"))]
+    pub refresh_token: String,
 }
 
 /// DTO для представления **payload (claims)** токена.
@@ -22,10 +41,14 @@ pub struct TokenReadDto {
 /// - `email` — Email пользователя.
 /// - `iat` — Время выпуска токена.
 /// - `exp` — Время истечения срока действия токена.
+/// - `jti` — Уникальный идентификатор токена (для отзыва при выходе).
+/// - `sid` — Сессия, в которой выдан токен.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct TokenClaimsDto {
     pub sub: i32,
     pub email: String,
     pub iat: i64,
     pub exp: i64,
+    pub jti: Uuid,
+    pub sid: Uuid,
 }
@@ -0,0 +1,201 @@
+use crate::dto::task::{validate_recurrence, TaskCreateDto};
+use crate::entities::task::{Task, TaskPriority, TaskStatus};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Формат файла импорта и экспорта задач.
+///
+/// - `Csv` — CSV с заголовком (колонки как у `TaskExportDto`).
+/// - `Jsonl` — JSON Lines: одна задача (`TaskExportDto`) на строку.
+/// - `Ics` — iCalendar с задачами в компонентах `VTODO`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferFormat {
+    Csv,
+    Jsonl,
+    Ics,
+}
+
+impl TransferFormat {
+    /// MIME-тип файла.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TransferFormat::Csv => "text/csv; charset=utf-8",
+            TransferFormat::Jsonl => "application/x-ndjson",
+            TransferFormat::Ics => "text/calendar; charset=utf-8",
+        }
+    }
+
+    /// Расширение имени файла.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TransferFormat::Csv => "csv",
+            TransferFormat::Jsonl => "jsonl",
+            TransferFormat::Ics => "ics",
+        }
+    }
+}
+
+/// Параметры импорта и экспорта (`/tasks/export`, `/tasks/import`).
+///
+/// - `format` — формат файла.
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct TransferQuery {
+    pub format: TransferFormat,
+}
+
+/// Строка экспорта задачи (CSV и JSON Lines).
+///
+/// Те же поля принимает импорт; `id` и `parent_id` при импорте ссылаются
+/// на строки того же файла, `created_at` и `completed_at` игнорируются.
+///
+/// - `id` — идентификатор задачи.
+/// - `title` — заголовок.
+/// - `description` — описание.
+/// - `status` — статус.
+/// - `priority` — приоритет.
+/// - `due_at` — срок выполнения.
+/// - `estimated_minutes` — оценка трудозатрат в минутах.
+/// - `recurrence` — правило повторения (RRULE).
+/// - `parent_id` — родительская задача.
+/// - `project_id` — проект.
+/// - `created_at` — дата создания.
+/// - `completed_at` — дата выполнения.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TaskExportDto {
+    pub id: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
+    pub due_at: Option<DateTime<Utc>>,
+    pub estimated_minutes: Option<i32>,
+    pub recurrence: Option<String>,
+    pub parent_id: Option<i32>,
+    pub project_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub completed_at: Option<NaiveDateTime>,
+}
+impl TaskExportDto {
+    pub fn from(model: Task) -> TaskExportDto {
+        Self {
+            id: model.id,
+            title: model.title,
+            description: model.description,
+            status: model.status,
+            priority: model.priority,
+            due_at: model.due_at,
+            estimated_minutes: model.estimated_minutes,
+            recurrence: model.recurrence,
+            parent_id: model.parent_id,
+            project_id: model.project_id,
+            created_at: model.created_at,
+            completed_at: model.completed_at,
+        }
+    }
+}
+
+/// Поля задачи из строки импорта.
+///
+/// Проверяются так же, как `TaskCreateDto`, кроме правила «срок не в прошлом»:
+/// импорт восстанавливает и просроченные, и выполненные задачи из экспорта.
+#[derive(Clone, Deserialize, Validate)]
+pub struct TaskImportDto {
+    #[validate(length(
+        min = 3,
+        max = 100,
+        message = "Title must be between 3 and 100 characters"
+    ))]
+    pub title: String,
+    #[validate(length(
+        max = 500,
+        message = "Description must not exceed 500 characters"
+    ))]
+    pub description: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[validate(range(min = 1, message = "Estimated minutes must be positive"))]
+    pub estimated_minutes: Option<i32>,
+    pub parent_id: Option<i32>,
+    #[validate(custom(function = "validate_recurrence"))]
+    pub recurrence: Option<String>,
+    pub project_id: Option<i32>,
+}
+impl TaskImportDto {
+    /// Преобразование в `TaskCreateDto` для создания задачи.
+    pub fn into_create_dto(self) -> TaskCreateDto {
+        TaskCreateDto {
+            title: self.title,
+            description: self.description,
+            due_at: self.due_at,
+            priority: self.priority,
+            estimated_minutes: self.estimated_minutes,
+            parent_id: self.parent_id,
+            recurrence: self.recurrence,
+            project_id: self.project_id,
+        }
+    }
+}
+
+/// Ошибка строки импорта.
+///
+/// - `row` — номер строки файла (для iCalendar — порядковый номер `VTODO`).
+/// - `code` — HTTP-код, который вернул бы запрос с этой строкой.
+/// - `message` — текст ошибки.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TaskImportErrorDto {
+    pub row: usize,
+    pub code: u16,
+    pub message: String,
+}
+
+/// DTO результата импорта (`POST /tasks/import`).
+///
+/// - `imported` — количество созданных задач.
+/// - `task_ids` — идентификаторы созданных задач.
+/// - `errors` — ошибки строк, которые не были импортированы.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TaskImportResultDto {
+    pub imported: usize,
+    pub task_ids: Vec<i32>,
+    pub errors: Vec<TaskImportErrorDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn import_accepts_past_due_date() {
+        let dto: TaskImportDto = serde_json::from_value(json!({
+            "title": "Overdue task",
+            "due_at": "2020-01-01T09:00:00Z",
+            "recurrence": "FREQ=WEEKLY;BYDAY=MO",
+        }))
+        .unwrap();
+        assert!(dto.validate().is_ok());
+
+        let create = dto.into_create_dto();
+        assert_eq!(create.due_at.unwrap().to_rfc3339(), "2020-01-01T09:00:00+00:00");
+        assert!(create.validate().is_err());
+    }
+
+    #[test]
+    fn import_keeps_format_checks() {
+        let dto: TaskImportDto = serde_json::from_value(json!({
+            "title": "ab",
+            "estimated_minutes": 0,
+            "recurrence": "FREQ=WEEKLY;BYDAY=Éa",
+        }))
+        .unwrap();
+        let errors = dto.validate().unwrap_err();
+        let fields = errors.field_errors();
+
+        assert!(fields.contains_key("title"));
+        assert!(fields.contains_key("estimated_minutes"));
+        assert!(fields.contains_key("recurrence"));
+    }
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Модель вложения задачи (таблица `task_attachments`).
+///
+/// Содержимое файла хранится во внешнем хранилище (`FileStorage`) по ключу `storage_key`.
+///
+/// - `id` — уникальный идентификатор вложения.
+/// - `task_id` — задача.
+/// - `user_id` — пользователь, загрузивший файл.
+/// - `file_name` — исходное имя файла.
+/// - `content_type` — MIME-тип.
+/// - `size_bytes` — размер в байтах.
+/// - `checksum_sha256` — SHA-256 содержимого (hex).
+/// - `storage_key` — ключ объекта в хранилище.
+/// - `created_at` — дата загрузки.
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Attachment {
+    pub id: i32,
+    pub task_id: i32,
+    pub user_id: i32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub checksum_sha256: String,
+    pub storage_key: String,
+    pub created_at: NaiveDateTime,
+}
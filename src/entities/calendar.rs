@@ -0,0 +1,19 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Модель календарной ленты, соответствующая таблице `calendar_feeds`.
+///
+/// У пользователя не больше одной ленты. Сам токен не хранится:
+/// по нему вычисляется `token_hash`, новый токен отзывает прежний.
+///
+/// - `user_id` — ID пользователя, владельца ленты.
+/// - `token_hash` — SHA-256 секретного токена (hex).
+/// - `created_at` — дата выпуска текущего токена.
+/// - `last_accessed_at` — дата последнего обращения к ленте (может отсутствовать).
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CalendarFeed {
+    pub user_id: i32,
+    pub token_hash: String,
+    pub created_at: NaiveDateTime,
+    pub last_accessed_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,23 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Модель колонки доски, соответствующая таблице `board_columns`.
+///
+/// Колонки принадлежат проекту; порядок колонок и задач внутри колонки
+/// задаётся лексикографическими ключами `rank`.
+///
+/// - `id` — уникальный идентификатор колонки.
+/// - `project_id` — проект, которому принадлежит доска.
+/// - `name` — название колонки.
+/// - `rank` — ключ порядка колонки на доске.
+/// - `created_at` — дата создания колонки.
+/// - `updated_at` — дата последнего обновления (может отсутствовать).
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BoardColumn {
+    pub id: i32,
+    pub project_id: i32,
+    pub name: String,
+    pub rank: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,46 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Модель комментария к задаче (таблица `task_comments`).
+///
+/// - `id` — уникальный идентификатор комментария.
+/// - `task_id` — задача, к которой относится комментарий.
+/// - `user_id` — автор комментария.
+/// - `parent_id` — комментарий, на который дан ответ (`None` — корневой комментарий).
+/// - `body` — текст в формате Markdown.
+/// - `created_at` — дата создания.
+/// - `updated_at` — дата последнего изменения текста.
+/// - `deleted_at` — дата удаления (мягкое удаление).
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Comment {
+    pub id: i32,
+    pub task_id: i32,
+    pub user_id: i32,
+    pub parent_id: Option<i32>,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+/// Комментарий вместе с именем автора (`users.user_name`).
+#[derive(Clone, sqlx::FromRow)]
+pub struct CommentWithAuthor {
+    #[sqlx(flatten)]
+    pub comment: Comment,
+    pub author_name: String,
+}
+
+/// Предыдущая версия текста комментария (таблица `task_comment_revisions`).
+///
+/// - `id` — уникальный идентификатор версии.
+/// - `comment_id` — комментарий.
+/// - `body` — текст до изменения.
+/// - `created_at` — момент изменения.
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CommentRevision {
+    pub id: i32,
+    pub comment_id: i32,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+}
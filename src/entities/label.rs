@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Модель метки, соответствующая таблице `labels`.
+///
+/// Метки принадлежат пользователю и назначаются его задачам через таблицу `task_labels`.
+///
+/// - `id` — уникальный идентификатор метки.
+/// - `user_id` — ID пользователя, владельца метки.
+/// - `name` — название метки (уникально в пределах пользователя без учёта регистра).
+/// - `colour` — цвет в формате `#rrggbb`.
+/// - `created_at` — дата создания метки.
+/// - `updated_at` — дата последнего обновления (может отсутствовать).
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Label {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub colour: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
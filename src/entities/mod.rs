@@ -1,2 +1,13 @@
 pub mod user;
-pub mod task;
\ No newline at end of file
+pub mod task;
+pub mod label;
+pub mod comment;
+pub mod attachment;
+pub mod series;
+pub mod project;
+pub mod column;
+pub mod search;
+pub mod calendar;
+pub mod time_entry;
+pub mod refresh_token;
+pub mod session;
\ No newline at end of file
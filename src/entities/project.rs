@@ -0,0 +1,81 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Роль участника проекта (тип `project_role` в базе данных).
+///
+/// - `Owner` — управляет проектом и участниками, работает с задачами.
+/// - `Editor` — создаёт и изменяет задачи проекта.
+/// - `Viewer` — только просматривает задачи проекта.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "project_role", rename_all = "snake_case")]
+pub enum ProjectRole {
+    Owner,
+    Editor,
+    Viewer,
+}
+
+impl ProjectRole {
+    /// Строковое имя роли (совпадает со значением в базе и JSON).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectRole::Owner => "owner",
+            ProjectRole::Editor => "editor",
+            ProjectRole::Viewer => "viewer",
+        }
+    }
+
+    /// Может ли роль изменять задачи проекта.
+    pub fn can_edit(&self) -> bool {
+        matches!(self, ProjectRole::Owner | ProjectRole::Editor)
+    }
+}
+
+impl fmt::Display for ProjectRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Модель проекта, соответствующая таблице `projects`.
+///
+/// Проект объединяет задачи, доступ к которым определяется участием в проекте.
+///
+/// - `id` — уникальный идентификатор проекта.
+/// - `name` — название проекта.
+/// - `description` — описание проекта (может отсутствовать).
+/// - `created_at` — дата создания проекта.
+/// - `updated_at` — дата последнего обновления (может отсутствовать).
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Project {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Проект вместе с ролью текущего пользователя в нём.
+#[derive(Clone, sqlx::FromRow)]
+pub struct ProjectWithRole {
+    #[sqlx(flatten)]
+    pub project: Project,
+    pub role: ProjectRole,
+}
+
+/// Модель участника проекта (таблица `project_members` с именем пользователя).
+///
+/// - `project_id` — идентификатор проекта.
+/// - `user_id` — идентификатор пользователя.
+/// - `user_name` — имя пользователя.
+/// - `role` — роль в проекте.
+/// - `created_at` — дата добавления в проект.
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ProjectMember {
+    pub project_id: i32,
+    pub user_id: i32,
+    pub user_name: String,
+    pub role: ProjectRole,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Модель refresh-токена, соответствующая таблице `refresh_tokens`.
+///
+/// Каждый вход начинает новое семейство; при обновлении токен помечается
+/// использованным и заменяется следующим токеном того же семейства.
+///
+/// - `id` — уникальный идентификатор записи.
+/// - `user_id` — ID пользователя, владельца токена.
+/// - `family_id` — семейство (цепочка ротаций от одного входа).
+/// - `token_hash` — SHA-256 токена (hex); сам токен не хранится.
+/// - `expires_at` — срок действия токена.
+/// - `created_at` — дата выпуска.
+/// - `used_at` — дата обмена на новую пару токенов (может отсутствовать).
+/// - `revoked_at` — дата отзыва (может отсутствовать).
+/// - `access_jti` — `jti` JWT, выданного вместе с токеном.
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RefreshToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub access_jti: Option<Uuid>,
+}
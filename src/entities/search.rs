@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// Результат полнотекстового поиска: задача или комментарий к ней.
+///
+/// - `task_id` — найденная задача (или задача найденного комментария).
+/// - `comment_id` — найденный комментарий (`None` — совпадение в самой задаче).
+/// - `title` — заголовок задачи.
+/// - `snippet` — фрагмент текста с выделенными совпадениями.
+/// - `rank` — релевантность (`ts_rank`).
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SearchHit {
+    pub task_id: i32,
+    pub comment_id: Option<i32>,
+    pub title: String,
+    pub snippet: String,
+    pub rank: f32,
+}
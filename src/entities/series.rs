@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Модель серии повторяющихся задач, соответствующая таблице `task_series`.
+///
+/// Каждое вхождение серии — обычная задача с `series_id`; новые вхождения
+/// копируются с последнего по сроку вхождения серии.
+///
+/// - `id` — уникальный идентификатор серии.
+/// - `user_id` — ID пользователя, владельца серии.
+/// - `recurrence` — нормализованное правило повторения (RRULE).
+/// - `starts_at` — начало расписания (`DTSTART`), срок первого вхождения.
+/// - `materialized_until` — срок последнего созданного вхождения.
+/// - `created_at` — дата создания серии.
+/// - `finished_at` — когда у серии не осталось вхождений (может отсутствовать).
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TaskSeries {
+    pub id: i32,
+    pub user_id: i32,
+    pub recurrence: String,
+    pub starts_at: DateTime<Utc>,
+    pub materialized_until: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Модель сессии, соответствующая таблице `sessions`.
+///
+/// Сессия начинается входом и объединяет цепочку refresh-токенов
+/// (`refresh_tokens.family_id`); её идентификатор передаётся в JWT (claim `sid`).
+///
+/// - `id` — уникальный идентификатор сессии.
+/// - `user_id` — ID пользователя, владельца сессии.
+/// - `user_agent` — заголовок `User-Agent` при входе.
+/// - `ip_address` — IP-адрес клиента при входе.
+/// - `created_at` — дата входа.
+/// - `last_seen_at` — дата последнего запроса (обновляется не чаще раза в минуту).
+/// - `expires_at` — срок действия последнего refresh-токена сессии.
+/// - `revoked_at` — дата завершения сессии (может отсутствовать).
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: i32,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<NaiveDateTime>,
+}
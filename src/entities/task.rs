@@ -1,4 +1,96 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Serialize, Deserialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Статус задачи (тип `task_status` в базе данных).
+///
+/// - `Todo` — задача создана, работа не начата.
+/// - `InProgress` — задача в работе.
+/// - `Blocked` — работа над задачей заблокирована.
+/// - `Done` — задача выполнена.
+/// - `Cancelled` — задача отменена.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "task_status", rename_all = "snake_case")]
+pub enum TaskStatus {
+    Todo,
+    InProgress,
+    Blocked,
+    Done,
+    Cancelled,
+}
+
+impl TaskStatus {
+    /// Все статусы в порядке объявления.
+    pub const ALL: [TaskStatus; 5] = [
+        TaskStatus::Todo,
+        TaskStatus::InProgress,
+        TaskStatus::Blocked,
+        TaskStatus::Done,
+        TaskStatus::Cancelled,
+    ];
+
+    /// Строковое имя статуса (совпадает со значением в базе и JSON).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Todo => "todo",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Blocked => "blocked",
+            TaskStatus::Done => "done",
+            TaskStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl FromStr for TaskStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        TaskStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == value)
+            .ok_or_else(|| format!("unknown task status `{}`", value))
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Приоритет задачи (тип `task_priority` в базе данных).
+///
+/// По умолчанию — `Medium`. Порядок вариантов соответствует возрастанию приоритета.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "task_priority", rename_all = "snake_case")]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+impl TaskPriority {
+    /// Строковое имя приоритета (совпадает со значением в базе и JSON).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskPriority::Low => "low",
+            TaskPriority::Medium => "medium",
+            TaskPriority::High => "high",
+            TaskPriority::Urgent => "urgent",
+        }
+    }
+}
+
+impl fmt::Display for TaskPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Модель задачи, соответствующая таблице в базе данных.
 ///
@@ -8,10 +100,74 @@ use serde::{Serialize, Deserialize};
 /// - `title` — заголовок задачи.
 /// - `description` — описание задачи (может отсутствовать).
 /// - `user_id` — идентификатор пользователя, которому принадлежит задача.
+/// - `created_at` — дата создания задачи.
+/// - `updated_at` — дата последнего обновления (может отсутствовать).
+/// - `status` — текущий статус задачи.
+/// - `completed_at` — дата перехода в статус `done` (если задача выполнена).
+/// - `due_at` — срок выполнения (с часовым поясом, может отсутствовать).
+/// - `priority` — приоритет задачи.
+/// - `estimated_minutes` — оценка трудозатрат в минутах (может отсутствовать).
+/// - `parent_id` — идентификатор родительской задачи (для подзадач).
+/// - `recurrence` — правило повторения в формате RRULE (может отсутствовать).
+/// - `series_id` — серия повторяющейся задачи (`task_series`).
+/// - `project_id` — проект задачи (`None` — личная задача владельца).
+/// - `column_id` — колонка доски проекта (`None` — задача не размещена на доске).
+/// - `rank` — ключ порядка задачи внутри колонки.
+/// - `deleted_at` — дата перемещения в корзину (`None` — задача не удалена).
 #[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Task {
     pub id: i32,
     pub title: String,
     pub description: Option<String>,
     pub user_id: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+    pub status: TaskStatus,
+    pub completed_at: Option<NaiveDateTime>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: TaskPriority,
+    pub estimated_minutes: Option<i32>,
+    pub parent_id: Option<i32>,
+    pub recurrence: Option<String>,
+    pub series_id: Option<i32>,
+    pub project_id: Option<i32>,
+    pub column_id: Option<i32>,
+    pub rank: Option<String>,
+    pub deleted_at: Option<NaiveDateTime>,
 }
+
+/// Наблюдатель задачи (строка `task_watchers` с именем пользователя).
+///
+/// - `task_id` — задача.
+/// - `user_id` — наблюдатель.
+/// - `user_name` — имя наблюдателя.
+/// - `created_at` — дата подписки.
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TaskWatcher {
+    pub task_id: i32,
+    pub user_id: i32,
+    pub user_name: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// Событие истории задачи (строка `task_events` с именем автора).
+///
+/// - `id` — порядковый номер события.
+/// - `task_id` — задача.
+/// - `actor_id` — автор изменения (`None` — фоновая задача или удалённый пользователь).
+/// - `actor_name` — имя автора.
+/// - `action` — тип события (`created`, `updated`, `status_changed`, ...).
+/// - `changes` — подробности изменения (для полей — `{"поле": {"from": ..., "to": ...}}`).
+/// - `request_id` — идентификатор HTTP-запроса, выполнившего изменение.
+/// - `created_at` — дата события.
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TaskEvent {
+    pub id: i64,
+    pub task_id: i32,
+    pub actor_id: Option<i32>,
+    pub actor_name: Option<String>,
+    pub action: String,
+    pub changes: serde_json::Value,
+    pub request_id: Option<String>,
+    pub created_at: NaiveDateTime,
+}
\ No newline at end of file
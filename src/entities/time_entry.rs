@@ -0,0 +1,41 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Модель записи учёта времени, соответствующая таблице `time_entries`.
+///
+/// Запись создаётся запуском таймера (пока он идёт, `ended_at` пуст)
+/// или вручную за прошедший интервал.
+///
+/// - `id` — уникальный идентификатор записи.
+/// - `task_id` — задача, на которую учтено время.
+/// - `user_id` — ID пользователя, владельца записи.
+/// - `started_at` — начало интервала.
+/// - `ended_at` — конец интервала (`None`, пока таймер запущен).
+/// - `note` — комментарий к записи.
+/// - `created_at` — дата создания записи.
+/// - `updated_at` — дата последнего изменения (может отсутствовать).
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TimeEntry {
+    pub id: i32,
+    pub task_id: i32,
+    pub user_id: i32,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Строка отчёта по учтённому времени.
+///
+/// - `period` — первый день периода (при группировке по дням и неделям).
+/// - `project_id` — проект (при группировке по проектам; `None` — личные задачи).
+/// - `seconds` — учтённое время в секундах.
+/// - `entries` — количество записей.
+#[derive(Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TimeReportRow {
+    pub period: Option<NaiveDate>,
+    pub project_id: Option<i32>,
+    pub seconds: i64,
+    pub entries: i64,
+}
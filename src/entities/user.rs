@@ -1,4 +1,4 @@
-use chrono::{NaiveDateTime};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Модель пользователя, соответствующая таблице в базе данных.
@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 /// - `created_at` — дата создания пользователя.
 /// - `updated_at` — дата последнего обновления (может отсутствовать).
 /// - `is_active` — статус активности пользователя (1 — активен, 0 — неактивен).
+/// - `tokens_revoked_at` — момент выхода со всех устройств: более ранние JWT недействительны.
 #[derive(Clone, Deserialize, Serialize, sqlx::FromRow)]
 pub struct User {
     pub id: i32,
@@ -25,4 +26,5 @@ pub struct User {
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
     pub is_active: i32,
+    pub tokens_revoked_at: Option<DateTime<Utc>>,
 }
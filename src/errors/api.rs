@@ -1,4 +1,9 @@
-use crate::errors::{db::DbError, token::TokenError, user::UserError};
+use crate::errors::{
+    attachment::AttachmentError, calendar::CalendarError, column::ColumnError, comment::CommentError, db::DbError,
+    label::LabelError, mail::MailError, password_reset::PasswordResetError, project::ProjectError, search::SearchError,
+    session::SessionError, storage::StorageError, task::TaskError, time_entry::TimeEntryError, token::TokenError,
+    transfer::TransferError, user::UserError,
+};
 use axum::response::{IntoResponse, Response};
 use thiserror::Error;
 
@@ -8,7 +13,22 @@ use thiserror::Error;
 ///
 /// - `TokenError` — ошибки, связанные с JWT-токенами.
 /// - `UserError` — ошибки, связанные с пользователями.
+/// - `TaskError` — ошибки, связанные с задачами.
+/// - `LabelError` — ошибки, связанные с метками.
+/// - `CommentError` — ошибки, связанные с комментариями.
+/// - `AttachmentError` — ошибки, связанные с вложениями.
+/// - `ProjectError` — ошибки, связанные с проектами.
+/// - `ColumnError` — ошибки, связанные с колонками доски.
+/// - `SearchError` — ошибки полнотекстового поиска.
+/// - `TransferError` — ошибки импорта и экспорта задач.
+/// - `CalendarError` — ошибки календарной ленты.
+/// - `TimeEntryError` — ошибки учёта времени.
+/// - `SessionError` — ошибки, связанные с сессиями.
+/// - `PasswordResetError` — ошибки сброса пароля.
 /// - `DbError` — ошибки при работе с базой данных.
+/// - `StorageError` — ошибки хранилища файлов.
+/// - `MailError` — ошибки отправки писем.
+#[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug)]
 pub enum ApiError {
     #[error(transparent)]
@@ -16,7 +36,35 @@ pub enum ApiError {
     #[error(transparent)]
     UserError(#[from] UserError),
     #[error(transparent)]
+    TaskError(#[from] TaskError),
+    #[error(transparent)]
+    LabelError(#[from] LabelError),
+    #[error(transparent)]
+    CommentError(#[from] CommentError),
+    #[error(transparent)]
+    AttachmentError(#[from] AttachmentError),
+    #[error(transparent)]
+    ProjectError(#[from] ProjectError),
+    #[error(transparent)]
+    ColumnError(#[from] ColumnError),
+    #[error(transparent)]
+    SearchError(#[from] SearchError),
+    #[error(transparent)]
+    TransferError(#[from] TransferError),
+    #[error(transparent)]
+    CalendarError(#[from] CalendarError),
+    #[error(transparent)]
+    TimeEntryError(#[from] TimeEntryError),
+    #[error(transparent)]
+    SessionError(#[from] SessionError),
+    #[error(transparent)]
+    PasswordResetError(#[from] PasswordResetError),
+    #[error(transparent)]
     DbError(#[from] DbError),
+    #[error(transparent)]
+    StorageError(#[from] StorageError),
+    #[error(transparent)]
+    MailError(#[from] MailError),
 }
 
 /// Реализация преобразования `ApiError` в HTTP-ответ.
@@ -27,7 +75,21 @@ impl IntoResponse for ApiError {
         match self {
             ApiError::TokenError(error) => error.into_response(),
             ApiError::UserError(error) => error.into_response(),
+            ApiError::TaskError(error) => error.into_response(),
+            ApiError::LabelError(error) => error.into_response(),
+            ApiError::CommentError(error) => error.into_response(),
+            ApiError::AttachmentError(error) => error.into_response(),
+            ApiError::ProjectError(error) => error.into_response(),
+            ApiError::ColumnError(error) => error.into_response(),
+            ApiError::SearchError(error) => error.into_response(),
+            ApiError::TransferError(error) => error.into_response(),
+            ApiError::CalendarError(error) => error.into_response(),
+            ApiError::TimeEntryError(error) => error.into_response(),
+            ApiError::SessionError(error) => error.into_response(),
+            ApiError::PasswordResetError(error) => error.into_response(),
             ApiError::DbError(error) => error.into_response(),
+            ApiError::StorageError(error) => error.into_response(),
+            ApiError::MailError(error) => error.into_response(),
         }
     }
 }
@@ -0,0 +1,53 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки, связанные с вложениями (`AttachmentError`).
+///
+/// - `AttachmentNotFound` — вложение не найдено или относится к другой задаче.
+/// - `MissingFile` — в multipart-запросе нет поля `file`.
+/// - `InvalidMultipart` — тело запроса не разбирается как `multipart/form-data`.
+/// - `FileTooLarge` — размер файла превышает `ATTACHMENT_MAX_BYTES`.
+/// - `BodyTooLarge` — тело запроса превышает лимит загрузки.
+/// - `UnsupportedMediaType` — MIME-тип файла не входит в `ATTACHMENT_ALLOWED_TYPES`.
+#[derive(Error, Debug)]
+pub enum AttachmentError {
+    #[error("Attachment not found")]
+    AttachmentNotFound,
+    #[error("Multipart field `file` is required")]
+    MissingFile,
+    #[error("Invalid multipart body: {0}")]
+    InvalidMultipart(String),
+    #[error("File exceeds the limit of {0} bytes")]
+    FileTooLarge(u64),
+    #[error("Request body exceeds the limit of {0} bytes")]
+    BodyTooLarge(usize),
+    #[error("Content type `{0}` is not allowed")]
+    UnsupportedMediaType(String),
+}
+
+/// Реализация преобразования `AttachmentError` в HTTP-ответ.
+///
+/// - `AttachmentNotFound` → 404 Not Found
+/// - `MissingFile` → 400 Bad Request
+/// - `InvalidMultipart` → 400 Bad Request
+/// - `FileTooLarge` → 413 Payload Too Large
+/// - `BodyTooLarge` → 413 Payload Too Large
+/// - `UnsupportedMediaType` → 415 Unsupported Media Type
+impl IntoResponse for AttachmentError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            AttachmentError::AttachmentNotFound => StatusCode::NOT_FOUND,
+            AttachmentError::MissingFile => StatusCode::BAD_REQUEST,
+            AttachmentError::InvalidMultipart(_) => StatusCode::BAD_REQUEST,
+            AttachmentError::FileTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AttachmentError::BodyTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AttachmentError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
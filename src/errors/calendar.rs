@@ -0,0 +1,28 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки, связанные с календарной лентой (`CalendarError`).
+///
+/// - `FeedNotFound` — лента не создана, отозвана или токен в URL неверен.
+#[derive(Error, Debug)]
+pub enum CalendarError {
+    #[error("Calendar feed not found")]
+    FeedNotFound,
+}
+
+/// Реализация преобразования `CalendarError` в HTTP-ответ.
+///
+/// - `FeedNotFound` → 404 Not Found
+impl IntoResponse for CalendarError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            CalendarError::FeedNotFound => StatusCode::NOT_FOUND,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
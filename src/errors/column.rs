@@ -0,0 +1,34 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки, связанные с колонками доски (`ColumnError`).
+///
+/// - `ColumnNotFound` — колонка не найдена в проекте.
+/// - `InvalidPosition` — опорный элемент не находится в целевом списке
+///   (или совпадает с перемещаемым).
+#[derive(Error, Debug)]
+pub enum ColumnError {
+    #[error("Column not found")]
+    ColumnNotFound,
+    #[error("Invalid position: {0}")]
+    InvalidPosition(String),
+}
+
+/// Реализация преобразования `ColumnError` в HTTP-ответ.
+///
+/// - `ColumnNotFound` → 404 Not Found
+/// - `InvalidPosition` → 409 Conflict
+impl IntoResponse for ColumnError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            ColumnError::ColumnNotFound => StatusCode::NOT_FOUND,
+            ColumnError::InvalidPosition(_) => StatusCode::CONFLICT,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
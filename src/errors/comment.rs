@@ -0,0 +1,43 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки, связанные с комментариями (`CommentError`).
+///
+/// - `CommentNotFound` — комментарий не найден, удалён или относится к другой задаче.
+/// - `ForbiddenCommentAccess` — изменять и удалять комментарий может только его автор.
+/// - `InvalidParentComment` — ответ на комментарий другой задачи или на удалённый комментарий.
+/// - `InvalidCursor` — повреждённый курсор пагинации.
+#[derive(Error, Debug)]
+pub enum CommentError {
+    #[error("Comment not found")]
+    CommentNotFound,
+    #[error("Only the author can modify this comment")]
+    ForbiddenCommentAccess,
+    #[error("Cannot reply to this comment")]
+    InvalidParentComment,
+    #[error("Malformed cursor")]
+    InvalidCursor,
+}
+
+/// Реализация преобразования `CommentError` в HTTP-ответ.
+///
+/// - `CommentNotFound` → 404 Not Found
+/// - `ForbiddenCommentAccess` → 403 Forbidden
+/// - `InvalidParentComment` → 400 Bad Request
+/// - `InvalidCursor` → 400 Bad Request
+impl IntoResponse for CommentError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            CommentError::CommentNotFound => StatusCode::NOT_FOUND,
+            CommentError::ForbiddenCommentAccess => StatusCode::FORBIDDEN,
+            CommentError::InvalidParentComment => StatusCode::BAD_REQUEST,
+            CommentError::InvalidCursor => StatusCode::BAD_REQUEST,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
@@ -33,3 +33,18 @@ impl IntoResponse for DbError {
         ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
     }
 }
+
+/// Преобразование ошибки `sqlx` в `DbError`.
+///
+/// - Код `23505` (unique_violation) → `UniqueConstraintViolation`
+/// - Остальные ошибки → `SomethingWentWrong`
+impl From<sqlx::Error> for DbError {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::Database(e) if e.code().as_deref() == Some("23505") => {
+                DbError::UniqueConstraintViolation(e.to_string())
+            }
+            e => DbError::SomethingWentWrong(e.to_string()),
+        }
+    }
+}
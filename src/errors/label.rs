@@ -0,0 +1,38 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки, связанные с метками (`LabelError`).
+///
+/// - `LabelNotFound` — метка не найдена.
+/// - `LabelAlreadyExists` — у пользователя уже есть метка с таким названием.
+/// - `ForbiddenLabelAccess` — попытка доступа к метке другого пользователя.
+#[derive(Error, Debug)]
+pub enum LabelError {
+    #[error("Label not found")]
+    LabelNotFound,
+    #[error("Label with this name already exists")]
+    LabelAlreadyExists,
+    #[error("Access to this label is forbidden")]
+    ForbiddenLabelAccess,
+}
+
+/// Реализация преобразования `LabelError` в HTTP-ответ.
+///
+/// - `LabelNotFound` → 404 Not Found
+/// - `LabelAlreadyExists` → 409 Conflict
+/// - `ForbiddenLabelAccess` → 403 Forbidden
+impl IntoResponse for LabelError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            LabelError::LabelNotFound => StatusCode::NOT_FOUND,
+            LabelError::LabelAlreadyExists => StatusCode::CONFLICT,
+            LabelError::ForbiddenLabelAccess => StatusCode::FORBIDDEN,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
@@ -0,0 +1,35 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки отправки писем (`MailError`).
+///
+/// - `Delivery` — письмо не удалось передать почтовому транспорту.
+#[derive(Error, Debug)]
+pub enum MailError {
+    #[error("Mail delivery failed: {0}")]
+    Delivery(String),
+}
+
+/// Реализация преобразования `MailError` в HTTP-ответ.
+///
+/// - `Delivery` → 500 Internal Server Error
+impl IntoResponse for MailError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            MailError::Delivery(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
+
+/// Преобразование ошибки ввода-вывода в `MailError`.
+impl From<std::io::Error> for MailError {
+    fn from(error: std::io::Error) -> Self {
+        MailError::Delivery(error.to_string())
+    }
+}
@@ -4,3 +4,17 @@ pub(crate) mod request;
 pub(crate) mod token;
 pub(crate) mod user;
 pub(crate) mod task;
+
+pub(crate) mod label;
+pub(crate) mod comment;
+pub(crate) mod storage;
+pub(crate) mod attachment;
+pub(crate) mod project;
+pub(crate) mod column;
+pub(crate) mod search;
+pub(crate) mod transfer;
+pub(crate) mod calendar;
+pub(crate) mod time_entry;
+pub(crate) mod session;
+pub(crate) mod mail;
+pub(crate) mod password_reset;
\ No newline at end of file
@@ -0,0 +1,28 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки сброса пароля (`PasswordResetError`).
+///
+/// - `InvalidResetToken` — токен сброса неизвестен, уже использован или истёк.
+#[derive(Error, Debug)]
+pub enum PasswordResetError {
+    #[error("Password reset token is invalid or expired")]
+    InvalidResetToken,
+}
+
+/// Реализация преобразования `PasswordResetError` в HTTP-ответ.
+///
+/// - `InvalidResetToken` → 400 Bad Request
+impl IntoResponse for PasswordResetError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            PasswordResetError::InvalidResetToken => StatusCode::BAD_REQUEST,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
@@ -0,0 +1,48 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки, связанные с проектами (`ProjectError`).
+///
+/// - `ProjectNotFound` — проект не найден.
+/// - `ForbiddenProjectAccess` — пользователь не участник проекта или его роли недостаточно.
+/// - `MemberNotFound` — пользователь не является участником проекта.
+/// - `MemberAlreadyExists` — пользователь уже участник проекта.
+/// - `LastOwner` — операция оставила бы проект без владельца.
+#[derive(Error, Debug)]
+pub enum ProjectError {
+    #[error("Project not found")]
+    ProjectNotFound,
+    #[error("Access to this project is forbidden")]
+    ForbiddenProjectAccess,
+    #[error("Project member not found")]
+    MemberNotFound,
+    #[error("User is already a member of this project")]
+    MemberAlreadyExists,
+    #[error("Project must have at least one owner")]
+    LastOwner,
+}
+
+/// Реализация преобразования `ProjectError` в HTTP-ответ.
+///
+/// - `ProjectNotFound` → 404 Not Found
+/// - `ForbiddenProjectAccess` → 403 Forbidden
+/// - `MemberNotFound` → 404 Not Found
+/// - `MemberAlreadyExists` → 409 Conflict
+/// - `LastOwner` → 409 Conflict
+impl IntoResponse for ProjectError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            ProjectError::ProjectNotFound => StatusCode::NOT_FOUND,
+            ProjectError::ForbiddenProjectAccess => StatusCode::FORBIDDEN,
+            ProjectError::MemberNotFound => StatusCode::NOT_FOUND,
+            ProjectError::MemberAlreadyExists => StatusCode::CONFLICT,
+            ProjectError::LastOwner => StatusCode::CONFLICT,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
@@ -1,11 +1,13 @@
 use crate::response::api::ApiErrorResponse;
 use async_trait::async_trait;
 use axum::{
-    extract::{FromRequest, Request, Json},
+    extract::{FromRequest, FromRequestParts, Query, Request, Json},
     body::Body,
+    http::request::Parts,
     response::{IntoResponse, Response},
 };
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use thiserror::Error;
 use validator::Validate;
 
@@ -13,6 +15,9 @@ use validator::Validate;
 ///
 /// - `ValidationError` — JSON-десериализация успешна, но валидация не пройдена.
 /// - `JsonParseError` — тело запроса содержит некорректный JSON.
+/// - `QueryParseError` — строка запроса (query string) не разбирается.
+/// - `ValueParseError` — JSON-значение (например, элемент массового запроса) не соответствует структуре.
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Error)]
 pub enum RequestError {
     #[error("Validation error: {0}")]
@@ -20,6 +25,12 @@ pub enum RequestError {
 
     #[error("Invalid JSON payload: {0}")]
     JsonParseError(#[from] axum::extract::rejection::JsonRejection),
+
+    #[error("Invalid query string: {0}")]
+    QueryParseError(#[from] axum::extract::rejection::QueryRejection),
+
+    #[error("Invalid JSON: {0}")]
+    ValueParseError(#[from] serde_json::Error),
 }
 
 /// Обёртка `ValidatedRequest<T>` — валидируемый JSON-запрос.
@@ -43,28 +54,71 @@ where
     S: Send + Sync,
     T: DeserializeOwned + Validate,
 {
-    type Rejection = Response;
+    type Rejection = RequestError;
 
     /// Парсинг JSON и валидация структуры `T`.
     async fn from_request(req: Request<Body>, state: &S) -> Result<Self, Self::Rejection> {
         // 1. Парсим JSON
-        let Json(value) = Json::<T>::from_request(req, state).await.map_err(|err| {
-            ApiErrorResponse::send(400, Some(format!("Invalid JSON: {}", err)))
-        })?;
+        let Json(value) = Json::<T>::from_request(req, state).await?;
 
         // 2. Проверяем валидацию
-        value.validate().map_err(|err| {
-            ApiErrorResponse::send(422, Some(format!("Validation error: {}", err)))
-        })?;
+        value.validate()?;
+
+        Ok(ValidatedRequest(value))
+    }
+}
+
+impl<T> ValidatedRequest<T>
+where
+    T: DeserializeOwned + Validate,
+{
+    /// Разбор и валидация уже прочитанного JSON-значения.
+    ///
+    /// Используется для элементов массовых запросов, где каждый элемент
+    /// проверяется отдельно.
+    ///
+    /// :param value: JSON-значение.
+    /// :return: `ValidatedRequest<T>`, `ValueParseError` или `ValidationError`.
+    pub fn from_value(value: Value) -> Result<Self, RequestError> {
+        let value = serde_json::from_value::<T>(value)?;
+        value.validate()?;
 
         Ok(ValidatedRequest(value))
     }
 }
 
+/// Обёртка `ValidatedQuery<T>` — валидируемые параметры строки запроса.
+///
+/// Аналог `ValidatedRequest<T>` для `Query<T>`.
+///
+/// - Если параметры не разбираются → `QueryParseError` (400 Bad Request).
+/// - Если параметры не проходят валидацию → `ValidationError` (422 Unprocessable Entity).
+#[derive(Debug)]
+pub struct ValidatedQuery<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for ValidatedQuery<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = RequestError;
+
+    /// Парсинг query string и валидация структуры `T`.
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        value.validate()?;
+
+        Ok(ValidatedQuery(value))
+    }
+}
+
 /// Реализация `IntoResponse` для `RequestError`.
 ///
 /// - `ValidationError` → 422 Unprocessable Entity.
 /// - `JsonParseError` → 400 Bad Request.
+/// - `QueryParseError` → 400 Bad Request.
+/// - `ValueParseError` → 400 Bad Request.
 impl IntoResponse for RequestError {
     fn into_response(self) -> Response {
         match self {
@@ -74,6 +128,12 @@ impl IntoResponse for RequestError {
             RequestError::JsonParseError(err) => {
                 ApiErrorResponse::send(400, Some(format!("Invalid JSON: {}", err)))
             }
+            RequestError::QueryParseError(err) => {
+                ApiErrorResponse::send(400, Some(format!("Invalid query string: {}", err)))
+            }
+            RequestError::ValueParseError(err) => {
+                ApiErrorResponse::send(400, Some(format!("Invalid JSON: {}", err)))
+            }
         }
     }
 }
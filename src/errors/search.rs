@@ -0,0 +1,28 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки полнотекстового поиска (`SearchError`).
+///
+/// - `InvalidQuery` — поисковый запрос не содержит искомых слов или составлен некорректно.
+#[derive(Error, Debug)]
+pub enum SearchError {
+    #[error("Invalid search query: {0}")]
+    InvalidQuery(String),
+}
+
+/// Реализация преобразования `SearchError` в HTTP-ответ.
+///
+/// - `InvalidQuery` → 400 Bad Request
+impl IntoResponse for SearchError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            SearchError::InvalidQuery(_) => StatusCode::BAD_REQUEST,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
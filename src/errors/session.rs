@@ -0,0 +1,28 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки, связанные с сессиями (`SessionError`).
+///
+/// - `SessionNotFound` — сессия не найдена, уже завершена или принадлежит другому пользователю.
+#[derive(Error, Debug)]
+pub enum SessionError {
+    #[error("Session not found")]
+    SessionNotFound,
+}
+
+/// Реализация преобразования `SessionError` в HTTP-ответ.
+///
+/// - `SessionNotFound` → 404 Not Found
+impl IntoResponse for SessionError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            SessionError::SessionNotFound => StatusCode::NOT_FOUND,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
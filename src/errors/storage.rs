@@ -0,0 +1,56 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки хранилища файлов (`StorageError`).
+///
+/// - `NotFound` — объект отсутствует в хранилище.
+/// - `Backend` — ошибка файловой системы или S3.
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("Stored file not found")]
+    NotFound,
+    #[error("Storage error: {0}")]
+    Backend(String),
+}
+
+/// Реализация преобразования `StorageError` в HTTP-ответ.
+///
+/// - `NotFound` → 404 Not Found
+/// - `Backend` → 500 Internal Server Error
+impl IntoResponse for StorageError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            StorageError::NotFound => StatusCode::NOT_FOUND,
+            StorageError::Backend(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
+
+/// Преобразование ошибки ввода-вывода в `StorageError`.
+///
+/// - `ErrorKind::NotFound` → `NotFound`
+/// - Остальные ошибки → `Backend`
+impl From<std::io::Error> for StorageError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => StorageError::NotFound,
+            _ => StorageError::Backend(error.to_string()),
+        }
+    }
+}
+
+/// Преобразование ошибки `object_store` в `StorageError`.
+impl From<object_store::Error> for StorageError {
+    fn from(error: object_store::Error) -> Self {
+        match error {
+            object_store::Error::NotFound { .. } => StorageError::NotFound,
+            e => StorageError::Backend(e.to_string()),
+        }
+    }
+}
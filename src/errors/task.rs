@@ -1,3 +1,4 @@
+use crate::entities::task::TaskStatus;
 use crate::response::api::ApiErrorResponse;
 use axum::{
     http::StatusCode,
@@ -12,6 +13,13 @@ use thiserror::Error;
 /// - `TaskNotFound` — задача не найдена.
 /// - `TaskAlreadyExists` — задача с таким названием уже существует.
 /// - `ForbiddenTaskAccess` — попытка доступа к задаче, которая принадлежит другому пользователю.
+/// - `InvalidListQuery` — некорректные параметры сортировки или пагинации списка задач.
+/// - `InvalidStatusTransition` — переход между статусами запрещён правилами `TaskWorkflow`.
+/// - `ParentCycle` — родительская задача является самой задачей или её подзадачей.
+/// - `DependencyCycle` — новая зависимость замыкает цикл в графе зависимостей.
+/// - `InvalidRecurrence` — некорректное правило повторения или повторение без срока.
+/// - `ProjectMismatch` — связываемые задачи относятся к разным проектам.
+/// - `UserWithoutAccess` — назначаемый исполнитель или наблюдатель не имеет доступа к задаче.
 #[derive(Error, Debug)]
 pub enum TaskError {
     #[error("Task not found")]
@@ -20,6 +28,20 @@ pub enum TaskError {
     TaskAlreadyExists,
     #[error("Access to this task is forbidden")]
     ForbiddenTaskAccess,
+    #[error("Invalid list query: {0}")]
+    InvalidListQuery(String),
+    #[error("Cannot transition task from `{from}` to `{to}`")]
+    InvalidStatusTransition { from: TaskStatus, to: TaskStatus },
+    #[error("Task cannot be a subtask of itself or of its own subtasks")]
+    ParentCycle,
+    #[error("Dependency would create a cycle")]
+    DependencyCycle,
+    #[error("Invalid recurrence: {0}")]
+    InvalidRecurrence(String),
+    #[error("Related tasks must belong to the same project")]
+    ProjectMismatch,
+    #[error("User does not have access to this task")]
+    UserWithoutAccess,
 }
 
 /// Реализация преобразования `TaskError` в HTTP-ответ.
@@ -27,12 +49,26 @@ pub enum TaskError {
 /// - `TaskNotFound` → 404 Not Found
 /// - `TaskAlreadyExists` → 400 Bad Request
 /// - `ForbiddenTaskAccess` → 403 Forbidden
+/// - `InvalidListQuery` → 400 Bad Request
+/// - `InvalidStatusTransition` → 409 Conflict
+/// - `ParentCycle` → 409 Conflict
+/// - `DependencyCycle` → 409 Conflict
+/// - `InvalidRecurrence` → 400 Bad Request
+/// - `ProjectMismatch` → 409 Conflict
+/// - `UserWithoutAccess` → 422 Unprocessable Entity
 impl IntoResponse for TaskError {
     fn into_response(self) -> Response {
         let status_code = match self {
             TaskError::TaskNotFound => StatusCode::NOT_FOUND,
             TaskError::TaskAlreadyExists => StatusCode::BAD_REQUEST,
             TaskError::ForbiddenTaskAccess => StatusCode::FORBIDDEN,
+            TaskError::InvalidListQuery(_) => StatusCode::BAD_REQUEST,
+            TaskError::InvalidStatusTransition { .. } => StatusCode::CONFLICT,
+            TaskError::ParentCycle => StatusCode::CONFLICT,
+            TaskError::DependencyCycle => StatusCode::CONFLICT,
+            TaskError::InvalidRecurrence(_) => StatusCode::BAD_REQUEST,
+            TaskError::ProjectMismatch => StatusCode::CONFLICT,
+            TaskError::UserWithoutAccess => StatusCode::UNPROCESSABLE_ENTITY,
         };
 
         ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
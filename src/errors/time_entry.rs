@@ -0,0 +1,53 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки, связанные с учётом времени (`TimeEntryError`).
+///
+/// - `TimeEntryNotFound` — запись не найдена.
+/// - `ForbiddenTimeEntryAccess` — изменять и удалять запись может только её владелец.
+/// - `TimerAlreadyRunning` — у пользователя уже запущен таймер.
+/// - `TimerNotRunning` — таймер по задаче не запущен.
+/// - `InvalidTimeRange` — конец интервала раньше начала или в будущем.
+/// - `InvalidReportRange` — период отчёта пуст или длиннее года.
+#[derive(Error, Debug)]
+pub enum TimeEntryError {
+    #[error("Time entry not found")]
+    TimeEntryNotFound,
+    #[error("Only the owner can modify this time entry")]
+    ForbiddenTimeEntryAccess,
+    #[error("Another timer is already running")]
+    TimerAlreadyRunning,
+    #[error("Timer is not running for this task")]
+    TimerNotRunning,
+    #[error("End time must be after start time and not in the future")]
+    InvalidTimeRange,
+    #[error("Report range must cover between 1 and 366 days")]
+    InvalidReportRange,
+}
+
+/// Реализация преобразования `TimeEntryError` в HTTP-ответ.
+///
+/// - `TimeEntryNotFound` → 404 Not Found
+/// - `ForbiddenTimeEntryAccess` → 403 Forbidden
+/// - `TimerAlreadyRunning` → 409 Conflict
+/// - `TimerNotRunning` → 409 Conflict
+/// - `InvalidTimeRange` → 400 Bad Request
+/// - `InvalidReportRange` → 400 Bad Request
+impl IntoResponse for TimeEntryError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            TimeEntryError::TimeEntryNotFound => StatusCode::NOT_FOUND,
+            TimeEntryError::ForbiddenTimeEntryAccess => StatusCode::FORBIDDEN,
+            TimeEntryError::TimerAlreadyRunning => StatusCode::CONFLICT,
+            TimeEntryError::TimerNotRunning => StatusCode::CONFLICT,
+            TimeEntryError::InvalidTimeRange => StatusCode::BAD_REQUEST,
+            TimeEntryError::InvalidReportRange => StatusCode::BAD_REQUEST,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
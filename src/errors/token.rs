@@ -12,7 +12,11 @@ use thiserror::Error;
 /// - `InvalidToken` — токен повреждён или невалиден.
 /// - `TokenExpired` — срок действия токена истёк.
 /// - `MissingToken` — заголовок Authorization отсутствует или не содержит токен.
+/// - `TokenRevoked` — токен отозван (выход из системы).
 /// - `TokenCreationError` — ошибка при генерации нового токена.
+/// - `InvalidRefreshToken` — refresh-токен неизвестен, истёк или отозван.
+/// - `RefreshTokenReused` — повторное предъявление уже обменянного refresh-токена;
+///   всё семейство токенов отозвано.
 #[derive(Error, Debug)]
 pub enum TokenError {
     #[error("Invalid token")]
@@ -21,8 +25,14 @@ pub enum TokenError {
     TokenExpired,
     #[error("Missing Bearer token")]
     MissingToken,
+    #[error("Token has been revoked")]
+    TokenRevoked,
     #[error("Token error: {0}")]
     TokenCreationError(String),
+    #[error("Invalid refresh token")]
+    InvalidRefreshToken,
+    #[error("Refresh token has already been used, please sign in again")]
+    RefreshTokenReused,
 }
 
 /// Реализация конверсии `TokenError` в HTTP-ответ Axum.
@@ -32,14 +42,20 @@ pub enum TokenError {
 /// - `InvalidToken` → 401 Unauthorized
 /// - `TokenExpired` → 401 Unauthorized
 /// - `MissingToken` → 401 Unauthorized
+/// - `TokenRevoked` → 401 Unauthorized
 /// - `TokenCreationError` → 500 Internal Server Error
+/// - `InvalidRefreshToken` → 401 Unauthorized
+/// - `RefreshTokenReused` → 401 Unauthorized
 impl IntoResponse for TokenError {
     fn into_response(self) -> Response {
         let status_code = match self {
             TokenError::InvalidToken(_) => StatusCode::UNAUTHORIZED,
             TokenError::TokenExpired => StatusCode::UNAUTHORIZED,
             TokenError::MissingToken => StatusCode::UNAUTHORIZED,
+            TokenError::TokenRevoked => StatusCode::UNAUTHORIZED,
             TokenError::TokenCreationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            TokenError::InvalidRefreshToken => StatusCode::UNAUTHORIZED,
+            TokenError::RefreshTokenReused => StatusCode::UNAUTHORIZED,
         };
 
         ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
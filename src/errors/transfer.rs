@@ -0,0 +1,36 @@
+use crate::response::api::ApiErrorResponse;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Ошибки импорта и экспорта задач (`TransferError`).
+///
+/// Ошибки отдельных строк файла не прерывают импорт и попадают в отчёт;
+/// эти ошибки относятся к файлу целиком.
+///
+/// - `InvalidFile` — файл не разбирается в выбранном формате.
+/// - `TooManyRows` — в файле больше строк, чем можно импортировать за раз.
+#[derive(Error, Debug)]
+pub enum TransferError {
+    #[error("Invalid import file: {0}")]
+    InvalidFile(String),
+    #[error("Import file must not contain more than {0} rows")]
+    TooManyRows(usize),
+}
+
+/// Реализация преобразования `TransferError` в HTTP-ответ.
+///
+/// - `InvalidFile` → 400 Bad Request
+/// - `TooManyRows` → 413 Payload Too Large
+impl IntoResponse for TransferError {
+    fn into_response(self) -> Response {
+        let status_code = match self {
+            TransferError::InvalidFile(_) => StatusCode::BAD_REQUEST,
+            TransferError::TooManyRows(_) => StatusCode::PAYLOAD_TOO_LARGE,
+        };
+
+        ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
+    }
+}
@@ -12,6 +12,8 @@ use thiserror::Error;
 /// - `UserNotFound` — пользователь не найден.
 /// - `UserAlreadyExists` — пользователь с таким email или username уже существует.
 /// - `InvalidPassword` — введён неверный пароль.
+/// - `UserNotActive` — email пользователя ещё не подтверждён.
+/// - `InvalidVerificationToken` — токен подтверждения email неизвестен или истёк.
 #[derive(Error, Debug)]
 pub enum UserError {
     #[error("User not found")]
@@ -20,6 +22,10 @@ pub enum UserError {
     UserAlreadyExists,
     #[error("Invalid password")]
     InvalidPassword,
+    #[error("User account is not activated, confirm the email address first")]
+    UserNotActive,
+    #[error("Email verification token is invalid or expired")]
+    InvalidVerificationToken,
 }
 
 /// Реализация преобразования `UserError` в HTTP-ответ.
@@ -29,12 +35,16 @@ pub enum UserError {
 /// - `UserNotFound` → 404 Not Found
 /// - `UserAlreadyExists` → 400 Bad Request
 /// - `InvalidPassword` → 400 Bad Request
+/// - `UserNotActive` → 403 Forbidden
+/// - `InvalidVerificationToken` → 400 Bad Request
 impl IntoResponse for UserError {
     fn into_response(self) -> Response {
         let status_code = match self {
             UserError::UserNotFound => StatusCode::NOT_FOUND,
             UserError::UserAlreadyExists => StatusCode::BAD_REQUEST,
             UserError::InvalidPassword => StatusCode::BAD_REQUEST,
+            UserError::UserNotActive => StatusCode::FORBIDDEN,
+            UserError::InvalidVerificationToken => StatusCode::BAD_REQUEST,
         };
 
         ApiErrorResponse::send(status_code.as_u16(), Some(self.to_string()))
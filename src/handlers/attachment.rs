@@ -0,0 +1,116 @@
+use crate::dto::attachment::AttachmentReadDto;
+use crate::entities::user::User;
+use crate::errors::{api::ApiError, attachment::AttachmentError};
+use crate::response::api::ApiSuccessResponse;
+use crate::services::attachment::{multipart_error, MULTIPART_OVERHEAD_BYTES};
+use crate::states::attachment::AttachmentState;
+use axum::{
+    body::Body,
+    extract::{Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+
+/// Обработчик получения списка вложений задачи.
+pub async fn list_attachments(
+    State(state): State<AttachmentState>,
+    Extension(current_user): Extension<User>,
+    Path(task_id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<Vec<AttachmentReadDto>>>, ApiError> {
+    let attachments = state
+        .attachment_service
+        .list_attachments(&current_user, task_id)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(attachments)))
+}
+
+/// Обработчик загрузки вложения (`multipart/form-data`).
+///
+/// - Файл передаётся в поле `file`.
+/// - Остальные поля пропускаются, но вместе не могут быть больше
+///   `MULTIPART_OVERHEAD_BYTES` (иначе `InvalidMultipart`).
+///
+/// Возвращает `201 Created` и метаданные вложения, `FileTooLarge` (413)
+/// или `UnsupportedMediaType` (415).
+pub async fn upload_attachment(
+    State(state): State<AttachmentState>,
+    Extension(current_user): Extension<User>,
+    Path(task_id): Path<i32>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<AttachmentReadDto>>), ApiError> {
+    let body_limit = state.attachment_service.limits().body_limit();
+    let mut skipped_bytes: u64 = 0;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| multipart_error(e, body_limit))?
+    {
+        if field.name() == Some("file") {
+            let attachment = state
+                .attachment_service
+                .upload_attachment(&current_user, task_id, field)
+                .await?;
+            return Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(attachment))));
+        }
+
+        while let Some(chunk) = field.chunk().await.map_err(|e| multipart_error(e, body_limit))? {
+            skipped_bytes += chunk.len() as u64;
+            if skipped_bytes > MULTIPART_OVERHEAD_BYTES {
+                return Err(AttachmentError::InvalidMultipart(format!(
+                    "fields other than `file` exceed {} bytes",
+                    MULTIPART_OVERHEAD_BYTES
+                ))
+                .into());
+            }
+        }
+    }
+
+    Err(AttachmentError::MissingFile.into())
+}
+
+/// Обработчик скачивания вложения.
+///
+/// Отдаёт содержимое потоком с заголовками `Content-Type`, `Content-Length`,
+/// `Content-Disposition` и контрольной суммой в `ETag` / `X-Checksum-Sha256`.
+pub async fn download_attachment(
+    State(state): State<AttachmentState>,
+    Extension(current_user): Extension<User>,
+    Path((task_id, id)): Path<(i32, i32)>,
+) -> Result<Response, ApiError> {
+    let (attachment, stream) = state
+        .attachment_service
+        .download_attachment(&current_user, task_id, id)
+        .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (header::CONTENT_LENGTH, attachment.size_bytes.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", attachment.file_name.replace(|c: char| !c.is_ascii(), "_")),
+            ),
+            (header::ETAG, format!("\"{}\"", attachment.checksum_sha256)),
+            (header::HeaderName::from_static("x-checksum-sha256"), attachment.checksum_sha256),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
+
+/// Обработчик удаления вложения.
+///
+/// Возвращает `204 No Content`.
+pub async fn delete_attachment(
+    State(state): State<AttachmentState>,
+    Extension(current_user): Extension<User>,
+    Path((task_id, id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .attachment_service
+        .delete_attachment(&current_user, task_id, id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
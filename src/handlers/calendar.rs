@@ -0,0 +1,69 @@
+use crate::dto::calendar::{CalendarFeedQuery, CalendarFeedReadDto};
+use crate::entities::user::User;
+use crate::errors::{api::ApiError, request::ValidatedQuery};
+use crate::response::api::ApiSuccessResponse;
+use crate::states::calendar::CalendarState;
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+
+/// Обработчик получения календарной ленты текущего пользователя.
+///
+/// Возвращает `CalendarFeedReadDto` без адреса или `FeedNotFound` (404).
+pub async fn get_feed(
+    State(state): State<CalendarState>,
+    Extension(current_user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<CalendarFeedReadDto>>, ApiError> {
+    let feed = state.calendar_service.get_feed(&current_user).await?;
+    Ok(Json(ApiSuccessResponse::send(feed)))
+}
+
+/// Обработчик выпуска нового токена ленты.
+///
+/// Возвращает `201 Created` и ленту с адресом; прежний адрес перестаёт работать.
+pub async fn regenerate_feed(
+    State(state): State<CalendarState>,
+    Extension(current_user): Extension<User>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<CalendarFeedReadDto>>), ApiError> {
+    let feed = state.calendar_service.regenerate_feed(&current_user).await?;
+    Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(feed))))
+}
+
+/// Обработчик отключения ленты.
+///
+/// Возвращает `204 No Content` или `FeedNotFound` (404).
+pub async fn revoke_feed(
+    State(state): State<CalendarState>,
+    Extension(current_user): Extension<User>,
+) -> Result<StatusCode, ApiError> {
+    state.calendar_service.revoke_feed(&current_user).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик календарной ленты по токену.
+///
+/// - `query.component` — `vtodo` (по умолчанию) или `vevent`.
+///
+/// Отдаёт календарь с типом `text/calendar`.
+pub async fn feed(
+    State(state): State<CalendarState>,
+    Extension(current_user): Extension<User>,
+    ValidatedQuery(query): ValidatedQuery<CalendarFeedQuery>,
+) -> Result<Response, ApiError> {
+    let calendar = state
+        .calendar_service
+        .render_feed(&current_user, query.component)
+        .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CACHE_CONTROL, "private, max-age=300"),
+        ],
+        calendar,
+    )
+        .into_response())
+}
@@ -0,0 +1,79 @@
+use crate::dto::column::{BoardColumnDto, ColumnCreateDto, ColumnReadDto, ColumnUpdateDto};
+use crate::entities::user::User;
+use crate::errors::{api::ApiError, request::ValidatedRequest};
+use crate::response::api::ApiSuccessResponse;
+use crate::states::column::ColumnState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+
+/// Обработчик получения колонок доски проекта.
+pub async fn list_columns(
+    State(state): State<ColumnState>,
+    Extension(current_user): Extension<User>,
+    Path(project_id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<Vec<ColumnReadDto>>>, ApiError> {
+    let columns = state.column_service.list_columns(&current_user, project_id).await?;
+    Ok(Json(ApiSuccessResponse::send(columns)))
+}
+
+/// Обработчик получения доски проекта.
+///
+/// Возвращает колонки по порядку вместе с задачами каждой колонки.
+pub async fn get_board(
+    State(state): State<ColumnState>,
+    Extension(current_user): Extension<User>,
+    Path(project_id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<Vec<BoardColumnDto>>>, ApiError> {
+    let board = state.column_service.get_board(&current_user, project_id).await?;
+    Ok(Json(ApiSuccessResponse::send(board)))
+}
+
+/// Обработчик создания колонки (владелец или редактор).
+///
+/// Возвращает `201 Created`; колонка добавляется в конец доски.
+pub async fn create_column(
+    State(state): State<ColumnState>,
+    Extension(current_user): Extension<User>,
+    Path(project_id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<ColumnCreateDto>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<ColumnReadDto>>), ApiError> {
+    let column = state
+        .column_service
+        .create_column(&current_user, project_id, payload)
+        .await?;
+    Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(column))))
+}
+
+/// Обработчик переименования и перестановки колонки.
+///
+/// Возвращает колонку, `ColumnNotFound` (404) или `InvalidPosition` (409).
+pub async fn update_column(
+    State(state): State<ColumnState>,
+    Extension(current_user): Extension<User>,
+    Path((project_id, id)): Path<(i32, i32)>,
+    ValidatedRequest(payload): ValidatedRequest<ColumnUpdateDto>,
+) -> Result<Json<ApiSuccessResponse<ColumnReadDto>>, ApiError> {
+    let column = state
+        .column_service
+        .update_column(&current_user, project_id, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(column)))
+}
+
+/// Обработчик удаления колонки.
+///
+/// Задачи колонки снимаются с доски. Возвращает `204 No Content`.
+pub async fn delete_column(
+    State(state): State<ColumnState>,
+    Extension(current_user): Extension<User>,
+    Path((project_id, id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .column_service
+        .delete_column(&current_user, project_id, id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,94 @@
+use crate::dto::comment::{
+    CommentCreateDto, CommentListQuery, CommentReadDto, CommentRevisionDto, CommentUpdateDto,
+};
+use crate::entities::user::User;
+use crate::errors::{api::ApiError, request::{ValidatedQuery, ValidatedRequest}};
+use crate::response::api::{ApiPaginatedResponse, ApiSuccessResponse};
+use crate::states::comment::CommentState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+
+/// Обработчик получения комментариев задачи.
+///
+/// - `query` — пагинация по корневым комментариям (`limit`, `cursor`).
+/// - Ответы вложены в поле `replies` корневых комментариев.
+pub async fn list_comments(
+    State(state): State<CommentState>,
+    Extension(current_user): Extension<User>,
+    Path(task_id): Path<i32>,
+    ValidatedQuery(query): ValidatedQuery<CommentListQuery>,
+) -> Result<Json<ApiPaginatedResponse<CommentReadDto>>, ApiError> {
+    let page = state
+        .comment_service
+        .list_comments(&current_user, task_id, query)
+        .await?;
+    Ok(Json(ApiPaginatedResponse::send(
+        page.items,
+        page.total,
+        page.next_cursor,
+        page.prev_cursor,
+    )))
+}
+
+/// Обработчик создания комментария или ответа.
+///
+/// Автором становится текущий пользователь. Возвращает `201 Created`.
+pub async fn create_comment(
+    State(state): State<CommentState>,
+    Extension(current_user): Extension<User>,
+    Path(task_id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<CommentCreateDto>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<CommentReadDto>>), ApiError> {
+    let comment = state
+        .comment_service
+        .create_comment(&current_user, task_id, payload)
+        .await?;
+    Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(comment))))
+}
+
+/// Обработчик изменения текста комментария.
+///
+/// Доступно только автору; прежний текст попадает в историю правок.
+pub async fn update_comment(
+    State(state): State<CommentState>,
+    Extension(current_user): Extension<User>,
+    Path((task_id, id)): Path<(i32, i32)>,
+    ValidatedRequest(payload): ValidatedRequest<CommentUpdateDto>,
+) -> Result<Json<ApiSuccessResponse<CommentReadDto>>, ApiError> {
+    let comment = state
+        .comment_service
+        .update_comment(&current_user, task_id, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(comment)))
+}
+
+/// Обработчик удаления комментария.
+///
+/// Доступно только автору. Возвращает `204 No Content`.
+pub async fn delete_comment(
+    State(state): State<CommentState>,
+    Extension(current_user): Extension<User>,
+    Path((task_id, id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .comment_service
+        .delete_comment(&current_user, task_id, id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик получения истории правок комментария.
+pub async fn list_comment_revisions(
+    State(state): State<CommentState>,
+    Extension(current_user): Extension<User>,
+    Path((task_id, id)): Path<(i32, i32)>,
+) -> Result<Json<ApiSuccessResponse<Vec<CommentRevisionDto>>>, ApiError> {
+    let revisions = state
+        .comment_service
+        .list_revisions(&current_user, task_id, id)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(revisions)))
+}
@@ -0,0 +1,71 @@
+use crate::dto::label::{LabelReadDto, LabelWriteDto};
+use crate::entities::user::User;
+use crate::errors::{api::ApiError, request::ValidatedRequest};
+use crate::response::api::ApiSuccessResponse;
+use crate::states::label::LabelState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+
+/// Обработчик получения списка меток текущего пользователя.
+pub async fn list_labels(
+    State(state): State<LabelState>,
+    Extension(current_user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Vec<LabelReadDto>>>, ApiError> {
+    let labels = state.label_service.list_labels(&current_user).await?;
+    Ok(Json(ApiSuccessResponse::send(labels)))
+}
+
+/// Обработчик получения метки по ID.
+///
+/// Возвращает `LabelReadDto`, `LabelNotFound` (404) или `ForbiddenLabelAccess` (403).
+pub async fn get_label(
+    State(state): State<LabelState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<LabelReadDto>>, ApiError> {
+    let label = state.label_service.get_label(&current_user, id).await?;
+    Ok(Json(ApiSuccessResponse::send(label)))
+}
+
+/// Обработчик создания метки.
+///
+/// Возвращает `201 Created` и созданную метку или `LabelAlreadyExists` (409).
+pub async fn create_label(
+    State(state): State<LabelState>,
+    Extension(current_user): Extension<User>,
+    ValidatedRequest(payload): ValidatedRequest<LabelWriteDto>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<LabelReadDto>>), ApiError> {
+    let label = state.label_service.create_label(&current_user, payload).await?;
+    Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(label))))
+}
+
+/// Обработчик изменения метки.
+///
+/// Новое название и цвет сразу отображаются во всех задачах с этой меткой.
+pub async fn update_label(
+    State(state): State<LabelState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<LabelWriteDto>,
+) -> Result<Json<ApiSuccessResponse<LabelReadDto>>, ApiError> {
+    let label = state
+        .label_service
+        .update_label(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(label)))
+}
+
+/// Обработчик удаления метки.
+///
+/// Метка снимается со всех задач. Возвращает `204 No Content`.
+pub async fn delete_label(
+    State(state): State<LabelState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    state.label_service.delete_label(&current_user, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,2 +1,12 @@
 pub mod user;
-mod task;
\ No newline at end of file
+pub mod task;
+pub mod label;
+pub mod comment;
+pub mod attachment;
+pub mod project;
+pub mod column;
+pub mod search;
+pub mod transfer;
+pub mod calendar;
+pub mod time_entry;
+pub mod session;
\ No newline at end of file
@@ -0,0 +1,128 @@
+use crate::dto::project::{
+    ProjectMemberCreateDto, ProjectMemberReadDto, ProjectMemberUpdateDto, ProjectReadDto, ProjectWriteDto,
+};
+use crate::entities::user::User;
+use crate::errors::{api::ApiError, request::ValidatedRequest};
+use crate::response::api::ApiSuccessResponse;
+use crate::states::project::ProjectState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+
+/// Обработчик получения списка проектов текущего пользователя.
+pub async fn list_projects(
+    State(state): State<ProjectState>,
+    Extension(current_user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Vec<ProjectReadDto>>>, ApiError> {
+    let projects = state.project_service.list_projects(&current_user).await?;
+    Ok(Json(ApiSuccessResponse::send(projects)))
+}
+
+/// Обработчик получения проекта по ID.
+///
+/// Возвращает `ProjectReadDto`, `ProjectNotFound` (404) или `ForbiddenProjectAccess` (403).
+pub async fn get_project(
+    State(state): State<ProjectState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<ProjectReadDto>>, ApiError> {
+    let project = state.project_service.get_project(&current_user, id).await?;
+    Ok(Json(ApiSuccessResponse::send(project)))
+}
+
+/// Обработчик создания проекта.
+///
+/// Возвращает `201 Created`; текущий пользователь становится владельцем проекта.
+pub async fn create_project(
+    State(state): State<ProjectState>,
+    Extension(current_user): Extension<User>,
+    ValidatedRequest(payload): ValidatedRequest<ProjectWriteDto>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<ProjectReadDto>>), ApiError> {
+    let project = state.project_service.create_project(&current_user, payload).await?;
+    Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(project))))
+}
+
+/// Обработчик изменения проекта (только владелец).
+pub async fn update_project(
+    State(state): State<ProjectState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<ProjectWriteDto>,
+) -> Result<Json<ApiSuccessResponse<ProjectReadDto>>, ApiError> {
+    let project = state
+        .project_service
+        .update_project(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(project)))
+}
+
+/// Обработчик удаления проекта (только владелец).
+///
+/// Задачи проекта удаляются вместе с ним. Возвращает `204 No Content`.
+pub async fn delete_project(
+    State(state): State<ProjectState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    state.project_service.delete_project(&current_user, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик получения участников проекта.
+pub async fn list_members(
+    State(state): State<ProjectState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<Vec<ProjectMemberReadDto>>>, ApiError> {
+    let members = state.project_service.list_members(&current_user, id).await?;
+    Ok(Json(ApiSuccessResponse::send(members)))
+}
+
+/// Обработчик добавления участника в проект.
+///
+/// Возвращает `201 Created`, `UserNotFound` (404) или `MemberAlreadyExists` (409).
+pub async fn add_member(
+    State(state): State<ProjectState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<ProjectMemberCreateDto>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<ProjectMemberReadDto>>), ApiError> {
+    let member = state
+        .project_service
+        .add_member(&current_user, id, payload)
+        .await?;
+    Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(member))))
+}
+
+/// Обработчик смены роли участника.
+///
+/// Возвращает обновлённого участника или `LastOwner` (409).
+pub async fn update_member(
+    State(state): State<ProjectState>,
+    Extension(current_user): Extension<User>,
+    Path((id, user_id)): Path<(i32, i32)>,
+    ValidatedRequest(payload): ValidatedRequest<ProjectMemberUpdateDto>,
+) -> Result<Json<ApiSuccessResponse<ProjectMemberReadDto>>, ApiError> {
+    let member = state
+        .project_service
+        .update_member(&current_user, id, user_id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(member)))
+}
+
+/// Обработчик удаления участника из проекта (или выхода из проекта).
+///
+/// Возвращает `204 No Content`.
+pub async fn remove_member(
+    State(state): State<ProjectState>,
+    Extension(current_user): Extension<User>,
+    Path((id, user_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .project_service
+        .remove_member(&current_user, id, user_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,21 @@
+use crate::dto::search::{SearchQuery, SearchResultDto};
+use crate::entities::user::User;
+use crate::errors::{api::ApiError, request::ValidatedQuery};
+use crate::response::api::ApiSuccessResponse;
+use crate::states::search::SearchState;
+use axum::{extract::State, Extension, Json};
+
+/// Обработчик полнотекстового поиска.
+///
+/// - `query.q` — поисковый запрос (фразы в кавычках, `слово*`, `OR`, `-слово`).
+/// - `query.limit` / `query.offset` — пагинация.
+///
+/// Возвращает задачи и комментарии по убыванию релевантности с выделенными фрагментами.
+pub async fn search(
+    State(state): State<SearchState>,
+    Extension(current_user): Extension<User>,
+    ValidatedQuery(query): ValidatedQuery<SearchQuery>,
+) -> Result<Json<ApiSuccessResponse<Vec<SearchResultDto>>>, ApiError> {
+    let results = state.search_service.search(&current_user, query).await?;
+    Ok(Json(ApiSuccessResponse::send(results)))
+}
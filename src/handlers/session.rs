@@ -0,0 +1,43 @@
+use crate::dto::session::SessionReadDto;
+use crate::dto::token::TokenClaimsDto;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::response::api::ApiSuccessResponse;
+use crate::states::user::TokenState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use uuid::Uuid;
+
+/// Обработчик получения действующих сессий текущего пользователя.
+///
+/// Сессия, из которой выполнен запрос, отмечена `is_current`.
+pub async fn list_sessions(
+    State(state): State<TokenState>,
+    Extension(current_user): Extension<User>,
+    Extension(claims): Extension<TokenClaimsDto>,
+) -> Result<Json<ApiSuccessResponse<Vec<SessionReadDto>>>, ApiError> {
+    let sessions = state
+        .session_service
+        .list_sessions(&current_user, claims.sid)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(sessions)))
+}
+
+/// Обработчик завершения сессии.
+///
+/// Токены сессии перестают действовать. Возвращает `204 No Content`
+/// или `SessionNotFound` (404).
+pub async fn revoke_session(
+    State(state): State<TokenState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .revocation_service
+        .revoke_session(&current_user, id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
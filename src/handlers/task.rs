@@ -0,0 +1,424 @@
+use crate::dto::task::{
+    TaskAssigneeCreateDto, TaskBulkDto, TaskBulkResultDto, TaskCreateDto, TaskDeleteQuery, TaskDependencyCreateDto,
+    TaskEventReadDto, TaskLabelCreateDto, TaskListQuery, TaskMoveDto, TaskPatchDto, TaskReadDto, TaskTransitionDto, TaskTreeDto,
+    TaskUpcomingQuery, TaskUpdateDto, TaskWatcherCreateDto, TaskWatcherReadDto,
+};
+use crate::entities::user::User;
+use crate::errors::{api::ApiError, request::{ValidatedQuery, ValidatedRequest}};
+use crate::response::api::{ApiPaginatedResponse, ApiSuccessResponse};
+use crate::states::task::TaskState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+
+/// Обработчик получения списка задач текущего пользователя.
+///
+/// - `current_user` — извлекается из JWT-токена через middleware.
+/// - `query` — пагинация (`limit`, `offset` или `cursor`), сортировка (`sort`) и фильтры.
+/// - Возвращает страницу `TaskReadDto` в формате `ApiPaginatedResponse`.
+///
+/// # Пример ответа:
+/// ```json
+/// {
+///   "data": [ { "id": 1, "title": "Write docs", ... } ],
+///   "total": 42,
+///   "next_cursor": "eyJkIjoibmV4dCIsLi4ufQ",
+///   "prev_cursor": null
+/// }
+/// ```
+pub async fn list_tasks(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    ValidatedQuery(query): ValidatedQuery<TaskListQuery>,
+) -> Result<Json<ApiPaginatedResponse<TaskReadDto>>, ApiError> {
+    let page = state.task_service.list_tasks(&current_user, query).await?;
+    Ok(Json(ApiPaginatedResponse::send(
+        page.items,
+        page.total,
+        page.next_cursor,
+        page.prev_cursor,
+    )))
+}
+
+/// Обработчик получения просроченных задач.
+///
+/// Возвращает открытые задачи текущего пользователя, срок которых уже наступил,
+/// отсортированные по сроку.
+pub async fn list_overdue_tasks(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskReadDto>>>, ApiError> {
+    let tasks = state.task_service.list_overdue(&current_user).await?;
+    Ok(Json(ApiSuccessResponse::send(tasks)))
+}
+
+/// Обработчик получения предстоящих задач.
+///
+/// - `query.within` — горизонт выборки (например, `?within=7d`).
+///
+/// Возвращает открытые задачи со сроком в пределах горизонта, отсортированные по сроку.
+pub async fn list_upcoming_tasks(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    ValidatedQuery(query): ValidatedQuery<TaskUpcomingQuery>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskReadDto>>>, ApiError> {
+    let tasks = state
+        .task_service
+        .list_upcoming(&current_user, query)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(tasks)))
+}
+
+/// Обработчик получения задач, назначенных текущему пользователю.
+///
+/// Возвращает открытые задачи, в которых пользователь указан исполнителем,
+/// отсортированные по сроку.
+pub async fn list_assigned_tasks(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskReadDto>>>, ApiError> {
+    let tasks = state.task_service.list_assigned(&current_user).await?;
+    Ok(Json(ApiSuccessResponse::send(tasks)))
+}
+
+/// Обработчик получения задач из корзины.
+///
+/// Возвращает удалённые задачи, доступные текущему пользователю,
+/// начиная с последних удалённых.
+pub async fn list_trash(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskReadDto>>>, ApiError> {
+    let tasks = state.task_service.list_trash(&current_user).await?;
+    Ok(Json(ApiSuccessResponse::send(tasks)))
+}
+
+/// Обработчик получения открытых задач в порядке зависимостей.
+///
+/// Каждая задача идёт после всех задач, которые её блокируют.
+pub async fn list_tasks_in_order(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskReadDto>>>, ApiError> {
+    let tasks = state
+        .task_service
+        .list_in_dependency_order(&current_user)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(tasks)))
+}
+
+/// Обработчик получения задачи по ID.
+///
+/// Возвращает:
+/// - `TaskReadDto` — если задача принадлежит текущему пользователю;
+/// - `TaskNotFound` (404) или `ForbiddenTaskAccess` (403).
+pub async fn get_task(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<TaskReadDto>>, ApiError> {
+    let task = state.task_service.get_task(&current_user, id).await?;
+    Ok(Json(ApiSuccessResponse::send(task)))
+}
+
+/// Обработчик получения задачи вместе со всеми подзадачами.
+///
+/// Возвращает `TaskTreeDto`: поля задачи и рекурсивный список `children`.
+pub async fn get_task_tree(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<TaskTreeDto>>, ApiError> {
+    let tree = state.task_service.get_task_tree(&current_user, id).await?;
+    Ok(Json(ApiSuccessResponse::send(tree)))
+}
+
+/// Обработчик создания задачи.
+///
+/// - `payload` — данные задачи, проходят валидацию через `ValidatedRequest`.
+/// - Владельцем задачи становится текущий пользователь.
+///
+/// Возвращает `201 Created` и созданную задачу.
+pub async fn create_task(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    ValidatedRequest(payload): ValidatedRequest<TaskCreateDto>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<TaskReadDto>>), ApiError> {
+    let task = state.task_service.create_task(&current_user, payload).await?;
+    Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(task))))
+}
+
+/// Обработчик полной замены задачи (PUT).
+///
+/// Возвращает обновлённую задачу, `TaskNotFound` или `ForbiddenTaskAccess`.
+pub async fn update_task(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TaskUpdateDto>,
+) -> Result<Json<ApiSuccessResponse<TaskReadDto>>, ApiError> {
+    let task = state
+        .task_service
+        .update_task(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(task)))
+}
+
+/// Обработчик частичного обновления задачи (PATCH).
+///
+/// Изменяет только переданные поля.
+/// Возвращает обновлённую задачу, `TaskNotFound` или `ForbiddenTaskAccess`.
+pub async fn patch_task(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TaskPatchDto>,
+) -> Result<Json<ApiSuccessResponse<TaskReadDto>>, ApiError> {
+    let task = state
+        .task_service
+        .patch_task(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(task)))
+}
+
+/// Обработчик смены статуса задачи.
+///
+/// - `payload` — целевой статус (`todo`, `in_progress`, `blocked`, `done`, `cancelled`).
+///
+/// Возвращает обновлённую задачу или `InvalidStatusTransition` (409),
+/// если переход запрещён правилами.
+pub async fn transition_task(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TaskTransitionDto>,
+) -> Result<Json<ApiSuccessResponse<TaskReadDto>>, ApiError> {
+    let task = state
+        .task_service
+        .transition_task(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(task)))
+}
+
+/// Обработчик массовой обработки задач.
+///
+/// - `payload.mode` — `all_or_nothing` (по умолчанию) или `per_item`.
+/// - `payload.operations` — операции `update_status`, `add_label`, `move`, `delete`.
+///
+/// Возвращает результат каждой операции с HTTP-кодом и текстом ошибки для неудачных.
+pub async fn bulk_tasks(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    ValidatedRequest(payload): ValidatedRequest<TaskBulkDto>,
+) -> Result<Json<ApiSuccessResponse<TaskBulkResultDto>>, ApiError> {
+    let result = state.task_service.bulk(&current_user, payload).await?;
+    Ok(Json(ApiSuccessResponse::send(result)))
+}
+
+/// Обработчик перемещения задачи на доске проекта.
+///
+/// - `payload.column_id` — целевая колонка.
+/// - `payload.after_id` — задача, после которой встаёт карточка (`null` — в начало).
+///
+/// Возвращает задачу с новыми `column_id` и `rank`, `ColumnNotFound` (404),
+/// `ProjectMismatch` (409) или `InvalidPosition` (409).
+pub async fn move_task(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TaskMoveDto>,
+) -> Result<Json<ApiSuccessResponse<TaskReadDto>>, ApiError> {
+    let task = state
+        .task_service
+        .move_task(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(task)))
+}
+
+/// Обработчик получения задач, от которых зависит задача.
+pub async fn list_dependencies(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskReadDto>>>, ApiError> {
+    let tasks = state.task_service.list_dependencies(&current_user, id).await?;
+    Ok(Json(ApiSuccessResponse::send(tasks)))
+}
+
+/// Обработчик добавления зависимости.
+///
+/// - `payload.depends_on_id` — задача, которая блокирует текущую.
+///
+/// Возвращает актуальный список блокирующих задач или `DependencyCycle` (409).
+pub async fn add_dependency(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TaskDependencyCreateDto>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskReadDto>>>, ApiError> {
+    let tasks = state
+        .task_service
+        .add_dependency(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(tasks)))
+}
+
+/// Обработчик удаления зависимости.
+///
+/// Возвращает `204 No Content`.
+pub async fn remove_dependency(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path((id, depends_on_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .task_service
+        .remove_dependency(&current_user, id, depends_on_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик назначения метки задаче.
+///
+/// Возвращает задачу с обновлённым списком меток,
+/// `LabelNotFound` (404) или `ForbiddenLabelAccess` (403).
+pub async fn add_label(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TaskLabelCreateDto>,
+) -> Result<Json<ApiSuccessResponse<TaskReadDto>>, ApiError> {
+    let task = state
+        .task_service
+        .add_label(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(task)))
+}
+
+/// Обработчик снятия метки с задачи.
+///
+/// Возвращает `204 No Content`.
+pub async fn remove_label(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path((id, label_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .task_service
+        .remove_label(&current_user, id, label_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик назначения исполнителя задачи.
+///
+/// Возвращает задачу с обновлённым списком исполнителей или
+/// `UserWithoutAccess` (422), если у пользователя нет доступа к задаче.
+pub async fn assign_user(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TaskAssigneeCreateDto>,
+) -> Result<Json<ApiSuccessResponse<TaskReadDto>>, ApiError> {
+    let task = state
+        .task_service
+        .assign_user(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(task)))
+}
+
+/// Обработчик снятия исполнителя с задачи.
+///
+/// Возвращает `204 No Content`.
+pub async fn unassign_user(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path((id, user_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .task_service
+        .unassign_user(&current_user, id, user_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик получения истории изменений задачи.
+pub async fn list_history(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskEventReadDto>>>, ApiError> {
+    let events = state.task_service.list_history(&current_user, id).await?;
+    Ok(Json(ApiSuccessResponse::send(events)))
+}
+
+/// Обработчик получения наблюдателей задачи.
+pub async fn list_watchers(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskWatcherReadDto>>>, ApiError> {
+    let watchers = state.task_service.list_watchers(&current_user, id).await?;
+    Ok(Json(ApiSuccessResponse::send(watchers)))
+}
+
+/// Обработчик добавления наблюдателя задачи.
+///
+/// Возвращает актуальный список наблюдателей или `UserWithoutAccess` (422).
+pub async fn add_watcher(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TaskWatcherCreateDto>,
+) -> Result<Json<ApiSuccessResponse<Vec<TaskWatcherReadDto>>>, ApiError> {
+    let watchers = state
+        .task_service
+        .add_watcher(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(watchers)))
+}
+
+/// Обработчик удаления наблюдателя задачи.
+///
+/// Возвращает `204 No Content`.
+pub async fn remove_watcher(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path((id, user_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .task_service
+        .remove_watcher(&current_user, id, user_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик удаления задачи в корзину.
+///
+/// - `query.keep_children` — сохранить подзадачи, перенеся их к родителю удаляемой задачи.
+///
+/// Возвращает `204 No Content`, `TaskNotFound` или `ForbiddenTaskAccess`.
+pub async fn delete_task(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedQuery(query): ValidatedQuery<TaskDeleteQuery>,
+) -> Result<StatusCode, ApiError> {
+    state.task_service.delete_task(&current_user, id, query).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик восстановления задачи из корзины.
+///
+/// Возвращает восстановленную задачу, `TaskNotFound` (404), если задачи нет
+/// в корзине, или `ForbiddenTaskAccess` (403).
+pub async fn restore_task(
+    State(state): State<TaskState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<TaskReadDto>>, ApiError> {
+    let task = state.task_service.restore_task(&current_user, id).await?;
+    Ok(Json(ApiSuccessResponse::send(task)))
+}
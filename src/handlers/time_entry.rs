@@ -0,0 +1,124 @@
+use crate::dto::time_entry::{TimeEntryReadDto, TimeEntryWriteDto, TimeReportDto, TimeReportQuery};
+use crate::entities::user::User;
+use crate::errors::{
+    api::ApiError,
+    request::{ValidatedQuery, ValidatedRequest},
+};
+use crate::response::api::ApiSuccessResponse;
+use crate::states::time_entry::TimeEntryState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+
+/// Обработчик получения записей времени текущего пользователя по задаче.
+pub async fn list_entries(
+    State(state): State<TimeEntryState>,
+    Extension(current_user): Extension<User>,
+    Path(task_id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<Vec<TimeEntryReadDto>>>, ApiError> {
+    let entries = state
+        .time_entry_service
+        .list_entries(&current_user, task_id)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(entries)))
+}
+
+/// Обработчик ручной записи времени по задаче.
+///
+/// Возвращает `201 Created` и запись или `InvalidTimeRange` (400).
+pub async fn create_entry(
+    State(state): State<TimeEntryState>,
+    Extension(current_user): Extension<User>,
+    Path(task_id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TimeEntryWriteDto>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<TimeEntryReadDto>>), ApiError> {
+    let entry = state
+        .time_entry_service
+        .create_entry(&current_user, task_id, payload)
+        .await?;
+    Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(entry))))
+}
+
+/// Обработчик запуска таймера по задаче.
+///
+/// Возвращает `201 Created` и запись или `TimerAlreadyRunning` (409).
+pub async fn start_timer(
+    State(state): State<TimeEntryState>,
+    Extension(current_user): Extension<User>,
+    Path(task_id): Path<i32>,
+) -> Result<(StatusCode, Json<ApiSuccessResponse<TimeEntryReadDto>>), ApiError> {
+    let entry = state
+        .time_entry_service
+        .start_timer(&current_user, task_id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(ApiSuccessResponse::send(entry))))
+}
+
+/// Обработчик остановки таймера по задаче.
+///
+/// Возвращает завершённую запись или `TimerNotRunning` (409).
+pub async fn stop_timer(
+    State(state): State<TimeEntryState>,
+    Extension(current_user): Extension<User>,
+    Path(task_id): Path<i32>,
+) -> Result<Json<ApiSuccessResponse<TimeEntryReadDto>>, ApiError> {
+    let entry = state
+        .time_entry_service
+        .stop_timer(&current_user, task_id)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(entry)))
+}
+
+/// Обработчик получения запущенного таймера текущего пользователя.
+///
+/// Возвращает запись или `null`, если таймер не запущен.
+pub async fn get_running(
+    State(state): State<TimeEntryState>,
+    Extension(current_user): Extension<User>,
+) -> Result<Json<ApiSuccessResponse<Option<TimeEntryReadDto>>>, ApiError> {
+    let entry = state.time_entry_service.get_running(&current_user).await?;
+    Ok(Json(ApiSuccessResponse::send(entry)))
+}
+
+/// Обработчик изменения записи времени.
+///
+/// Изменять запись может только её владелец.
+pub async fn update_entry(
+    State(state): State<TimeEntryState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+    ValidatedRequest(payload): ValidatedRequest<TimeEntryWriteDto>,
+) -> Result<Json<ApiSuccessResponse<TimeEntryReadDto>>, ApiError> {
+    let entry = state
+        .time_entry_service
+        .update_entry(&current_user, id, payload)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(entry)))
+}
+
+/// Обработчик удаления записи времени.
+///
+/// Возвращает `204 No Content`.
+pub async fn delete_entry(
+    State(state): State<TimeEntryState>,
+    Extension(current_user): Extension<User>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    state.time_entry_service.delete_entry(&current_user, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик отчёта по учтённому времени.
+///
+/// - `query.from` / `query.to` — период (даты в UTC, включительно).
+/// - `query.group_by` — `day`, `week` или `project`.
+pub async fn report(
+    State(state): State<TimeEntryState>,
+    Extension(current_user): Extension<User>,
+    ValidatedQuery(query): ValidatedQuery<TimeReportQuery>,
+) -> Result<Json<ApiSuccessResponse<TimeReportDto>>, ApiError> {
+    let report = state.time_entry_service.report(&current_user, query).await?;
+    Ok(Json(ApiSuccessResponse::send(report)))
+}
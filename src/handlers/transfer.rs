@@ -0,0 +1,57 @@
+use crate::dto::transfer::{TaskImportResultDto, TransferQuery};
+use crate::entities::user::User;
+use crate::errors::{api::ApiError, request::ValidatedQuery};
+use crate::response::api::ApiSuccessResponse;
+use crate::states::transfer::TransferState;
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+
+/// Обработчик экспорта задач.
+///
+/// - `query.format` — `csv`, `jsonl` или `ics`.
+///
+/// Отдаёт файл потоком с заголовками `Content-Type` и `Content-Disposition`.
+pub async fn export_tasks(
+    State(state): State<TransferState>,
+    Extension(current_user): Extension<User>,
+    ValidatedQuery(query): ValidatedQuery<TransferQuery>,
+) -> Result<Response, ApiError> {
+    let stream = state.transfer_service.export_tasks(&current_user, query.format);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, query.format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"tasks.{}\"", query.format.extension()),
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
+
+/// Обработчик импорта задач.
+///
+/// - `query.format` — `csv`, `jsonl` или `ics`.
+/// - Тело запроса — содержимое файла (UTF-8).
+///
+/// Возвращает отчёт с идентификаторами созданных задач и ошибками строк,
+/// `InvalidFile` (400) или `TooManyRows` (413).
+pub async fn import_tasks(
+    State(state): State<TransferState>,
+    Extension(current_user): Extension<User>,
+    ValidatedQuery(query): ValidatedQuery<TransferQuery>,
+    body: Bytes,
+) -> Result<Json<ApiSuccessResponse<TaskImportResultDto>>, ApiError> {
+    let result = state
+        .transfer_service
+        .import_tasks(&current_user, query.format, body)
+        .await?;
+    Ok(Json(ApiSuccessResponse::send(result)))
+}
@@ -1,11 +1,15 @@
-use crate::dto::{token::TokenReadDto, user::{UserLoginDto, UserReadDto, UserRegisterDto}};
-use crate::errors::{api::ApiError, request::ValidatedRequest, user::UserError};
+use crate::dto::{email_verification::{EmailVerificationResendDto, EmailVerifyQuery}, password_reset::{PasswordForgotDto, PasswordResetDto}, session::SessionClientDto, token::{TokenClaimsDto, TokenReadDto, TokenRefreshDto}, user::{UserLoginDto, UserReadDto, UserRegisterDto}};
+use crate::errors::{api::ApiError, request::{ValidatedQuery, ValidatedRequest}, user::UserError};
 use crate::repositories::user::UserRepositoryTrait;
-use crate::services::token::TokenServiceTrait;
-use crate::states::user::{AuthState, UserState};
+use crate::states::user::{AuthState, TokenState, UserState};
 use crate::entities::user::User;
 use crate::response::api::ApiSuccessResponse;
-use axum::{extract::State, Json, Extension};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, StatusCode},
+    Json, Extension,
+};
+use std::net::SocketAddr;
 
 /// Обработчик авторизации пользователя.
 ///
@@ -14,13 +18,19 @@ use axum::{extract::State, Json, Extension};
 /// - `payload` — данные для входа: email и пароль.
 /// - `user_repo` — используется для поиска пользователя по email.
 /// - `user_service` — выполняет проверку пароля.
-/// - `token_service` — генерирует JWT токен.
+/// - `refresh_token_service` — генерирует JWT и refresh-токен.
+///
+/// Вход начинает сессию с `User-Agent` и IP-адресом клиента. `X-Forwarded-For`
+/// учитывается, только если соединение пришло от доверенного прокси (`TRUSTED_PROXIES`).
 ///
 /// Возвращает:
 /// - `TokenReadDto` при успешной авторизации;
-/// - Ошибку `UserNotFound` или `InvalidPassword`, если данные неверные.
+/// - Ошибку `UserNotFound` или `InvalidPassword`, если данные неверные;
+/// - Ошибку `UserNotActive`, если email ещё не подтверждён.
 pub async fn auth(
     State(state): State<AuthState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     ValidatedRequest(payload): ValidatedRequest<UserLoginDto>,
 ) -> Result<Json<TokenReadDto>, ApiError> {
     // Поиск пользователя по email
@@ -32,15 +42,133 @@ pub async fn auth(
 
     // Проверка пароля
     match state.user_service.verify_password(&user, &payload.password) {
+        true if user.is_active == 0 => Err(UserError::UserNotActive)?,
         true => {
             // Генерация токена
-            let token = state.token_service.generate_token(user)?;
+            let client = SessionClientDto {
+                user_agent: headers
+                    .get(header::USER_AGENT)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string),
+                ip_address: Some(
+                    state
+                        .trusted_proxies
+                        .client_ip(
+                            addr.ip(),
+                            headers.get("x-forwarded-for").and_then(|value| value.to_str().ok()),
+                        )
+                        .to_string(),
+                ),
+            };
+            let token = state.refresh_token_service.issue(user, client).await?;
             Ok(Json(token))
         }
         false => Err(UserError::InvalidPassword)?,
     }
 }
 
+/// Обработчик обновления токенов.
+///
+/// Обменивает refresh-токен на новую пару JWT и refresh-токена; предъявленный
+/// токен становится недействительным.
+///
+/// Возвращает:
+/// - `TokenReadDto` с новыми токенами;
+/// - `InvalidRefreshToken` или `TokenExpired`, если токен недействителен;
+/// - `RefreshTokenReused`, если токен уже был обменян (все токены этого входа отзываются).
+pub async fn refresh(
+    State(state): State<AuthState>,
+    ValidatedRequest(payload): ValidatedRequest<TokenRefreshDto>,
+) -> Result<Json<TokenReadDto>, ApiError> {
+    let token = state
+        .refresh_token_service
+        .refresh(&payload.refresh_token)
+        .await?;
+    Ok(Json(token))
+}
+
+/// Обработчик подтверждения email.
+///
+/// Активирует аккаунт по токену из письма, отправленного при регистрации.
+///
+/// Возвращает:
+/// - `UserReadDto` активированного пользователя;
+/// - `InvalidVerificationToken`, если токен неизвестен, уже использован или истёк.
+pub async fn verify_email(
+    State(state): State<AuthState>,
+    ValidatedQuery(query): ValidatedQuery<EmailVerifyQuery>,
+) -> Result<Json<ApiSuccessResponse<UserReadDto>>, ApiError> {
+    let user = state.email_verification_service.verify(&query.token).await?;
+    Ok(Json(ApiSuccessResponse::send(user)))
+}
+
+/// Обработчик повторной отправки письма подтверждения.
+///
+/// Письмо уходит не чаще раза в минуту и только неподтверждённым пользователям.
+/// Ответ всегда `202 Accepted` и не зависит от того, зарегистрирован ли адрес.
+pub async fn resend_verification(
+    State(state): State<AuthState>,
+    ValidatedRequest(payload): ValidatedRequest<EmailVerificationResendDto>,
+) -> StatusCode {
+    state.email_verification_service.request_verification(payload.email);
+    StatusCode::ACCEPTED
+}
+
+/// Обработчик запроса на сброс пароля.
+///
+/// Отправляет на email письмо с одноразовым токеном сброса. Ответ всегда
+/// `202 Accepted` и не зависит от того, зарегистрирован ли адрес.
+pub async fn forgot_password(
+    State(state): State<AuthState>,
+    ValidatedRequest(payload): ValidatedRequest<PasswordForgotDto>,
+) -> StatusCode {
+    state.password_reset_service.request_reset(payload.email);
+    StatusCode::ACCEPTED
+}
+
+/// Обработчик установки нового пароля.
+///
+/// Меняет пароль по токену из письма и завершает все сессии пользователя.
+///
+/// Возвращает:
+/// - `204 No Content` при успешной смене пароля;
+/// - `InvalidResetToken`, если токен неизвестен, уже использован или истёк.
+pub async fn reset_password(
+    State(state): State<AuthState>,
+    ValidatedRequest(payload): ValidatedRequest<PasswordResetDto>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .password_reset_service
+        .reset_password(&payload.token, &payload.password)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик выхода из системы.
+///
+/// Отзывает текущий JWT и выданную вместе с ним цепочку refresh-токенов.
+/// Возвращает `204 No Content`.
+pub async fn logout(
+    State(state): State<TokenState>,
+    Extension(current_user): Extension<User>,
+    Extension(claims): Extension<TokenClaimsDto>,
+) -> Result<StatusCode, ApiError> {
+    state.revocation_service.revoke(&current_user, &claims).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Обработчик выхода со всех устройств.
+///
+/// Отзывает все выданные пользователю JWT и refresh-токены.
+/// Возвращает `204 No Content`.
+pub async fn logout_all(
+    State(state): State<TokenState>,
+    Extension(current_user): Extension<User>,
+) -> Result<StatusCode, ApiError> {
+    state.revocation_service.revoke_all(&current_user).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Обработчик получения профиля текущего пользователя.
 ///
 /// Используется для получения данных авторизованного пользователя.
@@ -68,9 +196,11 @@ pub async fn get_profile(
 /// Обработчик регистрации нового пользователя.
 ///
 /// Выполняет создание пользователя в системе на основе регистрационных данных.
+/// Пользователь создаётся неактивным, на его email отправляется ссылка подтверждения.
 ///
 /// - `payload` — регистрационные данные пользователя.
 /// - `state.user_service` — создаёт пользователя в базе.
+/// - `state.email_verification_service` — отправляет письмо подтверждения.
 ///
 /// Возвращает:
 /// - `UserReadDto` — данные зарегистрированного пользователя;
@@ -80,5 +210,6 @@ pub async fn register_user(
     ValidatedRequest(payload): ValidatedRequest<UserRegisterDto>,
 ) -> Result<Json<ApiSuccessResponse<UserReadDto>>, ApiError> {
     let user = state.user_service.create_user(payload).await?;
+    state.email_verification_service.request_verification(user.email.clone());
     Ok(Json(ApiSuccessResponse::send(user)))
 }
\ No newline at end of file
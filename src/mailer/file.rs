@@ -0,0 +1,47 @@
+use crate::errors::mail::MailError;
+use crate::mailer::mailer::{Mail, Mailer};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::path::PathBuf;
+use tokio::fs;
+use uuid::Uuid;
+
+/// Почтовый транспорт в файлы (`FileMailer`).
+///
+/// Каждое письмо сохраняется в каталог отдельным файлом `.eml`
+/// (для разработки и проверки писем без почтового сервера).
+#[derive(Clone)]
+pub struct FileMailer {
+    dir: PathBuf,
+    from: String,
+}
+
+impl FileMailer {
+    /// Создание транспорта.
+    ///
+    /// :param dir: каталог писем (создаётся при первой отправке).
+    /// :param from: адрес отправителя.
+    pub fn new(dir: impl Into<PathBuf>, from: String) -> Self {
+        Self { dir: dir.into(), from }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, mail: Mail) -> Result<(), MailError> {
+        let now = Utc::now();
+        let message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n",
+            self.from,
+            mail.to,
+            mail.subject,
+            now.to_rfc2822(),
+            mail.body.replace('\n', "\r\n"),
+        );
+
+        fs::create_dir_all(&self.dir).await?;
+        let name = format!("{}-{}.eml", now.format("%Y%m%dT%H%M%S"), Uuid::new_v4().simple());
+        fs::write(self.dir.join(name), message).await?;
+        Ok(())
+    }
+}
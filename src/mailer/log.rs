@@ -0,0 +1,34 @@
+use crate::errors::mail::MailError;
+use crate::mailer::mailer::{Mail, Mailer};
+use async_trait::async_trait;
+
+/// Почтовый транспорт для разработки (`LogMailer`).
+///
+/// Письма не отправляются, а целиком пишутся в лог на уровне `info`.
+#[derive(Clone)]
+pub struct LogMailer {
+    from: String,
+}
+
+impl LogMailer {
+    /// Создание транспорта.
+    ///
+    /// :param from: адрес отправителя.
+    pub fn new(from: String) -> Self {
+        Self { from }
+    }
+}
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, mail: Mail) -> Result<(), MailError> {
+        tracing::info!(
+            from = %self.from,
+            to = %mail.to,
+            subject = %mail.subject,
+            "mail:\n{}",
+            mail.body
+        );
+        Ok(())
+    }
+}
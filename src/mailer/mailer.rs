@@ -0,0 +1,54 @@
+use crate::errors::mail::MailError;
+use crate::mailer::{file::FileMailer, log::LogMailer};
+use crate::settings::settings;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Адрес отправителя по умолчанию.
+const DEFAULT_FROM: &str = "noreply@localhost";
+
+/// Исходящее письмо (`Mail`).
+///
+/// - `to` — адрес получателя.
+/// - `subject` — тема.
+/// - `body` — текст письма (`text/plain`).
+#[derive(Clone, Debug)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Трейт `Mailer` — интерфейс отправки писем.
+///
+/// Сервисы не знают, как доставляется письмо: реализация выбирается
+/// настройками окружения (`from_settings`).
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Отправка письма.
+    ///
+    /// :param mail: письмо.
+    /// :return: `()` либо `MailError`.
+    async fn send(&self, mail: Mail) -> Result<(), MailError>;
+}
+
+/// Создание почтового транспорта по настройкам окружения.
+///
+/// - `MAILER` — `log` (по умолчанию, письма пишутся в лог) или `file`.
+/// - `MAIL_DIR` — каталог для `file` (по умолчанию `mail`), по файлу `.eml` на письмо.
+/// - `MAIL_FROM` — адрес отправителя (по умолчанию `noreply@localhost`).
+///
+/// # Паника
+/// Если `MAILER` имеет неизвестное значение.
+pub fn from_settings() -> Arc<dyn Mailer> {
+    let from = settings::get_optional("MAIL_FROM").unwrap_or_else(|| DEFAULT_FROM.to_string());
+
+    match settings::get_optional("MAILER").as_deref() {
+        None | Some("log") => Arc::new(LogMailer::new(from)),
+        Some("file") => Arc::new(FileMailer::new(
+            settings::get_optional("MAIL_DIR").unwrap_or_else(|| "mail".to_string()),
+            from,
+        )),
+        Some(other) => panic!("MAILER `{}` is not supported", other),
+    }
+}
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod mailer;
+pub mod log;
+pub mod file;
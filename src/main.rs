@@ -1,7 +1,10 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
 use crate::db::db as other_db;
 use crate::settings::settings as other_settings;
 use crate::db::db::DatabaseTrait;
+use crate::services::series::TaskSeriesService;
+use crate::services::trash::TaskTrashService;
 use tokio::net::TcpListener;
 
 mod settings;
@@ -14,6 +17,8 @@ mod handlers;
 mod states;
 mod repositories;
 mod services;
+mod storage;
+mod mailer;
 mod middleware;
 mod routes;
 
@@ -26,6 +31,7 @@ async fn main() {
     let connection = other_db::Database::init()
         .await
         .unwrap_or_else(|e| panic!("❌ Database error: {}", e));
+    let connection = Arc::new(connection);
 
     // Чтение порта из .env
     let host = format!("0.0.0.0:{}", 3000);
@@ -38,11 +44,17 @@ async fn main() {
         .await
         .expect("Failed to bind address");
 
+    // Фоновое создание вхождений повторяющихся задач
+    TaskSeriesService::new(&connection).spawn_materializer();
+
+    // Фоновая очистка корзины задач
+    TaskTrashService::new(&connection).spawn_purger();
+
     // Инициализируем маршруты
-    let app = crate::routes::root::routes(Arc::new(connection));
+    let app = crate::routes::root::routes(connection);
 
-    // Запускаем сервер с axum::serve
-    axum::serve(listener, app)
+    // Запускаем сервер с axum::serve (адрес клиента нужен для списка сессий)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .expect("Server error");
 }
@@ -24,8 +24,11 @@ use jsonwebtoken::errors::ErrorKind;
 /// - Если токен истёк — `TokenError::TokenExpired`
 /// - Если токен некорректен — `TokenError::InvalidToken`
 /// - Если пользователь не найден — `UserError::UserNotFound`
+/// - Если email пользователя не подтверждён — `UserError::UserNotActive`
+/// - Если токен отозван (выход) — `TokenError::TokenRevoked`
 ///
-/// При успешной проверке пользователь добавляется в `Request.extensions()`.
+/// При успешной проверке пользователь и claims токена добавляются в `Request.extensions()`,
+/// а сессия отмечается активной (в фоне, не чаще раза в минуту).
 pub async fn auth(
     State(state): State<TokenState>,
     mut req: Request<Body>,
@@ -52,11 +55,23 @@ pub async fn auth(
     match state.token_service.retrieve_token_claims(token) {
         Ok(token_data) => {
             // Поиск пользователя по email из claims
-            let user = state.user_repo.find_by_email(token_data.claims.email).await;
+            let user = state.user_repo.find_by_email(token_data.claims.email.clone()).await;
 
             match user {
+                Some(user) if user.is_active == 0 => Err(UserError::UserNotActive.into()),
                 Some(user) => {
+                    // Проверка отзыва токена
+                    if state
+                        .revocation_service
+                        .is_revoked(&user, &token_data.claims)
+                        .await?
+                    {
+                        return Err(TokenError::TokenRevoked.into());
+                    }
+                    state.session_service.touch(token_data.claims.sid);
+
                     req.extensions_mut().insert(user); // Добавление user в request
+                    req.extensions_mut().insert(token_data.claims);
                     Ok(next.run(req).await)
                 }
                 None => Err(UserError::UserNotFound.into()),
@@ -0,0 +1,30 @@
+use crate::errors::api::ApiError;
+use crate::states::calendar::CalendarState;
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::Request,
+    middleware::Next,
+    response::IntoResponse,
+};
+
+/// Middleware-проверка токена календарной ленты (`feed_token`).
+///
+/// Календарные приложения не передают заголовок `Authorization`, поэтому
+/// вместо JWT пользователь определяется по секретному токену из пути
+/// (`/calendar/:token/...`).
+///
+/// - Если токен неверен или отозван — `CalendarError::FeedNotFound`
+///
+/// При успешной проверке пользователь добавляется в `Request.extensions()`.
+pub async fn feed_token(
+    State(state): State<CalendarState>,
+    Path(token): Path<String>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = state.calendar_service.authenticate(&token).await?;
+    req.extensions_mut().insert(user);
+    Ok(next.run(req).await)
+}
@@ -1 +1,3 @@
-pub mod auth;
\ No newline at end of file
+pub mod auth;
+pub mod request_id;
+pub mod calendar;
\ No newline at end of file
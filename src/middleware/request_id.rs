@@ -0,0 +1,51 @@
+use axum::{
+    body::Body,
+    http::{HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+/// Заголовок с идентификатором запроса.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Максимальная длина идентификатора, принимаемого от клиента.
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+tokio::task_local! {
+    /// Идентификатор запроса, обрабатываемого текущей задачей tokio.
+    static REQUEST_ID: String;
+}
+
+/// Middleware идентификатора запроса (`request_id`).
+///
+/// Берёт идентификатор из заголовка `X-Request-Id` (видимые ASCII-символы,
+/// не длиннее 64) или генерирует новый UUID. Идентификатор доступен
+/// обработчикам через `current()` и возвращается в заголовке ответа.
+pub async fn request_id(req: Request<Body>, next: Next) -> Response {
+    let id = req
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| {
+            !value.is_empty()
+                && value.len() <= MAX_REQUEST_ID_LEN
+                && value.bytes().all(|byte| byte.is_ascii_graphic())
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let mut response = REQUEST_ID.scope(id.clone(), next.run(req)).await;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
+
+/// Идентификатор текущего запроса.
+///
+/// :return: идентификатор или `None` вне обработки запроса (фоновые задачи).
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
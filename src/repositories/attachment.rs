@@ -0,0 +1,67 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::entities::attachment::Attachment;
+use async_trait::async_trait;
+use sqlx::Error;
+use std::sync::Arc;
+
+/// Репозиторий вложений (`AttachmentRepository`).
+///
+/// Предоставляет методы доступа к таблице `task_attachments`.
+#[derive(Clone)]
+pub struct AttachmentRepository {
+    pub(crate) db_conn: Arc<Database>,
+}
+
+/// Трейт `AttachmentRepositoryTrait` — интерфейс репозитория вложений.
+///
+/// - `new` — создание экземпляра репозитория.
+/// - `find` — поиск вложения по ID.
+/// - `find_by_task` — все вложения задачи.
+#[async_trait]
+pub trait AttachmentRepositoryTrait {
+    /// Создание нового экземпляра репозитория вложений.
+    ///
+    /// :param db_conn: подключение к базе данных.
+    fn new(db_conn: &Arc<Database>) -> Self;
+
+    /// Поиск вложения по ID.
+    ///
+    /// :param id: идентификатор вложения.
+    /// :return: `Some(Attachment)`, если вложение найдено, иначе `None`.
+    async fn find(&self, id: i32) -> Option<Attachment>;
+
+    /// Все вложения задачи в порядке загрузки.
+    ///
+    /// :param task_id: идентификатор задачи.
+    /// :return: список вложений либо `sqlx::Error`.
+    async fn find_by_task(&self, task_id: i32) -> Result<Vec<Attachment>, Error>;
+}
+
+#[async_trait]
+impl AttachmentRepositoryTrait for AttachmentRepository {
+    fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    async fn find(&self, id: i32) -> Option<Attachment> {
+        let result = sqlx::query_as::<_, Attachment>(
+            "SELECT * FROM task_attachments WHERE id = $1"
+        )
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await;
+
+        result.unwrap_or(None)
+    }
+
+    async fn find_by_task(&self, task_id: i32) -> Result<Vec<Attachment>, Error> {
+        sqlx::query_as::<_, Attachment>(
+            "SELECT * FROM task_attachments WHERE task_id = $1 ORDER BY id"
+        )
+            .bind(task_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+}
@@ -0,0 +1,70 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::entities::calendar::CalendarFeed;
+use crate::entities::user::User;
+use async_trait::async_trait;
+use sqlx::Error;
+use std::sync::Arc;
+
+/// Репозиторий календарных лент (`CalendarRepository`).
+///
+/// Предоставляет методы доступа к таблице `calendar_feeds`.
+#[derive(Clone)]
+pub struct CalendarRepository {
+    pub(crate) db_conn: Arc<Database>,
+}
+
+/// Трейт `CalendarRepositoryTrait` — интерфейс репозитория календарных лент.
+///
+/// - `new` — создание экземпляра репозитория.
+/// - `find` — лента пользователя.
+/// - `find_user_by_token_hash` — владелец ленты по хешу токена.
+#[async_trait]
+pub trait CalendarRepositoryTrait {
+    /// Создание нового экземпляра репозитория календарных лент.
+    ///
+    /// :param db_conn: подключение к базе данных.
+    fn new(db_conn: &Arc<Database>) -> Self;
+
+    /// Лента пользователя.
+    ///
+    /// :param user_id: идентификатор владельца ленты.
+    /// :return: `Some(CalendarFeed)`, если лента создана, `None` или `sqlx::Error`.
+    async fn find(&self, user_id: i32) -> Result<Option<CalendarFeed>, Error>;
+
+    /// Владелец ленты по хешу токена.
+    ///
+    /// :param token_hash: SHA-256 токена из URL (hex).
+    /// :return: `Some(User)`, если лента с таким токеном есть, `None` или `sqlx::Error`.
+    async fn find_user_by_token_hash(&self, token_hash: &str) -> Result<Option<User>, Error>;
+}
+
+#[async_trait]
+impl CalendarRepositoryTrait for CalendarRepository {
+    fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    async fn find(&self, user_id: i32) -> Result<Option<CalendarFeed>, Error> {
+        sqlx::query_as::<_, CalendarFeed>(
+            "SELECT * FROM calendar_feeds WHERE user_id = $1"
+        )
+            .bind(user_id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_user_by_token_hash(&self, token_hash: &str) -> Result<Option<User>, Error> {
+        sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.* FROM users u
+            JOIN calendar_feeds f ON f.user_id = u.id
+            WHERE f.token_hash = $1
+            "#,
+        )
+            .bind(token_hash)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+    }
+}
@@ -0,0 +1,171 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::pagination::{Cursor, CursorDirection};
+use crate::entities::comment::{Comment, CommentRevision, CommentWithAuthor};
+use async_trait::async_trait;
+use sqlx::Error;
+use std::sync::Arc;
+
+/// Выборка комментариев вместе с именем автора.
+const SELECT_WITH_AUTHOR: &str =
+    "SELECT c.*, u.user_name AS author_name FROM task_comments c JOIN users u ON u.id = c.user_id";
+
+/// Условие видимости корневого комментария: не удалён либо на него есть ответы.
+const VISIBLE_ROOT: &str = r#"
+    c.task_id = $1 AND c.parent_id IS NULL
+    AND (c.deleted_at IS NULL OR EXISTS (SELECT 1 FROM task_comments r WHERE r.parent_id = c.id))
+"#;
+
+/// Репозиторий комментариев (`CommentRepository`).
+///
+/// Предоставляет методы доступа к таблицам `task_comments` и `task_comment_revisions`.
+#[derive(Clone)]
+pub struct CommentRepository {
+    pub(crate) db_conn: Arc<Database>,
+}
+
+/// Трейт `CommentRepositoryTrait` — интерфейс репозитория комментариев.
+///
+/// - `new` — создание экземпляра репозитория.
+/// - `find` — поиск комментария по ID.
+/// - `find_with_author` — поиск комментария по ID вместе с именем автора.
+/// - `find_roots` — страница корневых комментариев задачи.
+/// - `count_roots` — количество видимых корневых комментариев задачи.
+/// - `find_replies` — все ответы (рекурсивно) на комментарии из списка.
+/// - `find_revisions` — история правок комментария.
+#[async_trait]
+pub trait CommentRepositoryTrait {
+    /// Создание нового экземпляра репозитория комментариев.
+    ///
+    /// :param db_conn: подключение к базе данных.
+    fn new(db_conn: &Arc<Database>) -> Self;
+
+    /// Поиск комментария по ID.
+    ///
+    /// :param id: идентификатор комментария.
+    /// :return: `Some(Comment)`, если комментарий найден, иначе `None`.
+    async fn find(&self, id: i32) -> Option<Comment>;
+
+    /// Поиск комментария по ID вместе с именем автора.
+    ///
+    /// :param id: идентификатор комментария.
+    /// :return: комментарий либо `sqlx::Error` (`RowNotFound`, если его нет).
+    async fn find_with_author(&self, id: i32) -> Result<CommentWithAuthor, Error>;
+
+    /// Страница корневых комментариев задачи в порядке создания.
+    ///
+    /// Удалённые комментарии без ответов пропускаются. Для `prev`-курсора
+    /// записи возвращаются в обратном порядке.
+    ///
+    /// :param task_id: идентификатор задачи.
+    /// :param cursor: курсор keyset-пагинации по `id`.
+    /// :param limit: максимальное количество записей.
+    /// :return: список комментариев либо `sqlx::Error`.
+    async fn find_roots(
+        &self,
+        task_id: i32,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<CommentWithAuthor>, Error>;
+
+    /// Количество видимых корневых комментариев задачи.
+    ///
+    /// :param task_id: идентификатор задачи.
+    /// :return: количество либо `sqlx::Error`.
+    async fn count_roots(&self, task_id: i32) -> Result<i64, Error>;
+
+    /// Все ответы на комментарии на любой глубине, в порядке создания.
+    ///
+    /// :param ids: идентификаторы комментариев.
+    /// :return: ответы (без самих комментариев) либо `sqlx::Error`.
+    async fn find_replies(&self, ids: &[i32]) -> Result<Vec<CommentWithAuthor>, Error>;
+
+    /// История правок комментария, от старых версий к новым.
+    ///
+    /// :param comment_id: идентификатор комментария.
+    /// :return: список версий либо `sqlx::Error`.
+    async fn find_revisions(&self, comment_id: i32) -> Result<Vec<CommentRevision>, Error>;
+}
+
+#[async_trait]
+impl CommentRepositoryTrait for CommentRepository {
+    fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    async fn find(&self, id: i32) -> Option<Comment> {
+        let result = sqlx::query_as::<_, Comment>(
+            "SELECT * FROM task_comments WHERE id = $1"
+        )
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await;
+
+        result.unwrap_or(None)
+    }
+
+    async fn find_with_author(&self, id: i32) -> Result<CommentWithAuthor, Error> {
+        sqlx::query_as::<_, CommentWithAuthor>(&format!("{} WHERE c.id = $1", SELECT_WITH_AUTHOR))
+            .bind(id)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_roots(
+        &self,
+        task_id: i32,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<CommentWithAuthor>, Error> {
+        let after = cursor.and_then(|cursor| cursor.values.first()?.parse::<i32>().ok());
+        let backwards = cursor.is_some_and(|cursor| cursor.direction == CursorDirection::Prev);
+        let (condition, order) = if backwards { ("<", "DESC") } else { (">", "ASC") };
+
+        sqlx::query_as::<_, CommentWithAuthor>(&format!(
+            "{} WHERE {} AND ($2::int IS NULL OR c.id {} $2) ORDER BY c.id {} LIMIT $3",
+            SELECT_WITH_AUTHOR, VISIBLE_ROOT, condition, order
+        ))
+            .bind(task_id)
+            .bind(after)
+            .bind(limit)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn count_roots(&self, task_id: i32) -> Result<i64, Error> {
+        sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM task_comments c WHERE {}",
+            VISIBLE_ROOT
+        ))
+            .bind(task_id)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_replies(&self, ids: &[i32]) -> Result<Vec<CommentWithAuthor>, Error> {
+        sqlx::query_as::<_, CommentWithAuthor>(&format!(
+            r#"
+            WITH RECURSIVE thread AS (
+                SELECT id FROM task_comments WHERE parent_id = ANY($1)
+                UNION ALL
+                SELECT t.id FROM task_comments t JOIN thread th ON t.parent_id = th.id
+            )
+            {} WHERE c.id IN (SELECT id FROM thread) ORDER BY c.id
+            "#,
+            SELECT_WITH_AUTHOR
+        ))
+            .bind(ids)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_revisions(&self, comment_id: i32) -> Result<Vec<CommentRevision>, Error> {
+        sqlx::query_as::<_, CommentRevision>(
+            "SELECT * FROM task_comment_revisions WHERE comment_id = $1 ORDER BY id"
+        )
+            .bind(comment_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+}
@@ -0,0 +1,103 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::entities::label::Label;
+use async_trait::async_trait;
+use sqlx::Error;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Репозиторий меток (`LabelRepository`).
+///
+/// Предоставляет методы доступа к таблицам `labels` и `task_labels`.
+#[derive(Clone)]
+pub struct LabelRepository {
+    pub(crate) db_conn: Arc<Database>,
+}
+
+/// Трейт `LabelRepositoryTrait` — интерфейс репозитория меток.
+///
+/// - `new` — создание экземпляра репозитория.
+/// - `find` — поиск метки по ID.
+/// - `find_all` — все метки пользователя.
+/// - `find_by_tasks` — метки, назначенные задачам из списка.
+#[async_trait]
+pub trait LabelRepositoryTrait {
+    /// Создание нового экземпляра репозитория меток.
+    ///
+    /// :param db_conn: подключение к базе данных.
+    fn new(db_conn: &Arc<Database>) -> Self;
+
+    /// Поиск метки по ID.
+    ///
+    /// :param id: идентификатор метки.
+    /// :return: `Some(Label)`, если метка найдена, иначе `None`.
+    async fn find(&self, id: i32) -> Option<Label>;
+
+    /// Все метки пользователя, отсортированные по названию.
+    ///
+    /// :param user_id: идентификатор владельца меток.
+    /// :return: список меток либо `sqlx::Error`.
+    async fn find_all(&self, user_id: i32) -> Result<Vec<Label>, Error>;
+
+    /// Метки, назначенные задачам.
+    ///
+    /// :param task_ids: идентификаторы задач.
+    /// :return: отображение `task_id → метки` (по названию) либо `sqlx::Error`.
+    async fn find_by_tasks(&self, task_ids: &[i32]) -> Result<HashMap<i32, Vec<Label>>, Error>;
+}
+
+#[async_trait]
+impl LabelRepositoryTrait for LabelRepository {
+    fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    async fn find(&self, id: i32) -> Option<Label> {
+        let result = sqlx::query_as::<_, Label>(
+            "SELECT * FROM labels WHERE id = $1"
+        )
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await;
+
+        result.unwrap_or(None)
+    }
+
+    async fn find_all(&self, user_id: i32) -> Result<Vec<Label>, Error> {
+        sqlx::query_as::<_, Label>(
+            "SELECT * FROM labels WHERE user_id = $1 ORDER BY LOWER(name), id"
+        )
+            .bind(user_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_by_tasks(&self, task_ids: &[i32]) -> Result<HashMap<i32, Vec<Label>>, Error> {
+        #[derive(sqlx::FromRow)]
+        struct TaskLabel {
+            task_id: i32,
+            #[sqlx(flatten)]
+            label: Label,
+        }
+
+        let rows = sqlx::query_as::<_, TaskLabel>(
+            r#"
+            SELECT tl.task_id, l.* FROM task_labels tl
+            JOIN labels l ON l.id = tl.label_id
+            WHERE tl.task_id = ANY($1)
+            ORDER BY LOWER(l.name), l.id
+            "#,
+        )
+            .bind(task_ids)
+            .fetch_all(self.db_conn.get_pool())
+            .await?;
+
+        let mut labels: HashMap<i32, Vec<Label>> = HashMap::new();
+        for row in rows {
+            labels.entry(row.task_id).or_default().push(row.label);
+        }
+
+        Ok(labels)
+    }
+}
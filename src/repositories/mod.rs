@@ -1 +1,10 @@
-pub mod user;
\ No newline at end of file
+pub mod user;
+pub mod task;
+pub mod label;
+pub mod comment;
+pub mod attachment;
+pub mod project;
+pub mod search;
+pub mod calendar;
+pub mod time_entry;
+pub mod session;
\ No newline at end of file
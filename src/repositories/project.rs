@@ -0,0 +1,148 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::entities::project::{ProjectMember, ProjectRole, ProjectWithRole};
+use async_trait::async_trait;
+use sqlx::Error;
+use std::sync::Arc;
+
+/// Выборка участников с именами пользователей.
+const SELECT_MEMBERS: &str =
+    "SELECT m.project_id, m.user_id, u.user_name, m.role, m.created_at FROM project_members m JOIN users u ON u.id = m.user_id";
+
+/// Репозиторий проектов (`ProjectRepository`).
+///
+/// Предоставляет методы доступа к таблицам `projects` и `project_members`.
+#[derive(Clone)]
+pub struct ProjectRepository {
+    pub(crate) db_conn: Arc<Database>,
+}
+
+/// Трейт `ProjectRepositoryTrait` — интерфейс репозитория проектов.
+///
+/// - `new` — создание экземпляра репозитория.
+/// - `exists` — проверка существования проекта.
+/// - `find_for_user` — проект вместе с ролью пользователя.
+/// - `find_all` — проекты, в которых участвует пользователь.
+/// - `find_role` — роль пользователя в проекте.
+/// - `find_members` — участники проекта.
+/// - `find_member` — участник проекта по ID пользователя.
+#[async_trait]
+pub trait ProjectRepositoryTrait {
+    /// Создание нового экземпляра репозитория проектов.
+    ///
+    /// :param db_conn: подключение к базе данных.
+    fn new(db_conn: &Arc<Database>) -> Self;
+
+    /// Проверка существования проекта.
+    ///
+    /// :param id: идентификатор проекта.
+    /// :return: `true`, если проект существует, либо `sqlx::Error`.
+    async fn exists(&self, id: i32) -> Result<bool, Error>;
+
+    /// Проект вместе с ролью пользователя.
+    ///
+    /// :param id: идентификатор проекта.
+    /// :param user_id: идентификатор пользователя.
+    /// :return: `Some(ProjectWithRole)`, если пользователь участник проекта, иначе `None`.
+    async fn find_for_user(&self, id: i32, user_id: i32) -> Result<Option<ProjectWithRole>, Error>;
+
+    /// Проекты, в которых участвует пользователь, отсортированные по названию.
+    ///
+    /// :param user_id: идентификатор пользователя.
+    /// :return: список проектов с ролями либо `sqlx::Error`.
+    async fn find_all(&self, user_id: i32) -> Result<Vec<ProjectWithRole>, Error>;
+
+    /// Роль пользователя в проекте.
+    ///
+    /// :param id: идентификатор проекта.
+    /// :param user_id: идентификатор пользователя.
+    /// :return: `Some(ProjectRole)` для участника, иначе `None`.
+    async fn find_role(&self, id: i32, user_id: i32) -> Result<Option<ProjectRole>, Error>;
+
+    /// Участники проекта: сначала владельцы, затем по имени.
+    ///
+    /// :param id: идентификатор проекта.
+    /// :return: список участников либо `sqlx::Error`.
+    async fn find_members(&self, id: i32) -> Result<Vec<ProjectMember>, Error>;
+
+    /// Участник проекта по ID пользователя.
+    ///
+    /// :param id: идентификатор проекта.
+    /// :param user_id: идентификатор пользователя.
+    /// :return: `Some(ProjectMember)`, если пользователь участник проекта, иначе `None`.
+    async fn find_member(&self, id: i32, user_id: i32) -> Result<Option<ProjectMember>, Error>;
+}
+
+#[async_trait]
+impl ProjectRepositoryTrait for ProjectRepository {
+    fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    async fn exists(&self, id: i32) -> Result<bool, Error> {
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM projects WHERE id = $1)")
+            .bind(id)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_for_user(&self, id: i32, user_id: i32) -> Result<Option<ProjectWithRole>, Error> {
+        sqlx::query_as::<_, ProjectWithRole>(
+            r#"
+            SELECT p.*, m.role FROM projects p
+            JOIN project_members m ON m.project_id = p.id
+            WHERE p.id = $1 AND m.user_id = $2
+            "#,
+        )
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_all(&self, user_id: i32) -> Result<Vec<ProjectWithRole>, Error> {
+        sqlx::query_as::<_, ProjectWithRole>(
+            r#"
+            SELECT p.*, m.role FROM projects p
+            JOIN project_members m ON m.project_id = p.id
+            WHERE m.user_id = $1
+            ORDER BY LOWER(p.name), p.id
+            "#,
+        )
+            .bind(user_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_role(&self, id: i32, user_id: i32) -> Result<Option<ProjectRole>, Error> {
+        sqlx::query_scalar::<_, ProjectRole>(
+            "SELECT role FROM project_members WHERE project_id = $1 AND user_id = $2"
+        )
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_members(&self, id: i32) -> Result<Vec<ProjectMember>, Error> {
+        sqlx::query_as::<_, ProjectMember>(&format!(
+            "{} WHERE m.project_id = $1 ORDER BY m.role, LOWER(u.user_name), m.user_id",
+            SELECT_MEMBERS
+        ))
+            .bind(id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_member(&self, id: i32, user_id: i32) -> Result<Option<ProjectMember>, Error> {
+        sqlx::query_as::<_, ProjectMember>(&format!(
+            "{} WHERE m.project_id = $1 AND m.user_id = $2",
+            SELECT_MEMBERS
+        ))
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+    }
+}
@@ -0,0 +1,87 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::entities::search::SearchHit;
+use crate::repositories::task::VISIBLE_TO_USER;
+use async_trait::async_trait;
+use sqlx::Error;
+use std::sync::Arc;
+
+/// Параметры `ts_headline` для фрагментов выдачи.
+const HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MinWords=10, MaxWords=30, MaxFragments=2, FragmentDelimiter=\" … \"";
+
+/// Репозиторий полнотекстового поиска (`SearchRepository`).
+///
+/// Использует поисковые векторы `tasks.search_vector` и `task_comments.search_vector`.
+#[derive(Clone)]
+pub struct SearchRepository {
+    pub(crate) db_conn: Arc<Database>,
+}
+
+/// Трейт `SearchRepositoryTrait` — интерфейс репозитория поиска.
+///
+/// - `new` — создание экземпляра репозитория.
+/// - `find` — поиск задач и комментариев, доступных пользователю.
+#[async_trait]
+pub trait SearchRepositoryTrait {
+    /// Создание нового экземпляра репозитория поиска.
+    ///
+    /// :param db_conn: подключение к базе данных.
+    fn new(db_conn: &Arc<Database>) -> Self;
+
+    /// Поиск задач и комментариев по запросу `tsquery`.
+    ///
+    /// Совпадения в заголовке задачи весят больше совпадений в описании.
+    /// Удалённые комментарии не ищутся. Фрагменты строятся только для
+    /// возвращаемой страницы.
+    ///
+    /// :param user_id: идентификатор пользователя (личные задачи и задачи его проектов).
+    /// :param tsquery: запрос в синтаксисе `to_tsquery`.
+    /// :param limit: максимальное количество результатов.
+    /// :param offset: смещение.
+    /// :return: результаты по убыванию релевантности либо `sqlx::Error`.
+    async fn find(&self, user_id: i32, tsquery: &str, limit: i64, offset: i64) -> Result<Vec<SearchHit>, Error>;
+}
+
+#[async_trait]
+impl SearchRepositoryTrait for SearchRepository {
+    fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    async fn find(&self, user_id: i32, tsquery: &str, limit: i64, offset: i64) -> Result<Vec<SearchHit>, Error> {
+        sqlx::query_as::<_, SearchHit>(&format!(
+            r#"
+            WITH query AS (SELECT to_tsquery('simple', $2) AS q),
+            hits AS (
+                SELECT tasks.id AS task_id, NULL::integer AS comment_id, ts_rank(tasks.search_vector, query.q) AS rank
+                FROM tasks, query
+                WHERE tasks.search_vector @@ query.q AND {visible}
+                UNION ALL
+                SELECT c.task_id, c.id, ts_rank(c.search_vector, query.q)
+                FROM task_comments c JOIN tasks ON tasks.id = c.task_id, query
+                WHERE c.search_vector @@ query.q AND c.deleted_at IS NULL AND {visible}
+                ORDER BY rank DESC, task_id DESC, comment_id NULLS FIRST
+                LIMIT $3 OFFSET $4
+            )
+            SELECT h.task_id, h.comment_id, t.title,
+                   ts_headline('simple', COALESCE(c.body, t.title || E'\n' || COALESCE(t.description, '')), query.q, $5) AS snippet,
+                   h.rank
+            FROM hits h
+            JOIN tasks t ON t.id = h.task_id
+            LEFT JOIN task_comments c ON c.id = h.comment_id
+            CROSS JOIN query
+            ORDER BY h.rank DESC, h.task_id DESC, h.comment_id NULLS FIRST
+            "#,
+            visible = VISIBLE_TO_USER
+        ))
+            .bind(user_id)
+            .bind(tsquery)
+            .bind(limit)
+            .bind(offset)
+            .bind(HEADLINE_OPTIONS)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+}
@@ -0,0 +1,53 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::entities::session::Session;
+use async_trait::async_trait;
+use sqlx::Error;
+use std::sync::Arc;
+
+/// Репозиторий сессий (`SessionRepository`).
+///
+/// Предоставляет методы доступа к таблице `sessions`.
+#[derive(Clone)]
+pub struct SessionRepository {
+    pub(crate) db_conn: Arc<Database>,
+}
+
+/// Трейт `SessionRepositoryTrait` — интерфейс репозитория сессий.
+///
+/// - `new` — создание экземпляра репозитория.
+/// - `find_active` — действующие сессии пользователя.
+#[async_trait]
+pub trait SessionRepositoryTrait {
+    /// Создание нового экземпляра репозитория сессий.
+    ///
+    /// :param db_conn: подключение к базе данных.
+    fn new(db_conn: &Arc<Database>) -> Self;
+
+    /// Действующие (не завершённые и не истёкшие) сессии пользователя.
+    ///
+    /// :param user_id: идентификатор пользователя.
+    /// :return: сессии, начиная с последней активной, либо `sqlx::Error`.
+    async fn find_active(&self, user_id: i32) -> Result<Vec<Session>, Error>;
+}
+
+#[async_trait]
+impl SessionRepositoryTrait for SessionRepository {
+    fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    async fn find_active(&self, user_id: i32) -> Result<Vec<Session>, Error> {
+        sqlx::query_as::<_, Session>(
+            r#"
+            SELECT * FROM sessions
+            WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > now()
+            ORDER BY last_seen_at DESC, created_at DESC
+            "#,
+        )
+            .bind(user_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+}
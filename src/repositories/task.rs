@@ -0,0 +1,636 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::pagination::{Cursor, CursorDirection, SortDirection, SortKey};
+use crate::dto::task::{LabelMatch, TaskListQuery, TaskSortField};
+use crate::entities::task::{Task, TaskEvent, TaskWatcher};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Error, Postgres, QueryBuilder};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Условие доступности задачи пользователю (`$1`): не удалённая личная задача
+/// пользователя или задача проекта, в котором он участвует.
+pub(crate) const VISIBLE_TO_USER: &str = r#"
+    (tasks.deleted_at IS NULL
+        AND ((tasks.project_id IS NULL AND tasks.user_id = $1)
+            OR tasks.project_id IN (SELECT project_id FROM project_members WHERE user_id = $1)))
+"#;
+
+/// Репозиторий задач (`TaskRepository`).
+///
+/// Предоставляет методы доступа к таблице задач в базе данных.
+/// Задачи в корзине (`deleted_at IS NOT NULL`) возвращают только
+/// `find_deleted` и `find_trash`.
+#[derive(Clone)]
+pub struct TaskRepository {
+    pub(crate) db_conn: Arc<Database>,
+}
+
+/// Трейт `TaskRepositoryTrait` — интерфейс репозитория задач.
+///
+/// Определяет базовые методы чтения из таблицы задач.
+///
+/// - `new` — создание экземпляра репозитория.
+/// - `find` — поиск задачи по ID.
+/// - `find_deleted` — поиск задачи в корзине по ID.
+/// - `find_trash` — задачи пользователя в корзине.
+/// - `find_page` — страница задач пользователя с фильтрами, сортировкой и пагинацией.
+/// - `count` — количество задач пользователя, подходящих под фильтры.
+/// - `find_due` — открытые задачи пользователя со сроком в заданном интервале.
+/// - `find_descendants` — все подзадачи задачи (рекурсивно).
+/// - `is_ancestor` — проверка, является ли задача предком другой задачи.
+/// - `progress` — процент выполненных подзадач для списка задач.
+/// - `find_dependencies` — задачи, от которых зависит задача.
+/// - `find_blocked` — какие из задач заблокированы незавершёнными зависимостями.
+/// - `find_open` — открытые задачи пользователя.
+/// - `find_dependency_edges` — рёбра графа зависимостей пользователя.
+/// - `find_assignees` — исполнители для списка задач.
+/// - `tracked_seconds` — учтённое время для списка задач.
+/// - `find_assigned` — открытые задачи, назначенные пользователю.
+/// - `find_watchers` — наблюдатели задачи.
+/// - `find_events` — история изменений задачи.
+/// - `find_export_batch` — порция задач пользователя для экспорта.
+#[async_trait]
+pub trait TaskRepositoryTrait {
+    /// Создание нового экземпляра репозитория задач.
+    ///
+    /// :param db_conn: подключение к базе данных.
+    fn new(db_conn: &Arc<Database>) -> Self;
+
+    /// Поиск задачи по ID.
+    ///
+    /// :param id: идентификатор задачи.
+    /// :return: `Some(Task)`, если задача найдена и не удалена, иначе `None`.
+    async fn find(&self, id: i32) -> Option<Task>;
+
+    /// Поиск задачи в корзине по ID.
+    ///
+    /// :param id: идентификатор задачи.
+    /// :return: `Some(Task)`, если задача находится в корзине, иначе `None`.
+    async fn find_deleted(&self, id: i32) -> Option<Task>;
+
+    /// Задачи в корзине, доступные пользователю.
+    ///
+    /// :param user_id: идентификатор пользователя (личные задачи и задачи его проектов).
+    /// :return: задачи, начиная с последних удалённых, либо `sqlx::Error`.
+    async fn find_trash(&self, user_id: i32) -> Result<Vec<Task>, Error>;
+
+    /// Получение страницы задач пользователя.
+    ///
+    /// При наличии курсора выборка начинается после (или до) граничной записи курсора,
+    /// а `offset` из запроса не применяется. Для `prev`-курсора записи возвращаются
+    /// в обратном порядке сортировки.
+    ///
+    /// :param user_id: идентификатор пользователя (личные задачи и задачи его проектов).
+    /// :param query: фильтры и смещение.
+    /// :param sort: ключи сортировки (последний ключ должен быть уникальным).
+    /// :param cursor: курсор keyset-пагинации.
+    /// :param limit: максимальное количество записей.
+    /// :return: список задач либо `sqlx::Error`.
+    async fn find_page(
+        &self,
+        user_id: i32,
+        query: &TaskListQuery,
+        sort: &[SortKey<TaskSortField>],
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<Task>, Error>;
+
+    /// Количество задач пользователя, подходящих под фильтры.
+    ///
+    /// :param user_id: идентификатор пользователя (личные задачи и задачи его проектов).
+    /// :param query: фильтры.
+    /// :return: количество задач либо `sqlx::Error`.
+    async fn count(&self, user_id: i32, query: &TaskListQuery) -> Result<i64, Error>;
+
+    /// Открытые (не `done` и не `cancelled`) задачи со сроком в интервале `[from, to)`.
+    ///
+    /// :param user_id: идентификатор пользователя (личные задачи и задачи его проектов).
+    /// :param from: начало интервала (`None` — без нижней границы).
+    /// :param to: конец интервала (не включительно).
+    /// :return: задачи, отсортированные по сроку, либо `sqlx::Error`.
+    async fn find_due(
+        &self,
+        user_id: i32,
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Task>, Error>;
+
+    /// Все подзадачи задачи на любой глубине вложенности.
+    ///
+    /// :param id: идентификатор корневой задачи.
+    /// :return: подзадачи (без самой задачи) либо `sqlx::Error`.
+    async fn find_descendants(&self, id: i32) -> Result<Vec<Task>, Error>;
+
+    /// Проверка, является ли `ancestor_id` самой задачей `id` или её предком.
+    ///
+    /// Используется для предотвращения циклов при смене родителя.
+    ///
+    /// :param ancestor_id: предполагаемый предок.
+    /// :param id: задача, цепочка родителей которой проверяется.
+    /// :return: `true`, если `ancestor_id` встречается в цепочке, либо `sqlx::Error`.
+    async fn is_ancestor(&self, ancestor_id: i32, id: i32) -> Result<bool, Error>;
+
+    /// Процент выполненных непосредственных подзадач.
+    ///
+    /// Отменённые подзадачи не учитываются. Задачи без подзадач в результат не попадают.
+    ///
+    /// :param ids: идентификаторы родительских задач.
+    /// :return: отображение `id → процент` либо `sqlx::Error`.
+    async fn progress(&self, ids: &[i32]) -> Result<HashMap<i32, i32>, Error>;
+
+    /// Задачи, от которых непосредственно зависит задача.
+    ///
+    /// :param id: идентификатор задачи.
+    /// :return: блокирующие задачи либо `sqlx::Error`.
+    async fn find_dependencies(&self, id: i32) -> Result<Vec<Task>, Error>;
+
+    /// Задачи из списка, у которых есть незавершённые (не `done` и не `cancelled`) зависимости.
+    ///
+    /// :param ids: идентификаторы проверяемых задач.
+    /// :return: множество заблокированных задач либо `sqlx::Error`.
+    async fn find_blocked(&self, ids: &[i32]) -> Result<HashSet<i32>, Error>;
+
+    /// Открытые (не `done` и не `cancelled`) задачи пользователя.
+    ///
+    /// :param user_id: идентификатор пользователя (личные задачи и задачи его проектов).
+    /// :return: список задач либо `sqlx::Error`.
+    async fn find_open(&self, user_id: i32) -> Result<Vec<Task>, Error>;
+
+    /// Рёбра `(task_id, depends_on_id)` между задачами пользователя.
+    ///
+    /// :param user_id: идентификатор пользователя (личные задачи и задачи его проектов).
+    /// :return: список рёбер либо `sqlx::Error`.
+    async fn find_dependency_edges(&self, user_id: i32) -> Result<Vec<(i32, i32)>, Error>;
+
+    /// Исполнители задач.
+    ///
+    /// :param ids: идентификаторы задач.
+    /// :return: отображение `task_id → [user_id]` либо `sqlx::Error`.
+    async fn find_assignees(&self, ids: &[i32]) -> Result<HashMap<i32, Vec<i32>>, Error>;
+
+    /// Учтённое время по задачам всех пользователей.
+    ///
+    /// Запущенные таймеры учитываются до текущего момента. Задачи без записей
+    /// в результат не попадают.
+    ///
+    /// :param ids: идентификаторы задач.
+    /// :return: отображение `task_id → секунды` либо `sqlx::Error`.
+    async fn tracked_seconds(&self, ids: &[i32]) -> Result<HashMap<i32, i64>, Error>;
+
+    /// Открытые (не `done` и не `cancelled`) задачи, назначенные пользователю.
+    ///
+    /// Учитываются только задачи, к которым у пользователя остался доступ.
+    ///
+    /// :param user_id: идентификатор исполнителя.
+    /// :return: задачи, отсортированные по сроку, либо `sqlx::Error`.
+    async fn find_assigned(&self, user_id: i32) -> Result<Vec<Task>, Error>;
+
+    /// Наблюдатели задачи.
+    ///
+    /// :param id: идентификатор задачи.
+    /// :return: наблюдатели в порядке подписки либо `sqlx::Error`.
+    async fn find_watchers(&self, id: i32) -> Result<Vec<TaskWatcher>, Error>;
+
+    /// История изменений задачи.
+    ///
+    /// :param id: идентификатор задачи.
+    /// :return: события в порядке записи либо `sqlx::Error`.
+    async fn find_events(&self, id: i32) -> Result<Vec<TaskEvent>, Error>;
+
+    /// Порция задач пользователя для экспорта (keyset по `id`).
+    ///
+    /// :param user_id: идентификатор пользователя (личные задачи и задачи его проектов).
+    /// :param after_id: последний `id` предыдущей порции (`None` — с начала).
+    /// :param limit: размер порции.
+    /// :return: задачи по возрастанию `id` либо `sqlx::Error`.
+    async fn find_export_batch(&self, user_id: i32, after_id: Option<i32>, limit: i64) -> Result<Vec<Task>, Error>;
+}
+
+#[async_trait]
+impl TaskRepositoryTrait for TaskRepository {
+    fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    async fn find(&self, id: i32) -> Option<Task> {
+        let result = sqlx::query_as::<_, Task>(
+            "SELECT * FROM tasks WHERE id = $1 AND deleted_at IS NULL"
+        )
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await;
+
+        result.unwrap_or(None)
+    }
+
+    async fn find_deleted(&self, id: i32) -> Option<Task> {
+        let result = sqlx::query_as::<_, Task>(
+            "SELECT * FROM tasks WHERE id = $1 AND deleted_at IS NOT NULL"
+        )
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await;
+
+        result.unwrap_or(None)
+    }
+
+    async fn find_trash(&self, user_id: i32) -> Result<Vec<Task>, Error> {
+        sqlx::query_as::<_, Task>(
+            r#"
+            SELECT * FROM tasks
+            WHERE deleted_at IS NOT NULL
+              AND ((project_id IS NULL AND user_id = $1)
+                OR project_id IN (SELECT project_id FROM project_members WHERE user_id = $1))
+            ORDER BY deleted_at DESC, id
+            "#,
+        )
+            .bind(user_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_page(
+        &self,
+        user_id: i32,
+        query: &TaskListQuery,
+        sort: &[SortKey<TaskSortField>],
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<Task>, Error> {
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT * FROM tasks");
+        push_filters(&mut builder, user_id, query);
+
+        let backwards = cursor.is_some_and(|cursor| cursor.direction == CursorDirection::Prev);
+
+        // Условие keyset: кортеж ключей строго после (до) значений курсора
+        if let Some(cursor) = cursor {
+            builder.push(" AND (");
+            for (i, key) in sort.iter().enumerate() {
+                if i > 0 {
+                    builder.push(" OR ");
+                }
+                builder.push("(");
+                for (j, previous) in sort[..i].iter().enumerate() {
+                    let (column, sql_type) = sort_column(previous.field);
+                    builder
+                        .push(format!("{} = ", column))
+                        .push_bind(cursor.values[j].clone())
+                        .push(format!("::{} AND ", sql_type));
+                }
+                let (column, sql_type) = sort_column(key.field);
+                let ascending = (key.direction == SortDirection::Asc) != backwards;
+                builder
+                    .push(format!("{} {} ", column, if ascending { ">" } else { "<" }))
+                    .push_bind(cursor.values[i].clone())
+                    .push(format!("::{})", sql_type));
+            }
+            builder.push(")");
+        }
+
+        builder.push(" ORDER BY ");
+        for (i, key) in sort.iter().enumerate() {
+            if i > 0 {
+                builder.push(", ");
+            }
+            let ascending = (key.direction == SortDirection::Asc) != backwards;
+            let (column, _) = sort_column(key.field);
+            builder.push(format!("{} {}", column, if ascending { "ASC" } else { "DESC" }));
+        }
+
+        builder.push(" LIMIT ").push_bind(limit);
+        if cursor.is_none() {
+            builder.push(" OFFSET ").push_bind(query.offset.unwrap_or(0));
+        }
+
+        builder
+            .build_query_as::<Task>()
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn count(&self, user_id: i32, query: &TaskListQuery) -> Result<i64, Error> {
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM tasks");
+        push_filters(&mut builder, user_id, query);
+
+        builder
+            .build_query_scalar::<i64>()
+            .fetch_one(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_due(
+        &self,
+        user_id: i32,
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Task>, Error> {
+        sqlx::query_as::<_, Task>(&format!(
+            r#"
+            SELECT * FROM tasks
+            WHERE {}
+              AND status NOT IN ('done', 'cancelled')
+              AND due_at < $3
+              AND ($2::timestamptz IS NULL OR due_at >= $2)
+            ORDER BY due_at, id
+            "#,
+            VISIBLE_TO_USER
+        ))
+            .bind(user_id)
+            .bind(from)
+            .bind(to)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_descendants(&self, id: i32) -> Result<Vec<Task>, Error> {
+        sqlx::query_as::<_, Task>(
+            r#"
+            WITH RECURSIVE subtree AS (
+                SELECT * FROM tasks WHERE parent_id = $1 AND deleted_at IS NULL
+                UNION ALL
+                SELECT t.* FROM tasks t JOIN subtree s ON t.parent_id = s.id WHERE t.deleted_at IS NULL
+            )
+            SELECT * FROM subtree ORDER BY created_at, id
+            "#,
+        )
+            .bind(id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn is_ancestor(&self, ancestor_id: i32, id: i32) -> Result<bool, Error> {
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            WITH RECURSIVE chain AS (
+                SELECT id, parent_id FROM tasks WHERE id = $2
+                UNION
+                SELECT t.id, t.parent_id FROM tasks t JOIN chain c ON t.id = c.parent_id
+            )
+            SELECT EXISTS (SELECT 1 FROM chain WHERE id = $1)
+            "#,
+        )
+            .bind(ancestor_id)
+            .bind(id)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn progress(&self, ids: &[i32]) -> Result<HashMap<i32, i32>, Error> {
+        let rows = sqlx::query_as::<_, (i32, i32)>(
+            r#"
+            SELECT parent_id,
+                   (100 * COUNT(*) FILTER (WHERE status = 'done')
+                        / GREATEST(COUNT(*) FILTER (WHERE status <> 'cancelled'), 1))::int
+            FROM tasks
+            WHERE parent_id = ANY($1) AND deleted_at IS NULL
+            GROUP BY parent_id
+            "#,
+        )
+            .bind(ids)
+            .fetch_all(self.db_conn.get_pool())
+            .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    async fn find_dependencies(&self, id: i32) -> Result<Vec<Task>, Error> {
+        sqlx::query_as::<_, Task>(
+            r#"
+            SELECT t.* FROM tasks t
+            JOIN task_dependencies d ON d.depends_on_id = t.id
+            WHERE d.task_id = $1 AND t.deleted_at IS NULL
+            ORDER BY t.created_at, t.id
+            "#,
+        )
+            .bind(id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_blocked(&self, ids: &[i32]) -> Result<HashSet<i32>, Error> {
+        let rows = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT DISTINCT d.task_id FROM task_dependencies d
+            JOIN tasks t ON t.id = d.depends_on_id
+            WHERE d.task_id = ANY($1)
+              AND t.status NOT IN ('done', 'cancelled')
+              AND t.deleted_at IS NULL
+            "#,
+        )
+            .bind(ids)
+            .fetch_all(self.db_conn.get_pool())
+            .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    async fn find_open(&self, user_id: i32) -> Result<Vec<Task>, Error> {
+        sqlx::query_as::<_, Task>(&format!(
+            r#"
+            SELECT * FROM tasks
+            WHERE {} AND status NOT IN ('done', 'cancelled')
+            ORDER BY created_at, id
+            "#,
+            VISIBLE_TO_USER
+        ))
+            .bind(user_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_dependency_edges(&self, user_id: i32) -> Result<Vec<(i32, i32)>, Error> {
+        sqlx::query_as::<_, (i32, i32)>(&format!(
+            r#"
+            SELECT d.task_id, d.depends_on_id FROM task_dependencies d
+            JOIN tasks ON tasks.id = d.task_id
+            WHERE {}
+            "#,
+            VISIBLE_TO_USER
+        ))
+            .bind(user_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_assignees(&self, ids: &[i32]) -> Result<HashMap<i32, Vec<i32>>, Error> {
+        let rows = sqlx::query_as::<_, (i32, i32)>(
+            "SELECT task_id, user_id FROM task_assignees WHERE task_id = ANY($1) ORDER BY created_at, user_id",
+        )
+            .bind(ids)
+            .fetch_all(self.db_conn.get_pool())
+            .await?;
+
+        let mut assignees: HashMap<i32, Vec<i32>> = HashMap::new();
+        for (task_id, user_id) in rows {
+            assignees.entry(task_id).or_default().push(user_id);
+        }
+
+        Ok(assignees)
+    }
+
+    async fn tracked_seconds(&self, ids: &[i32]) -> Result<HashMap<i32, i64>, Error> {
+        let rows = sqlx::query_as::<_, (i32, i64)>(
+            r#"
+            SELECT task_id, SUM(EXTRACT(EPOCH FROM COALESCE(ended_at, now()) - started_at))::bigint
+            FROM time_entries
+            WHERE task_id = ANY($1)
+            GROUP BY task_id
+            "#,
+        )
+            .bind(ids)
+            .fetch_all(self.db_conn.get_pool())
+            .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    async fn find_assigned(&self, user_id: i32) -> Result<Vec<Task>, Error> {
+        sqlx::query_as::<_, Task>(&format!(
+            r#"
+            SELECT tasks.* FROM tasks
+            JOIN task_assignees a ON a.task_id = tasks.id AND a.user_id = $1
+            WHERE {} AND status NOT IN ('done', 'cancelled')
+            ORDER BY due_at NULLS LAST, id
+            "#,
+            VISIBLE_TO_USER
+        ))
+            .bind(user_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_watchers(&self, id: i32) -> Result<Vec<TaskWatcher>, Error> {
+        sqlx::query_as::<_, TaskWatcher>(
+            r#"
+            SELECT w.task_id, w.user_id, u.user_name, w.created_at
+            FROM task_watchers w
+            JOIN users u ON u.id = w.user_id
+            WHERE w.task_id = $1
+            ORDER BY w.created_at, w.user_id
+            "#,
+        )
+            .bind(id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_events(&self, id: i32) -> Result<Vec<TaskEvent>, Error> {
+        sqlx::query_as::<_, TaskEvent>(
+            r#"
+            SELECT e.id, e.task_id, e.actor_id, u.user_name AS actor_name, e.action, e.changes, e.request_id, e.created_at
+            FROM task_events e
+            LEFT JOIN users u ON u.id = e.actor_id
+            WHERE e.task_id = $1
+            ORDER BY e.id
+            "#,
+        )
+            .bind(id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_export_batch(&self, user_id: i32, after_id: Option<i32>, limit: i64) -> Result<Vec<Task>, Error> {
+        sqlx::query_as::<_, Task>(&format!(
+            r#"
+            SELECT * FROM tasks
+            WHERE {} AND ($2::int IS NULL OR id > $2)
+            ORDER BY id
+            LIMIT $3
+            "#,
+            VISIBLE_TO_USER
+        ))
+            .bind(user_id)
+            .bind(after_id)
+            .bind(limit)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+}
+
+/// Добавление условия `WHERE` по доступности задач пользователю и фильтрам списка.
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, user_id: i32, query: &TaskListQuery) {
+    builder
+        .push(" WHERE deleted_at IS NULL AND ((project_id IS NULL AND user_id = ")
+        .push_bind(user_id)
+        .push(") OR project_id IN (SELECT project_id FROM project_members WHERE user_id = ")
+        .push_bind(user_id)
+        .push("))");
+
+    if let Some(project_id) = query.project_id {
+        builder.push(" AND project_id = ").push_bind(project_id);
+    }
+
+    if let Some(title) = &query.title {
+        builder
+            .push(" AND title ILIKE ")
+            .push_bind(format!("%{}%", escape_like(title)));
+    }
+
+    if let Some(statuses) = &query.status {
+        let statuses: Vec<&str> = statuses.iter().map(|status| status.as_str()).collect();
+        builder
+            .push(" AND status = ANY(")
+            .push_bind(statuses)
+            .push("::task_status[])");
+    }
+
+    if let Some(due_after) = query.due_after {
+        builder.push(" AND due_at >= ").push_bind(due_after);
+    }
+
+    if let Some(due_before) = query.due_before {
+        builder.push(" AND due_at <= ").push_bind(due_before);
+    }
+
+    if let Some(parent_id) = query.parent_id {
+        builder.push(" AND parent_id = ").push_bind(parent_id);
+    }
+
+    if let Some(labels) = query.labels.as_ref().filter(|labels| !labels.is_empty()) {
+        let mut labels = labels.clone();
+        labels.sort_unstable();
+        labels.dedup();
+        match query.labels_match {
+            LabelMatch::Any => {
+                builder
+                    .push(" AND id IN (SELECT task_id FROM task_labels WHERE label_id = ANY(")
+                    .push_bind(labels)
+                    .push("))");
+            }
+            LabelMatch::All => {
+                let count = labels.len() as i64;
+                builder
+                    .push(" AND (SELECT COUNT(*) FROM task_labels WHERE task_id = tasks.id AND label_id = ANY(")
+                    .push_bind(labels)
+                    .push(")) = ")
+                    .push_bind(count);
+            }
+        }
+    }
+}
+
+/// SQL-выражение и тип для поля сортировки.
+///
+/// Выражения не допускают `NULL`, чтобы сравнение кортежей keyset было корректным.
+fn sort_column(field: TaskSortField) -> (&'static str, &'static str) {
+    match field {
+        TaskSortField::Id => ("id", "integer"),
+        TaskSortField::Title => ("title", "text"),
+        TaskSortField::CreatedAt => ("created_at", "timestamp"),
+        TaskSortField::UpdatedAt => ("COALESCE(updated_at, created_at)", "timestamp"),
+        TaskSortField::Status => ("status", "task_status"),
+        TaskSortField::DueAt => ("COALESCE(due_at, 'infinity')", "timestamptz"),
+        TaskSortField::Priority => ("priority", "task_priority"),
+    }
+}
+
+/// Экранирование спецсимволов `LIKE` в пользовательской строке.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
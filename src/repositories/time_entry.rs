@@ -0,0 +1,144 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::time_entry::TimeReportGroup;
+use crate::entities::time_entry::{TimeEntry, TimeReportRow};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Error;
+use std::sync::Arc;
+
+/// Репозиторий записей учёта времени (`TimeEntryRepository`).
+///
+/// Предоставляет методы доступа к таблице `time_entries`.
+#[derive(Clone)]
+pub struct TimeEntryRepository {
+    pub(crate) db_conn: Arc<Database>,
+}
+
+/// Трейт `TimeEntryRepositoryTrait` — интерфейс репозитория записей учёта времени.
+///
+/// - `new` — создание экземпляра репозитория.
+/// - `find` — поиск записи по ID.
+/// - `find_by_task` — записи пользователя по задаче.
+/// - `find_running` — запущенный таймер пользователя.
+/// - `report` — учтённое пользователем время за период с группировкой.
+#[async_trait]
+pub trait TimeEntryRepositoryTrait {
+    /// Создание нового экземпляра репозитория записей учёта времени.
+    ///
+    /// :param db_conn: подключение к базе данных.
+    fn new(db_conn: &Arc<Database>) -> Self;
+
+    /// Поиск записи по ID.
+    ///
+    /// :param id: идентификатор записи.
+    /// :return: `Some(TimeEntry)`, если запись найдена, иначе `None`.
+    async fn find(&self, id: i32) -> Option<TimeEntry>;
+
+    /// Записи пользователя по задаче, начиная с последней.
+    ///
+    /// :param task_id: идентификатор задачи.
+    /// :param user_id: идентификатор владельца записей.
+    /// :return: список записей либо `sqlx::Error`.
+    async fn find_by_task(&self, task_id: i32, user_id: i32) -> Result<Vec<TimeEntry>, Error>;
+
+    /// Запущенный таймер пользователя.
+    ///
+    /// :param user_id: идентификатор пользователя.
+    /// :return: `Some(TimeEntry)`, если таймер запущен, `None` или `sqlx::Error`.
+    async fn find_running(&self, user_id: i32) -> Result<Option<TimeEntry>, Error>;
+
+    /// Учтённое пользователем время за период.
+    ///
+    /// Записи обрезаются по границам периода, запущенный таймер учитывается
+    /// до текущего момента. При группировке по дням и неделям запись относится
+    /// к периоду, в котором она началась.
+    ///
+    /// :param user_id: идентификатор владельца записей.
+    /// :param from: начало периода.
+    /// :param to: конец периода (не включительно).
+    /// :param group: группировка строк.
+    /// :return: строки отчёта по возрастанию периода или проекта либо `sqlx::Error`.
+    async fn report(
+        &self,
+        user_id: i32,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        group: TimeReportGroup,
+    ) -> Result<Vec<TimeReportRow>, Error>;
+}
+
+#[async_trait]
+impl TimeEntryRepositoryTrait for TimeEntryRepository {
+    fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    async fn find(&self, id: i32) -> Option<TimeEntry> {
+        let result = sqlx::query_as::<_, TimeEntry>(
+            "SELECT * FROM time_entries WHERE id = $1"
+        )
+            .bind(id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await;
+
+        result.unwrap_or(None)
+    }
+
+    async fn find_by_task(&self, task_id: i32, user_id: i32) -> Result<Vec<TimeEntry>, Error> {
+        sqlx::query_as::<_, TimeEntry>(
+            "SELECT * FROM time_entries WHERE task_id = $1 AND user_id = $2 ORDER BY started_at DESC, id DESC"
+        )
+            .bind(task_id)
+            .bind(user_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn find_running(&self, user_id: i32) -> Result<Option<TimeEntry>, Error> {
+        sqlx::query_as::<_, TimeEntry>(
+            "SELECT * FROM time_entries WHERE user_id = $1 AND ended_at IS NULL"
+        )
+            .bind(user_id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+    }
+
+    async fn report(
+        &self,
+        user_id: i32,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        group: TimeReportGroup,
+    ) -> Result<Vec<TimeReportRow>, Error> {
+        let key = match group {
+            TimeReportGroup::Day => "(GREATEST(e.started_at, $2) AT TIME ZONE 'UTC')::date AS period, NULL::int AS project_id",
+            TimeReportGroup::Week => {
+                "date_trunc('week', GREATEST(e.started_at, $2) AT TIME ZONE 'UTC')::date AS period, NULL::int AS project_id"
+            }
+            TimeReportGroup::Project => "NULL::date AS period, t.project_id",
+        };
+
+        sqlx::query_as::<_, TimeReportRow>(&format!(
+            r#"
+            SELECT {},
+                   SUM(EXTRACT(EPOCH FROM LEAST(COALESCE(e.ended_at, now()), $3) - GREATEST(e.started_at, $2)))::bigint AS seconds,
+                   COUNT(*) AS entries
+            FROM time_entries e
+            JOIN tasks t ON t.id = e.task_id
+            WHERE e.user_id = $1
+              AND e.started_at < $3
+              AND COALESCE(e.ended_at, now()) > $2
+            GROUP BY 1, 2
+            ORDER BY 1, 2 NULLS FIRST
+            "#,
+            key
+        ))
+            .bind(user_id)
+            .bind(from)
+            .bind(to)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+    }
+}
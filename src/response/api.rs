@@ -13,6 +13,22 @@ pub struct ApiSuccessResponse<T: Serialize> {
     data: T,
 }
 
+/// Формат успешного ответа со списком и метаданными пагинации.
+///
+/// Расширяет `ApiSuccessResponse` (поле `data` остаётся на верхнем уровне).
+///
+/// - `total` — общее количество элементов, подходящих под фильтры.
+/// - `next_cursor` — курсор следующей страницы (`None`, если страница последняя).
+/// - `prev_cursor` — курсор предыдущей страницы (`None`, если страница первая).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ApiPaginatedResponse<T: Serialize> {
+    #[serde(flatten)]
+    response: ApiSuccessResponse<Vec<T>>,
+    total: i64,
+    next_cursor: Option<String>,
+    prev_cursor: Option<String>,
+}
+
 /// Унифицированный формат ответа при ошибке.
 ///
 /// Позволяет вернуть код ошибки и человекочитаемое сообщение.
@@ -36,6 +52,29 @@ impl<T: Serialize> ApiSuccessResponse<T> {
     }
 }
 
+impl<T: Serialize> ApiPaginatedResponse<T> {
+    /// Создаёт ответ со страницей данных и метаданными пагинации.
+    ///
+    /// **<u>:param data</u>**: элементы текущей страницы.
+    /// **<u>:param total</u>**: общее количество элементов.
+    /// **<u>:param next_cursor</u>**: курсор следующей страницы.
+    /// **<u>:param prev_cursor</u>**: курсор предыдущей страницы.
+    /// **<u>:return</u>**: структура `ApiPaginatedResponse<T>`.
+    pub(crate) fn send(
+        data: Vec<T>,
+        total: i64,
+        next_cursor: Option<String>,
+        prev_cursor: Option<String>,
+    ) -> Self {
+        ApiPaginatedResponse {
+            response: ApiSuccessResponse::send(data),
+            total,
+            next_cursor,
+            prev_cursor,
+        }
+    }
+}
+
 impl ApiErrorResponse {
     /// Создаёт и отправляет стандартный JSON-ответ об ошибке.
     ///
@@ -0,0 +1,29 @@
+use crate::handlers::attachment;
+use crate::services::attachment::AttachmentLimits;
+use crate::states::attachment::AttachmentState;
+use axum::{extract::DefaultBodyLimit, routing::get, Router};
+
+/// Маршруты вложений задач (`/tasks/:id/attachments`).
+///
+/// Используется `AttachmentState` как shared state, все маршруты требуют JWT.
+/// Лимит тела запроса загрузки — `ATTACHMENT_MAX_BYTES` с небольшим запасом
+/// на multipart-разметку; сам размер файла проверяется `AttachmentService`
+/// по мере чтения.
+///
+/// - `GET /tasks/:id/attachments` — вложения задачи.
+/// - `POST /tasks/:id/attachments` — загрузка файла (`multipart/form-data`, поле `file`).
+/// - `GET /tasks/:id/attachments/:attachment_id` — скачивание файла.
+/// - `DELETE /tasks/:id/attachments/:attachment_id` — удаление файла.
+pub fn routes() -> Router<AttachmentState> {
+    Router::new()
+        .route(
+            "/tasks/:id/attachments",
+            get(attachment::list_attachments)
+                .post(attachment::upload_attachment)
+                .layer(DefaultBodyLimit::max(AttachmentLimits::from_settings().body_limit())),
+        )
+        .route(
+            "/tasks/:id/attachments/:attachment_id",
+            get(attachment::download_attachment).delete(attachment::delete_attachment),
+        )
+}
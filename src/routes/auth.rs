@@ -1,12 +1,36 @@
 use crate::handlers::user;
-use crate::states::user::AuthState;
-use axum::{routing::post, Router};
+use crate::states::user::{AuthState, TokenState};
+use axum::{routing::{get, post}, Router};
 
 /// Маршруты для аутентификации (`/auth`).
 ///
 /// Используется `AuthState` как shared state.
 ///
 /// - `POST /auth` — авторизация (логин).
+/// - `POST /auth/refresh` — обмен refresh-токена на новую пару токенов.
+/// - `GET /auth/verify?token=` — подтверждение email.
+/// - `POST /auth/verify/resend` — повторная отправка письма подтверждения.
+/// - `POST /auth/password/forgot` — письмо со ссылкой для сброса пароля.
+/// - `POST /auth/password/reset` — установка нового пароля по токену.
 pub fn routes() -> Router<AuthState> {
-    Router::new().route("/auth", post(user::auth))
+    Router::new()
+        .route("/auth", post(user::auth))
+        .route("/auth/refresh", post(user::refresh))
+        .route("/auth/verify", get(user::verify_email))
+        .route("/auth/verify/resend", post(user::resend_verification))
+        .route("/auth/password/forgot", post(user::forgot_password))
+        .route("/auth/password/reset", post(user::reset_password))
+}
+
+
+/// Маршруты выхода из системы.
+///
+/// Используется `TokenState` как shared state, все маршруты требуют JWT.
+///
+/// - `POST /auth/logout` — отзыв текущего токена.
+/// - `POST /auth/logout-all` — выход со всех устройств.
+pub fn logout_routes() -> Router<TokenState> {
+    Router::new()
+        .route("/auth/logout", post(user::logout))
+        .route("/auth/logout-all", post(user::logout_all))
 }
@@ -0,0 +1,28 @@
+use crate::handlers::calendar;
+use crate::states::calendar::CalendarState;
+use axum::{routing::get, Router};
+
+/// Маршруты управления календарной лентой.
+///
+/// Используется `CalendarState` как shared state, все маршруты требуют JWT.
+///
+/// - `GET /calendar/feed` — лента текущего пользователя.
+/// - `POST /calendar/feed` — выпуск нового токена (отзывает прежний адрес).
+/// - `DELETE /calendar/feed` — отключение ленты.
+pub fn routes() -> Router<CalendarState> {
+    Router::new().route(
+        "/calendar/feed",
+        get(calendar::get_feed)
+            .post(calendar::regenerate_feed)
+            .delete(calendar::revoke_feed),
+    )
+}
+
+/// Маршрут календарной ленты.
+///
+/// Используется `CalendarState` как shared state, доступ по токену в пути вместо JWT.
+///
+/// - `GET /calendar/:token/tasks.ics?component=vtodo|vevent` — задачи со сроком в формате iCalendar.
+pub fn feed_routes() -> Router<CalendarState> {
+    Router::new().route("/calendar/:token/tasks.ics", get(calendar::feed))
+}
@@ -0,0 +1,25 @@
+use crate::handlers::column;
+use crate::states::column::ColumnState;
+use axum::{routing::{get, patch}, Router};
+
+/// Маршруты доски проекта (`/projects/:id/columns`, `/projects/:id/board`).
+///
+/// Используется `ColumnState` как shared state, все маршруты требуют JWT.
+///
+/// - `GET /projects/:id/columns` — колонки доски.
+/// - `POST /projects/:id/columns` — создание колонки в конце доски.
+/// - `PATCH /projects/:id/columns/:column_id` — переименование и перестановка колонки.
+/// - `DELETE /projects/:id/columns/:column_id` — удаление колонки.
+/// - `GET /projects/:id/board` — колонки вместе с задачами.
+pub fn routes() -> Router<ColumnState> {
+    Router::new()
+        .route(
+            "/projects/:id/columns",
+            get(column::list_columns).post(column::create_column),
+        )
+        .route(
+            "/projects/:id/columns/:column_id",
+            patch(column::update_column).delete(column::delete_column),
+        )
+        .route("/projects/:id/board", get(column::get_board))
+}
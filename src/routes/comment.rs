@@ -0,0 +1,28 @@
+use crate::handlers::comment;
+use crate::states::comment::CommentState;
+use axum::{routing::{get, patch}, Router};
+
+/// Маршруты комментариев к задачам (`/tasks/:id/comments`).
+///
+/// Используется `CommentState` как shared state, все маршруты требуют JWT.
+///
+/// - `GET /tasks/:id/comments` — комментарии задачи с ответами.
+/// - `POST /tasks/:id/comments` — создание комментария или ответа.
+/// - `PATCH /tasks/:id/comments/:comment_id` — изменение текста комментария.
+/// - `DELETE /tasks/:id/comments/:comment_id` — удаление комментария.
+/// - `GET /tasks/:id/comments/:comment_id/history` — история правок.
+pub fn routes() -> Router<CommentState> {
+    Router::new()
+        .route(
+            "/tasks/:id/comments",
+            get(comment::list_comments).post(comment::create_comment),
+        )
+        .route(
+            "/tasks/:id/comments/:comment_id",
+            patch(comment::update_comment).delete(comment::delete_comment),
+        )
+        .route(
+            "/tasks/:id/comments/:comment_id/history",
+            get(comment::list_comment_revisions),
+        )
+}
@@ -0,0 +1,23 @@
+use crate::handlers::label;
+use crate::states::label::LabelState;
+use axum::{routing::get, Router};
+
+/// Маршруты меток (`/labels`).
+///
+/// Используется `LabelState` как shared state, все маршруты требуют JWT.
+///
+/// - `GET /labels` — метки текущего пользователя.
+/// - `POST /labels` — создание метки.
+/// - `GET /labels/:id` — получение метки.
+/// - `PUT /labels/:id` — изменение метки.
+/// - `DELETE /labels/:id` — удаление метки.
+pub fn routes() -> Router<LabelState> {
+    Router::new()
+        .route("/labels", get(label::list_labels).post(label::create_label))
+        .route(
+            "/labels/:id",
+            get(label::get_label)
+                .put(label::update_label)
+                .delete(label::delete_label),
+        )
+}
@@ -2,3 +2,14 @@ pub mod auth;
 mod profile;
 pub mod register;
 pub mod root;
+mod task;
+mod label;
+mod comment;
+mod attachment;
+mod project;
+mod column;
+mod search;
+mod transfer;
+mod calendar;
+mod time_entry;
+mod session;
@@ -0,0 +1,35 @@
+use crate::handlers::project;
+use crate::states::project::ProjectState;
+use axum::{routing::{get, patch}, Router};
+
+/// Маршруты проектов (`/projects`).
+///
+/// Используется `ProjectState` как shared state, все маршруты требуют JWT.
+///
+/// - `GET /projects` — проекты текущего пользователя.
+/// - `POST /projects` — создание проекта.
+/// - `GET /projects/:id` — получение проекта.
+/// - `PUT /projects/:id` — изменение проекта.
+/// - `DELETE /projects/:id` — удаление проекта.
+/// - `GET /projects/:id/members` — участники проекта.
+/// - `POST /projects/:id/members` — добавление участника.
+/// - `PATCH /projects/:id/members/:user_id` — смена роли участника.
+/// - `DELETE /projects/:id/members/:user_id` — удаление участника.
+pub fn routes() -> Router<ProjectState> {
+    Router::new()
+        .route("/projects", get(project::list_projects).post(project::create_project))
+        .route(
+            "/projects/:id",
+            get(project::get_project)
+                .put(project::update_project)
+                .delete(project::delete_project),
+        )
+        .route(
+            "/projects/:id/members",
+            get(project::list_members).post(project::add_member),
+        )
+        .route(
+            "/projects/:id/members/:user_id",
+            patch(project::update_member).delete(project::remove_member),
+        )
+}
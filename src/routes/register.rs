@@ -4,8 +4,9 @@ use axum::{routing::post, Router};
 
 /// Маршруты регистрации пользователя (`/register`).
 ///
-/// - `POST /register` — регистрация нового пользователя.
 /// Используется `UserState` как shared state.
+///
+/// - `POST /register` — регистрация нового пользователя.
 pub fn routes() -> Router<UserState> {
     Router::new().route("/register", post(user::register_user))
 }
@@ -1,7 +1,19 @@
 use super::auth;
 use crate::db::db::Database;
 use crate::middleware::auth as auth_middleware;
-use crate::routes::{profile, register};
+use crate::middleware::calendar as calendar_middleware;
+use crate::middleware::request_id::request_id;
+use crate::routes::{attachment, calendar, column, comment, label, profile, project, register, search, session, task, time_entry, transfer};
+use crate::states::attachment::AttachmentState;
+use crate::states::calendar::CalendarState;
+use crate::states::column::ColumnState;
+use crate::states::comment::CommentState;
+use crate::states::label::LabelState;
+use crate::states::project::ProjectState;
+use crate::states::search::SearchState;
+use crate::states::task::TaskState;
+use crate::states::time_entry::TimeEntryState;
+use crate::states::transfer::TransferState;
 use crate::states::user::{AuthState, TokenState, UserState};
 
 use axum::{
@@ -17,11 +29,27 @@ use tower_http::trace::TraceLayer;
 ///
 /// Объединяет все маршруты:
 /// - `/auth` — авторизация
+/// - `/auth/verify`, `/auth/verify/resend` — подтверждение email
+/// - `/auth/password/forgot`, `/auth/password/reset` — сброс пароля
+/// - `/auth/logout`, `/auth/logout-all` — выход, требует JWT
 /// - `/register` — регистрация
 /// - `/profile` — защищённый маршрут, требует JWT
+/// - `/profile/sessions` — сессии пользователя, требует JWT
+/// - `/tasks` — CRUD задач, требует JWT
+/// - `/labels` — CRUD меток, требует JWT
+/// - `/tasks/:id/comments` — комментарии к задачам, требует JWT
+/// - `/tasks/:id/attachments` — вложения задач, требует JWT
+/// - `/projects` — проекты и участники, требует JWT
+/// - `/projects/:id/columns`, `/projects/:id/board` — доска проекта, требует JWT
+/// - `/search` — полнотекстовый поиск, требует JWT
+/// - `/tasks/export`, `/tasks/import` — импорт и экспорт задач, требует JWT
+/// - `/calendar/feed` — управление календарной лентой, требует JWT
+/// - `/calendar/:token/tasks.ics` — календарная лента, доступ по токену в пути
+/// - `/tasks/:id/timer`, `/tasks/:id/time-entries`, `/timer`, `/time-entries` — учёт времени, требует JWT
 /// - `/health` — простой healthcheck
 ///
 /// Использует отдельные `State` для модулей и middleware авторизации.
+/// Каждому запросу присваивается идентификатор (`X-Request-Id`).
 ///
 /// :param db_conn: подключение к базе данных
 /// :return: готовый `IntoMakeService` для запуска приложения
@@ -30,23 +58,112 @@ pub fn routes(db_conn: Arc<Database>) -> Router {
     let auth_state = AuthState::new(&db_conn);
     let user_state = UserState::new(&db_conn);
     let token_state = TokenState::new(&db_conn);
+    let task_state = TaskState::new(&db_conn);
+    let label_state = LabelState::new(&db_conn);
+    let comment_state = CommentState::new(&db_conn);
+    let attachment_state = AttachmentState::new(&db_conn);
+    let project_state = ProjectState::new(&db_conn);
+    let column_state = ColumnState::new(&db_conn);
+    let search_state = SearchState::new(&db_conn);
+    let transfer_state = TransferState::new(&db_conn);
+    let calendar_state = CalendarState::new(&db_conn);
+    let time_entry_state = TimeEntryState::new(&db_conn);
 
     // Объединение маршрутов
     let merged_router = auth::routes()
         .with_state(auth_state)
         .merge(register::routes().with_state(user_state))
+        .merge(
+            auth::logout_routes().with_state(token_state.clone()).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
         .merge(
             profile::routes().layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            session::routes().with_state(token_state.clone()).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            task::routes().with_state(task_state).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            label::routes().with_state(label_state).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            comment::routes().with_state(comment_state).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            attachment::routes().with_state(attachment_state).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            project::routes().with_state(project_state).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            column::routes().with_state(column_state).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            search::routes().with_state(search_state).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            time_entry::routes().with_state(time_entry_state).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            calendar::routes().with_state(calendar_state.clone()).layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(token_state.clone(), auth_middleware::auth)),
+            ),
+        )
+        .merge(
+            calendar::feed_routes()
+                .route_layer(middleware::from_fn_with_state(
+                    calendar_state.clone(),
+                    calendar_middleware::feed_token,
+                ))
+                .with_state(calendar_state),
+        )
+        .merge(
+            transfer::routes().with_state(transfer_state).layer(
                 ServiceBuilder::new()
                     .layer(middleware::from_fn_with_state(token_state, auth_middleware::auth)),
             ),
         )
         .merge(Router::new().route("/health", get(|| async { "Healthy..." })));
 
-    // Финальный роутер с базовым префиксом `/api` и логгированием
-    let app_router = Router::new()
+    // Финальный роутер с базовым префиксом `/api`, идентификатором запроса и логгированием
+    Router::new()
         .nest("/api", merged_router)
-        .layer(TraceLayer::new_for_http());
-
-    app_router
+        .layer(middleware::from_fn(request_id))
+        .layer(TraceLayer::new_for_http())
 }
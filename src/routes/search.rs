@@ -0,0 +1,12 @@
+use crate::handlers::search;
+use crate::states::search::SearchState;
+use axum::{routing::get, Router};
+
+/// Маршруты поиска (`/search`).
+///
+/// Используется `SearchState` как shared state, все маршруты требуют JWT.
+///
+/// - `GET /search?q=` — полнотекстовый поиск по задачам и комментариям.
+pub fn routes() -> Router<SearchState> {
+    Router::new().route("/search", get(search::search))
+}
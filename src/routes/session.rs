@@ -0,0 +1,15 @@
+use crate::handlers::session;
+use crate::states::user::TokenState;
+use axum::{routing::{delete, get}, Router};
+
+/// Маршруты сессий пользователя (`/profile/sessions`).
+///
+/// Используется `TokenState` как shared state, все маршруты требуют JWT.
+///
+/// - `GET /profile/sessions` — устройства, на которых выполнен вход.
+/// - `DELETE /profile/sessions/:id` — завершение сессии.
+pub fn routes() -> Router<TokenState> {
+    Router::new()
+        .route("/profile/sessions", get(session::list_sessions))
+        .route("/profile/sessions/:id", delete(session::revoke_session))
+}
@@ -0,0 +1,74 @@
+use crate::handlers::task;
+use crate::states::task::TaskState;
+use axum::{routing::{delete, get, post}, Router};
+
+/// Маршруты задач (`/tasks`).
+///
+/// Используется `TaskState` как shared state, все маршруты требуют JWT.
+///
+/// - `GET /tasks` — список задач текущего пользователя.
+/// - `POST /tasks` — создание задачи.
+/// - `GET /tasks/overdue` — просроченные задачи.
+/// - `GET /tasks/upcoming?within=7d` — задачи со сроком в ближайшем будущем.
+/// - `GET /tasks/assigned` — открытые задачи, назначенные текущему пользователю.
+/// - `GET /tasks/trash` — задачи в корзине.
+/// - `GET /tasks/order` — открытые задачи в топологическом порядке зависимостей.
+/// - `POST /tasks/bulk` — массовая обработка задач в одной транзакции.
+/// - `GET /tasks/:id` — получение задачи.
+/// - `PUT /tasks/:id` — полная замена задачи.
+/// - `PATCH /tasks/:id` — частичное обновление задачи.
+/// - `DELETE /tasks/:id` — удаление задачи в корзину.
+/// - `POST /tasks/:id/restore` — восстановление задачи из корзины.
+/// - `POST /tasks/:id/transition` — смена статуса задачи.
+/// - `POST /tasks/:id/move` — перемещение задачи на доске проекта.
+/// - `GET /tasks/:id/tree` — задача со всеми подзадачами.
+/// - `GET /tasks/:id/history` — история изменений задачи.
+/// - `GET /tasks/:id/dependencies` — задачи, блокирующие данную.
+/// - `POST /tasks/:id/dependencies` — добавление зависимости.
+/// - `DELETE /tasks/:id/dependencies/:depends_on_id` — удаление зависимости.
+/// - `POST /tasks/:id/labels` — назначение метки.
+/// - `DELETE /tasks/:id/labels/:label_id` — снятие метки.
+/// - `POST /tasks/:id/assignees` — назначение исполнителя.
+/// - `DELETE /tasks/:id/assignees/:user_id` — снятие исполнителя.
+/// - `GET /tasks/:id/watchers` — наблюдатели задачи.
+/// - `POST /tasks/:id/watchers` — добавление наблюдателя.
+/// - `DELETE /tasks/:id/watchers/:user_id` — удаление наблюдателя.
+pub fn routes() -> Router<TaskState> {
+    Router::new()
+        .route("/tasks", get(task::list_tasks).post(task::create_task))
+        .route("/tasks/overdue", get(task::list_overdue_tasks))
+        .route("/tasks/upcoming", get(task::list_upcoming_tasks))
+        .route("/tasks/assigned", get(task::list_assigned_tasks))
+        .route("/tasks/trash", get(task::list_trash))
+        .route("/tasks/order", get(task::list_tasks_in_order))
+        .route("/tasks/bulk", post(task::bulk_tasks))
+        .route(
+            "/tasks/:id",
+            get(task::get_task)
+                .put(task::update_task)
+                .patch(task::patch_task)
+                .delete(task::delete_task),
+        )
+        .route("/tasks/:id/restore", post(task::restore_task))
+        .route("/tasks/:id/transition", post(task::transition_task))
+        .route("/tasks/:id/move", post(task::move_task))
+        .route("/tasks/:id/tree", get(task::get_task_tree))
+        .route("/tasks/:id/history", get(task::list_history))
+        .route(
+            "/tasks/:id/dependencies",
+            get(task::list_dependencies).post(task::add_dependency),
+        )
+        .route(
+            "/tasks/:id/dependencies/:depends_on_id",
+            delete(task::remove_dependency),
+        )
+        .route("/tasks/:id/labels", post(task::add_label))
+        .route("/tasks/:id/labels/:label_id", delete(task::remove_label))
+        .route("/tasks/:id/assignees", post(task::assign_user))
+        .route("/tasks/:id/assignees/:user_id", delete(task::unassign_user))
+        .route(
+            "/tasks/:id/watchers",
+            get(task::list_watchers).post(task::add_watcher),
+        )
+        .route("/tasks/:id/watchers/:user_id", delete(task::remove_watcher))
+}
@@ -0,0 +1,31 @@
+use crate::handlers::time_entry;
+use crate::states::time_entry::TimeEntryState;
+use axum::{routing::{get, post, put}, Router};
+
+/// Маршруты учёта времени.
+///
+/// Используется `TimeEntryState` как shared state, все маршруты требуют JWT.
+///
+/// - `POST /tasks/:id/timer/start` — запуск таймера (не больше одного на пользователя).
+/// - `POST /tasks/:id/timer/stop` — остановка таймера.
+/// - `GET /tasks/:id/time-entries` — свои записи по задаче.
+/// - `POST /tasks/:id/time-entries` — ручная запись времени.
+/// - `GET /timer` — запущенный таймер текущего пользователя.
+/// - `PUT /time-entries/:id` — изменение записи.
+/// - `DELETE /time-entries/:id` — удаление записи.
+/// - `GET /time-entries/report?from=&to=&group_by=day|week|project` — отчёт за период.
+pub fn routes() -> Router<TimeEntryState> {
+    Router::new()
+        .route("/tasks/:id/timer/start", post(time_entry::start_timer))
+        .route("/tasks/:id/timer/stop", post(time_entry::stop_timer))
+        .route(
+            "/tasks/:id/time-entries",
+            get(time_entry::list_entries).post(time_entry::create_entry),
+        )
+        .route("/timer", get(time_entry::get_running))
+        .route(
+            "/time-entries/:id",
+            put(time_entry::update_entry).delete(time_entry::delete_entry),
+        )
+        .route("/time-entries/report", get(time_entry::report))
+}
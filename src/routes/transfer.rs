@@ -0,0 +1,15 @@
+use crate::handlers::transfer;
+use crate::states::transfer::TransferState;
+use axum::{routing::{get, post}, Router};
+
+/// Маршруты импорта и экспорта задач.
+///
+/// Используется `TransferState` как shared state, все маршруты требуют JWT.
+///
+/// - `GET /tasks/export?format=csv|jsonl|ics` — выгрузка задач файлом.
+/// - `POST /tasks/import?format=csv|jsonl|ics` — загрузка задач из файла.
+pub fn routes() -> Router<TransferState> {
+    Router::new()
+        .route("/tasks/export", get(transfer::export_tasks))
+        .route("/tasks/import", post(transfer::import_tasks))
+}
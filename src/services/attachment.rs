@@ -0,0 +1,509 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::attachment::AttachmentReadDto;
+use crate::entities::attachment::Attachment;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::attachment::AttachmentError;
+use crate::errors::db::DbError;
+use crate::repositories::attachment::{AttachmentRepository, AttachmentRepositoryTrait};
+use crate::services::media_type::{self, SNIFF_BYTES};
+use crate::services::task::{TaskAccess, TaskService};
+use crate::settings::settings;
+use crate::storage::storage::{self as file_storage, ByteStream, FileStorage, ObjectWriter};
+use axum::extract::multipart::{Field, MultipartError};
+use axum::http::StatusCode;
+use bytes::Bytes;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use sha2::{Digest, Sha256};
+use std::pin::pin;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Размер вложения по умолчанию — 10 МиБ.
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Запас на заголовки multipart-запроса и поля кроме `file` сверх размера файла.
+pub const MULTIPART_OVERHEAD_BYTES: u64 = 64 * 1024;
+
+/// Допустимые MIME-типы по умолчанию.
+const DEFAULT_ALLOWED_TYPES: &str =
+    "image/*,application/pdf,text/plain,text/csv,text/markdown,application/json,application/zip";
+
+/// Ограничения на загружаемые файлы (`AttachmentLimits`).
+///
+/// - `max_bytes` — максимальный размер файла (`ATTACHMENT_MAX_BYTES`).
+/// - `allowed_types` — допустимые MIME-типы через запятую, `type/*` разрешает
+///   все подтипы (`ATTACHMENT_ALLOWED_TYPES`).
+#[derive(Clone, Debug)]
+pub struct AttachmentLimits {
+    pub max_bytes: u64,
+    pub allowed_types: Vec<String>,
+}
+
+impl AttachmentLimits {
+    /// Чтение ограничений из настроек окружения.
+    ///
+    /// # Паника
+    /// Если `ATTACHMENT_MAX_BYTES` задана, но не является неотрицательным числом.
+    pub fn from_settings() -> Self {
+        let max_bytes = settings::get_non_negative_or("ATTACHMENT_MAX_BYTES", DEFAULT_MAX_BYTES);
+        let allowed_types = settings::get_optional("ATTACHMENT_ALLOWED_TYPES")
+            .unwrap_or_else(|| DEFAULT_ALLOWED_TYPES.to_string())
+            .split(',')
+            .map(|value| value.trim().to_ascii_lowercase())
+            .filter(|value| !value.is_empty())
+            .collect();
+
+        Self {
+            max_bytes,
+            allowed_types,
+        }
+    }
+
+    /// Лимит тела запроса загрузки: размер файла и `MULTIPART_OVERHEAD_BYTES`.
+    ///
+    /// :return: максимальный размер тела в байтах.
+    pub fn body_limit(&self) -> usize {
+        usize::try_from(self.max_bytes.saturating_add(MULTIPART_OVERHEAD_BYTES)).unwrap_or(usize::MAX)
+    }
+
+    /// Проверка, разрешён ли MIME-тип.
+    ///
+    /// :param content_type: MIME-тип без параметров, в нижнем регистре.
+    pub fn allows(&self, content_type: &str) -> bool {
+        self.allowed_types.iter().any(|allowed| match allowed.strip_suffix("/*") {
+            Some(prefix) => content_type
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('/')),
+            None => allowed == content_type,
+        })
+    }
+}
+
+/// Сервис работы с вложениями задач (`AttachmentService`).
+///
+/// Метаданные и контрольная сумма хранятся в Postgres, содержимое — в `FileStorage`.
+/// Доступ к вложениям определяется доступом к задаче (`TaskService`).
+#[derive(Clone)]
+pub struct AttachmentService {
+    /// `attachment_repo` — репозиторий вложений.
+    attachment_repo: AttachmentRepository,
+
+    /// `task_service` — проверка доступа к задаче.
+    task_service: TaskService,
+
+    /// `storage` — хранилище содержимого файлов.
+    storage: Arc<dyn FileStorage>,
+
+    /// `limits` — ограничения на размер и тип файлов.
+    limits: AttachmentLimits,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+}
+
+impl AttachmentService {
+    /// Создание нового экземпляра `AttachmentService`.
+    ///
+    /// Хранилище и ограничения берутся из настроек окружения.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            attachment_repo: AttachmentRepository::new(db_conn),
+            task_service: TaskService::new(db_conn),
+            storage: file_storage::from_settings(),
+            limits: AttachmentLimits::from_settings(),
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Ограничения на загружаемые файлы.
+    pub fn limits(&self) -> &AttachmentLimits {
+        &self.limits
+    }
+
+    /// Вложения задачи.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :return: список DTO вложений или ошибка (`ApiError`).
+    pub async fn list_attachments(
+        &self,
+        user: &User,
+        task_id: i32,
+    ) -> Result<Vec<AttachmentReadDto>, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, TaskAccess::Read).await?;
+        let attachments = self
+            .attachment_repo
+            .find_by_task(task.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(attachments.into_iter().map(AttachmentReadDto::from).collect())
+    }
+
+    /// Загрузка файла из поля multipart-запроса.
+    ///
+    /// Заявленный тип файла проверяется до чтения содержимого, а тип, определённый
+    /// по первым байтам (`media_type::sniff`), — до записи в хранилище. Размер
+    /// проверяется по мере чтения, поэтому слишком большой файл отклоняется,
+    /// не будучи прочитанным целиком.
+    /// Содержимое пишется в хранилище потоково, параллельно считается SHA-256.
+    /// При любой ошибке частично записанный объект удаляется.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :param field: поле `file` multipart-запроса.
+    /// :return: DTO вложения, `UnsupportedMediaType`, `FileTooLarge` или ошибка хранилища.
+    pub async fn upload_attachment(
+        &self,
+        user: &User,
+        task_id: i32,
+        field: Field<'_>,
+    ) -> Result<AttachmentReadDto, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, TaskAccess::Write).await?;
+
+        let declared_type = media_type(field.content_type());
+        if !self.limits.allows(&declared_type) {
+            return Err(AttachmentError::UnsupportedMediaType(declared_type).into());
+        }
+
+        let file_name = field
+            .file_name()
+            .map(sanitize_file_name)
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "file".to_string());
+        let storage_key = format!("tasks/{}/{}", task.id, Uuid::new_v4());
+
+        let body_limit = self.limits.body_limit();
+        let mut chunks = Box::pin(field.map_err(move |e| multipart_error(e, body_limit)));
+
+        let head = read_head(&mut chunks, SNIFF_BYTES).await?;
+        let content_type = media_type::effective(&declared_type, media_type::sniff(&head.concat()));
+        if !self.limits.allows(&content_type) {
+            return Err(AttachmentError::UnsupportedMediaType(content_type).into());
+        }
+        let chunks = stream::iter(head.into_iter().map(Ok)).chain(chunks);
+        let (size_bytes, checksum) =
+            store(self.storage.as_ref(), &storage_key, chunks, self.limits.max_bytes).await?;
+
+        let attachment = sqlx::query_as::<_, Attachment>(
+            r#"
+            INSERT INTO task_attachments
+                (task_id, user_id, file_name, content_type, size_bytes, checksum_sha256, storage_key)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+            .bind(task.id)
+            .bind(user.id)
+            .bind(file_name)
+            .bind(content_type)
+            .bind(size_bytes as i64)
+            .bind(checksum)
+            .bind(&storage_key)
+            .fetch_one(self.db_conn.get_pool())
+            .await;
+
+        match attachment {
+            Ok(attachment) => Ok(AttachmentReadDto::from(attachment)),
+            Err(e) => {
+                let _ = self.storage.delete(&storage_key).await;
+                Err(DbError::from(e).into())
+            }
+        }
+    }
+
+    /// Получение вложения для скачивания.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :param id: идентификатор вложения.
+    /// :return: метаданные и поток содержимого или ошибка (`ApiError`).
+    pub async fn download_attachment(
+        &self,
+        user: &User,
+        task_id: i32,
+        id: i32,
+    ) -> Result<(AttachmentReadDto, ByteStream), ApiError> {
+        let attachment = self.find_in_task(user, task_id, id, TaskAccess::Read).await?;
+        let stream = self.storage.read(&attachment.storage_key).await?;
+
+        Ok((AttachmentReadDto::from(attachment), stream))
+    }
+
+    /// Удаление вложения.
+    ///
+    /// Сначала удаляется запись в базе, затем объект в хранилище.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :param id: идентификатор вложения.
+    /// :return: `()` при успехе или ошибка (`ApiError`).
+    pub async fn delete_attachment(&self, user: &User, task_id: i32, id: i32) -> Result<(), ApiError> {
+        let attachment = self.find_in_task(user, task_id, id, TaskAccess::Write).await?;
+
+        sqlx::query("DELETE FROM task_attachments WHERE id = $1")
+            .bind(attachment.id)
+            .execute(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        self.storage.delete(&attachment.storage_key).await?;
+
+        Ok(())
+    }
+
+    /// Поиск вложения задачи с проверкой доступа к задаче.
+    ///
+    /// :return: модель вложения либо `AttachmentNotFound`.
+    async fn find_in_task(
+        &self,
+        user: &User,
+        task_id: i32,
+        id: i32,
+        access: TaskAccess,
+    ) -> Result<Attachment, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, access).await?;
+
+        self.attachment_repo
+            .find(id)
+            .await
+            .filter(|attachment| attachment.task_id == task.id)
+            .ok_or_else(|| AttachmentError::AttachmentNotFound.into())
+    }
+}
+
+/// MIME-тип файла без параметров, в нижнем регистре.
+///
+/// :param content_type: заголовок `Content-Type` поля (`None` — `application/octet-stream`).
+fn media_type(content_type: Option<&str>) -> String {
+    content_type
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_else(|| "application/octet-stream".to_string())
+}
+
+/// Ошибка разбора multipart-запроса.
+///
+/// :param error: ошибка `axum`.
+/// :param body_limit: лимит тела запроса (`AttachmentLimits::body_limit`).
+/// :return: `BodyTooLarge` при превышении лимита, иначе `InvalidMultipart`.
+pub fn multipart_error(error: MultipartError, body_limit: usize) -> AttachmentError {
+    match error.status() {
+        StatusCode::PAYLOAD_TOO_LARGE => AttachmentError::BodyTooLarge(body_limit),
+        _ => AttachmentError::InvalidMultipart(error.body_text()),
+    }
+}
+
+/// Чтение первых фрагментов потока, пока их суммарный размер меньше `min_bytes`.
+///
+/// :return: прочитанные фрагменты (меньше `min_bytes` только для короткого файла).
+async fn read_head(
+    chunks: &mut (impl Stream<Item = Result<Bytes, AttachmentError>> + Unpin),
+    min_bytes: usize,
+) -> Result<Vec<Bytes>, AttachmentError> {
+    let mut head = Vec::new();
+    let mut size = 0;
+
+    while size < min_bytes {
+        let Some(chunk) = chunks.try_next().await? else {
+            break;
+        };
+        size += chunk.len();
+        head.push(chunk);
+    }
+
+    Ok(head)
+}
+
+/// Потоковая запись объекта в хранилище с проверкой размера.
+///
+/// При любой ошибке, в том числе при превышении `max_bytes`, запись отменяется
+/// через `abort()`, и частично записанный объект не остаётся в хранилище.
+///
+/// :return: размер в байтах и SHA-256 (hex).
+async fn store(
+    storage: &dyn FileStorage,
+    key: &str,
+    chunks: impl Stream<Item = Result<Bytes, AttachmentError>> + Send,
+    max_bytes: u64,
+) -> Result<(u64, String), ApiError> {
+    let mut writer = storage.writer(key).await?;
+    match write_limited(writer.as_mut(), chunks, max_bytes).await {
+        Ok(stored) => Ok(stored),
+        Err(e) => {
+            if let Err(abort_error) = writer.abort().await {
+                tracing::warn!("Failed to abort attachment upload `{}`: {}", key, abort_error);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Копирование фрагментов в `writer`, пока размер не превышает `max_bytes`.
+async fn write_limited(
+    writer: &mut dyn ObjectWriter,
+    chunks: impl Stream<Item = Result<Bytes, AttachmentError>> + Send,
+    max_bytes: u64,
+) -> Result<(u64, String), ApiError> {
+    let mut chunks = pin!(chunks);
+    let mut hasher = Sha256::new();
+    let mut size: u64 = 0;
+
+    while let Some(chunk) = chunks.try_next().await? {
+        size += chunk.len() as u64;
+        if size > max_bytes {
+            return Err(AttachmentError::FileTooLarge(max_bytes).into());
+        }
+
+        hasher.update(&chunk);
+        writer.write(chunk).await?;
+    }
+    writer.finish().await?;
+
+    Ok((size, hex::encode(hasher.finalize())))
+}
+
+/// Имя файла без пути и управляющих символов, не длиннее 255 символов.
+fn sanitize_file_name(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    name.chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(255)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::storage::StorageError;
+    use crate::storage::s3::S3Storage;
+    use futures::stream;
+
+    fn chunks(parts: &[&'static [u8]]) -> impl Stream<Item = Result<Bytes, AttachmentError>> + Send {
+        stream::iter(parts.iter().map(|part| Ok(Bytes::from_static(part))).collect::<Vec<_>>())
+    }
+
+    async fn read_all(storage: &S3Storage, key: &str) -> Result<Vec<u8>, StorageError> {
+        let parts: Vec<Bytes> = storage.read(key).await?.try_collect().await?;
+        Ok(parts.concat())
+    }
+
+    fn limits(allowed_types: &str) -> AttachmentLimits {
+        AttachmentLimits {
+            max_bytes: DEFAULT_MAX_BYTES,
+            allowed_types: allowed_types.split(',').map(str::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn body_limit_adds_multipart_overhead() {
+        let limits = AttachmentLimits {
+            max_bytes: 1024,
+            allowed_types: Vec::new(),
+        };
+        assert_eq!(limits.body_limit(), 1024 + MULTIPART_OVERHEAD_BYTES as usize);
+
+        let unlimited = AttachmentLimits {
+            max_bytes: u64::MAX,
+            allowed_types: Vec::new(),
+        };
+        assert_eq!(unlimited.body_limit(), usize::MAX);
+    }
+
+    #[test]
+    fn normalizes_media_type() {
+        assert_eq!(media_type(Some("Image/PNG; charset=binary")), "image/png");
+        assert_eq!(media_type(Some("  text/plain ")), "text/plain");
+        assert_eq!(media_type(None), "application/octet-stream");
+    }
+
+    #[test]
+    fn checks_allowed_media_types() {
+        let limits = limits("image/*,application/pdf");
+
+        assert!(limits.allows("image/png"));
+        assert!(limits.allows("application/pdf"));
+        assert!(!limits.allows("image"));
+        assert!(!limits.allows("imagex/png"));
+        assert!(!limits.allows("application/pdfx"));
+        assert!(!limits.allows("text/html"));
+        assert!(!limits.allows(&media_type(Some("ımage/png"))));
+        assert!(!limits.allows(&media_type(Some("application/pdf\u{200b}"))));
+    }
+
+    #[tokio::test]
+    async fn reads_head_across_chunks() {
+        let mut parts = chunks(&[b"abc", b"def", b"ghi"]);
+
+        let head = read_head(&mut parts, 4).await.unwrap();
+        assert_eq!(head.concat(), b"abcdef");
+        assert_eq!(parts.try_collect::<Vec<_>>().await.unwrap().concat(), b"ghi");
+
+        let head = read_head(&mut chunks(&[b"ab"]), 4).await.unwrap();
+        assert_eq!(head.concat(), b"ab");
+    }
+
+    #[tokio::test]
+    async fn stores_small_file_in_one_request() {
+        let storage = S3Storage::in_memory(1024);
+
+        let (size, checksum) = store(&storage, "tasks/1/a", chunks(&[b"hello ", b"world"]), 100)
+            .await
+            .unwrap();
+
+        assert_eq!(size, 11);
+        assert_eq!(checksum, hex::encode(Sha256::digest(b"hello world")));
+        assert_eq!(read_all(&storage, "tasks/1/a").await.unwrap(), b"hello world");
+    }
+
+    #[tokio::test]
+    async fn stores_large_file_with_multipart_upload() {
+        let storage = S3Storage::in_memory(4);
+
+        let (size, _) = store(&storage, "tasks/1/b", chunks(&[b"abc", b"defg", b"hij", b"k"]), 100)
+            .await
+            .unwrap();
+
+        assert_eq!(size, 11);
+        assert_eq!(read_all(&storage, "tasks/1/b").await.unwrap(), b"abcdefghijk");
+    }
+
+    #[tokio::test]
+    async fn rejects_too_large_file_before_multipart_upload() {
+        let storage = S3Storage::in_memory(1024);
+
+        let result = store(&storage, "tasks/1/c", chunks(&[b"12345", b"678"]), 6).await;
+
+        assert!(matches!(result, Err(ApiError::AttachmentError(AttachmentError::FileTooLarge(6)))));
+        assert!(matches!(read_all(&storage, "tasks/1/c").await, Err(StorageError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn aborts_multipart_upload_of_too_large_file() {
+        let storage = S3Storage::in_memory(4);
+
+        let result = store(&storage, "tasks/1/d", chunks(&[b"1234", b"5678", b"9"]), 8).await;
+
+        assert!(matches!(result, Err(ApiError::AttachmentError(AttachmentError::FileTooLarge(8)))));
+        assert!(matches!(read_all(&storage, "tasks/1/d").await, Err(StorageError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn aborts_upload_on_broken_stream() {
+        let storage = S3Storage::in_memory(4);
+        let broken = stream::iter(vec![
+            Ok(Bytes::from_static(b"12345678")),
+            Err(AttachmentError::InvalidMultipart("connection reset".to_string())),
+        ]);
+
+        let result = store(&storage, "tasks/1/e", broken, 100).await;
+
+        assert!(matches!(result, Err(ApiError::AttachmentError(AttachmentError::InvalidMultipart(_)))));
+        assert!(matches!(read_all(&storage, "tasks/1/e").await, Err(StorageError::NotFound)));
+    }
+}
@@ -0,0 +1,180 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::calendar::{CalendarComponent, CalendarFeedReadDto};
+use crate::entities::calendar::CalendarFeed;
+use crate::entities::task::Task;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::calendar::CalendarError;
+use crate::errors::db::DbError;
+use crate::repositories::calendar::{CalendarRepository, CalendarRepositoryTrait};
+use crate::repositories::task::{TaskRepository, TaskRepositoryTrait};
+use crate::services::ical;
+use crate::services::secret_token;
+use crate::settings::settings;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+/// Длина токена ленты (hex-символы).
+const TOKEN_LEN: usize = 64;
+
+/// Насколько вперёд лента показывает задачи (в днях).
+const FEED_HORIZON_DAYS: i64 = 365;
+
+/// Сервис календарной ленты (`CalendarService`).
+///
+/// Отдаёт открытые задачи пользователя со сроком в формате iCalendar
+/// по секретному токену в URL: календарные приложения не умеют передавать
+/// JWT, поэтому лента не использует заголовок `Authorization`.
+/// Выпуск нового токена отзывает прежний адрес.
+#[derive(Clone)]
+pub struct CalendarService {
+    /// `calendar_repo` — репозиторий календарных лент.
+    calendar_repo: CalendarRepository,
+
+    /// `task_repo` — репозиторий задач.
+    task_repo: TaskRepository,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+}
+
+impl CalendarService {
+    /// Создание нового экземпляра `CalendarService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            calendar_repo: CalendarRepository::new(db_conn),
+            task_repo: TaskRepository::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Календарная лента пользователя.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: DTO ленты без адреса или `FeedNotFound`.
+    pub async fn get_feed(&self, user: &User) -> Result<CalendarFeedReadDto, ApiError> {
+        let feed = self
+            .calendar_repo
+            .find(user.id)
+            .await
+            .map_err(DbError::from)?
+            .ok_or(CalendarError::FeedNotFound)?;
+
+        Ok(CalendarFeedReadDto::from(feed))
+    }
+
+    /// Выпуск нового токена ленты.
+    ///
+    /// Создаёт ленту или заменяет токен существующей: прежний адрес
+    /// перестаёт работать.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: DTO ленты с адресом, содержащим новый токен.
+    pub async fn regenerate_feed(&self, user: &User) -> Result<CalendarFeedReadDto, ApiError> {
+        let token = secret_token::generate();
+
+        let feed = sqlx::query_as::<_, CalendarFeed>(
+            r#"
+            INSERT INTO calendar_feeds (user_id, token_hash) VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET token_hash = EXCLUDED.token_hash, created_at = CURRENT_TIMESTAMP, last_accessed_at = NULL
+            RETURNING *
+            "#,
+        )
+            .bind(user.id)
+            .bind(secret_token::hash(&token))
+            .fetch_one(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(CalendarFeedReadDto {
+            url: Some(Self::feed_url(&token)),
+            ..CalendarFeedReadDto::from(feed)
+        })
+    }
+
+    /// Отключение ленты.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: `()` или `FeedNotFound`, если лента не создана.
+    pub async fn revoke_feed(&self, user: &User) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM calendar_feeds WHERE user_id = $1")
+            .bind(user.id)
+            .execute(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(CalendarError::FeedNotFound.into());
+        }
+
+        Ok(())
+    }
+
+    /// Владелец ленты по токену из URL.
+    ///
+    /// Отмечает время обращения к ленте.
+    ///
+    /// :param token: токен из адреса ленты.
+    /// :return: пользователь или `FeedNotFound`, если токен неверен или отозван.
+    pub async fn authenticate(&self, token: &str) -> Result<User, ApiError> {
+        if token.len() != TOKEN_LEN || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(CalendarError::FeedNotFound.into());
+        }
+
+        let user = self
+            .calendar_repo
+            .find_user_by_token_hash(&secret_token::hash(&token.to_ascii_lowercase()))
+            .await
+            .map_err(DbError::from)?
+            .ok_or(CalendarError::FeedNotFound)?;
+
+        sqlx::query("UPDATE calendar_feeds SET last_accessed_at = CURRENT_TIMESTAMP WHERE user_id = $1")
+            .bind(user.id)
+            .execute(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(user)
+    }
+
+    /// Содержимое ленты в формате iCalendar.
+    ///
+    /// В ленту попадают открытые задачи со сроком не позже чем через
+    /// `FEED_HORIZON_DAYS` дней, включая просроченные.
+    ///
+    /// :param user: владелец ленты.
+    /// :param component: `VTODO` или `VEVENT`.
+    /// :return: календарь с задачами.
+    pub async fn render_feed(&self, user: &User, component: CalendarComponent) -> Result<String, ApiError> {
+        let tasks = self
+            .task_repo
+            .find_due(user.id, None, Utc::now() + Duration::days(FEED_HORIZON_DAYS))
+            .await
+            .map_err(DbError::from)?;
+
+        let mut calendar = ical::calendar_start("Tasks");
+        for task in tasks {
+            // Вхождения серий уже созданы отдельными задачами: с RRULE календарь
+            // размножил бы их повторно
+            let task = Task { recurrence: None, ..task };
+            match component {
+                CalendarComponent::Vtodo => calendar.push_str(&ical::vtodo(&task)),
+                CalendarComponent::Vevent => calendar.extend(ical::vevent(&task)),
+            }
+        }
+        calendar.push_str(ical::CALENDAR_END);
+
+        Ok(calendar)
+    }
+
+    /// Адрес ленты с токеном.
+    ///
+    /// Начинается с `PUBLIC_URL`, если переменная задана, иначе адрес относительный.
+    fn feed_url(token: &str) -> String {
+        let base = settings::get_optional("PUBLIC_URL").unwrap_or_default();
+        format!("{}/api/calendar/{}/tasks.ics", base.trim_end_matches('/'), token)
+    }
+}
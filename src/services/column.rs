@@ -0,0 +1,344 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::column::{BoardColumnDto, ColumnCreateDto, ColumnReadDto, ColumnUpdateDto};
+use crate::entities::column::BoardColumn;
+use crate::entities::project::ProjectWithRole;
+use crate::entities::task::Task;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::column::ColumnError;
+use crate::errors::db::DbError;
+use crate::errors::project::ProjectError;
+use crate::services::history;
+use crate::services::project::ProjectService;
+use crate::services::rank;
+use crate::services::task::TaskService;
+use serde_json::json;
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Сервис колонок доски проекта (`ColumnService`).
+///
+/// Просматривать доску может любой участник проекта, изменять колонки —
+/// владелец и редакторы. Порядок колонок хранится в лексикографических ключах,
+/// поэтому перестановка колонки меняет одну строку.
+#[derive(Clone)]
+pub struct ColumnService {
+    /// `project_service` — проверка участия в проекте.
+    project_service: ProjectService,
+
+    /// `task_service` — преобразование задач доски в DTO.
+    task_service: TaskService,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+}
+
+impl ColumnService {
+    /// Создание нового экземпляра `ColumnService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            project_service: ProjectService::new(db_conn),
+            task_service: TaskService::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Колонки доски проекта по порядку.
+    ///
+    /// :param user: авторизованный пользователь (участник проекта).
+    /// :param project_id: идентификатор проекта.
+    /// :return: список DTO колонок или ошибка (`ApiError`).
+    pub async fn list_columns(&self, user: &User, project_id: i32) -> Result<Vec<ColumnReadDto>, ApiError> {
+        let project = self.project_service.find_member_project(user, project_id).await?;
+        let columns = self.find_columns(project.project.id).await?;
+
+        Ok(columns.into_iter().map(ColumnReadDto::from).collect())
+    }
+
+    /// Доска проекта: колонки вместе с задачами в порядке `rank`.
+    ///
+    /// :param user: авторизованный пользователь (участник проекта).
+    /// :param project_id: идентификатор проекта.
+    /// :return: список колонок с задачами или ошибка (`ApiError`).
+    pub async fn get_board(&self, user: &User, project_id: i32) -> Result<Vec<BoardColumnDto>, ApiError> {
+        let project = self.project_service.find_member_project(user, project_id).await?;
+        let columns = self.find_columns(project.project.id).await?;
+        let tasks = sqlx::query_as::<_, Task>(
+            r#"
+            SELECT t.* FROM tasks t
+            JOIN board_columns c ON c.id = t.column_id
+            WHERE c.project_id = $1 AND t.deleted_at IS NULL
+            ORDER BY t.column_id, t.rank
+            "#,
+        )
+            .bind(project.project.id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        let mut tasks_by_column: HashMap<i32, Vec<_>> = HashMap::new();
+        for task in self.task_service.read_dtos(tasks).await? {
+            if let Some(column_id) = task.column_id {
+                tasks_by_column.entry(column_id).or_default().push(task);
+            }
+        }
+
+        Ok(columns
+            .into_iter()
+            .map(|column| BoardColumnDto {
+                tasks: tasks_by_column.remove(&column.id).unwrap_or_default(),
+                column: ColumnReadDto::from(column),
+            })
+            .collect())
+    }
+
+    /// Создание колонки в конце доски.
+    ///
+    /// :param user: авторизованный пользователь (`owner` или `editor`).
+    /// :param project_id: идентификатор проекта.
+    /// :param payload: название колонки.
+    /// :return: DTO созданной колонки или ошибка (`ApiError`).
+    pub async fn create_column(
+        &self,
+        user: &User,
+        project_id: i32,
+        payload: ColumnCreateDto,
+    ) -> Result<ColumnReadDto, ApiError> {
+        let project = self.find_editable_project(user, project_id).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        Self::lock_project(&mut tx, project.project.id).await?;
+
+        let last = sqlx::query_scalar::<_, String>(
+            "SELECT rank FROM board_columns WHERE project_id = $1 ORDER BY rank DESC LIMIT 1",
+        )
+            .bind(project.project.id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        let rank = rank::between(last.as_deref(), None);
+
+        let column = sqlx::query_as::<_, BoardColumn>(
+            "INSERT INTO board_columns (project_id, name, rank) VALUES ($1, $2, $3) RETURNING *",
+        )
+            .bind(project.project.id)
+            .bind(payload.name.trim())
+            .bind(&rank)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        let column = if rank.len() > rank::MAX_RANK_LEN {
+            Self::rebalance(&mut tx, project.project.id).await?;
+            Self::reload(&mut tx, column.id).await?.ok_or(ColumnError::ColumnNotFound)?
+        } else {
+            column
+        };
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(ColumnReadDto::from(column))
+    }
+
+    /// Переименование и (или) перестановка колонки.
+    ///
+    /// Перестановка выполняется под блокировкой проекта и меняет только ключ
+    /// переставляемой колонки; при слишком длинном ключе колонки доски
+    /// перенумеровываются.
+    ///
+    /// :param user: авторизованный пользователь (`owner` или `editor`).
+    /// :param project_id: идентификатор проекта.
+    /// :param id: идентификатор колонки.
+    /// :param payload: новое название и (или) положение.
+    /// :return: DTO колонки, `ColumnNotFound` или `InvalidPosition`.
+    pub async fn update_column(
+        &self,
+        user: &User,
+        project_id: i32,
+        id: i32,
+        payload: ColumnUpdateDto,
+    ) -> Result<ColumnReadDto, ApiError> {
+        let project = self.find_editable_project(user, project_id).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        Self::lock_project(&mut tx, project.project.id).await?;
+
+        let mut column = Self::reload(&mut tx, id)
+            .await?
+            .filter(|column| column.project_id == project.project.id)
+            .ok_or(ColumnError::ColumnNotFound)?;
+
+        if let Some(name) = payload.name {
+            column.name = name.trim().to_string();
+        }
+        if let Some(after_id) = payload.after_id {
+            let before = match after_id {
+                Some(after_id) if after_id == column.id => {
+                    return Err(ColumnError::InvalidPosition("column cannot be placed after itself".to_string()).into());
+                }
+                Some(after_id) => Some(
+                    Self::reload(&mut tx, after_id)
+                        .await?
+                        .filter(|anchor| anchor.project_id == project.project.id)
+                        .ok_or_else(|| ColumnError::InvalidPosition("anchor column is not on this board".to_string()))?
+                        .rank,
+                ),
+                None => None,
+            };
+            let after = sqlx::query_scalar::<_, String>(
+                r#"
+                SELECT rank FROM board_columns
+                WHERE project_id = $1 AND id <> $2 AND ($3::text IS NULL OR rank > $3)
+                ORDER BY rank
+                LIMIT 1
+                "#,
+            )
+                .bind(project.project.id)
+                .bind(column.id)
+                .bind(&before)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(DbError::from)?;
+            column.rank = rank::between(before.as_deref(), after.as_deref());
+        }
+
+        let column = sqlx::query_as::<_, BoardColumn>(
+            "UPDATE board_columns SET name = $1, rank = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $3 RETURNING *",
+        )
+            .bind(&column.name)
+            .bind(&column.rank)
+            .bind(column.id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        let column = if column.rank.len() > rank::MAX_RANK_LEN {
+            Self::rebalance(&mut tx, project.project.id).await?;
+            Self::reload(&mut tx, column.id).await?.ok_or(ColumnError::ColumnNotFound)?
+        } else {
+            column
+        };
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(ColumnReadDto::from(column))
+    }
+
+    /// Удаление колонки.
+    ///
+    /// Задачи колонки не удаляются, а снимаются с доски.
+    ///
+    /// :param user: авторизованный пользователь (`owner` или `editor`).
+    /// :param project_id: идентификатор проекта.
+    /// :param id: идентификатор колонки.
+    /// :return: `()` при успехе или `ColumnNotFound`.
+    pub async fn delete_column(&self, user: &User, project_id: i32, id: i32) -> Result<(), ApiError> {
+        let project = self.find_editable_project(user, project_id).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let detached = sqlx::query_scalar::<_, i32>(
+            "UPDATE tasks SET column_id = NULL, rank = NULL WHERE column_id = $1 RETURNING id",
+        )
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        for task_id in detached {
+            let changes = json!({ "column_id": { "from": id, "to": null } });
+            history::record(&mut tx, task_id, Some(user.id), "moved", changes).await?;
+        }
+
+        let deleted = sqlx::query("DELETE FROM board_columns WHERE id = $1 AND project_id = $2")
+            .bind(id)
+            .bind(project.project.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if deleted.rows_affected() == 0 {
+            return Err(ColumnError::ColumnNotFound.into());
+        }
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Проект, в котором пользователь может изменять доску.
+    ///
+    /// :return: проект с ролью, `ProjectNotFound` или `ForbiddenProjectAccess`.
+    async fn find_editable_project(&self, user: &User, project_id: i32) -> Result<ProjectWithRole, ApiError> {
+        let project = self.project_service.find_member_project(user, project_id).await?;
+        if !project.role.can_edit() {
+            return Err(ProjectError::ForbiddenProjectAccess.into());
+        }
+
+        Ok(project)
+    }
+
+    /// Колонки проекта по порядку.
+    async fn find_columns(&self, project_id: i32) -> Result<Vec<BoardColumn>, ApiError> {
+        let columns = sqlx::query_as::<_, BoardColumn>(
+            "SELECT * FROM board_columns WHERE project_id = $1 ORDER BY rank",
+        )
+            .bind(project_id)
+            .fetch_all(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(columns)
+    }
+
+    /// Блокировка проекта: изменения порядка колонок выполняются последовательно.
+    async fn lock_project(conn: &mut PgConnection, project_id: i32) -> Result<(), ApiError> {
+        sqlx::query("SELECT id FROM projects WHERE id = $1 FOR UPDATE")
+            .bind(project_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Повторное чтение колонки внутри транзакции.
+    async fn reload(
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<BoardColumn>, ApiError> {
+        let column = sqlx::query_as::<_, BoardColumn>("SELECT * FROM board_columns WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(column)
+    }
+
+    /// Перенумерация колонок проекта равномерными короткими ключами.
+    async fn rebalance(conn: &mut PgConnection, project_id: i32) -> Result<(), ApiError> {
+        let ids = sqlx::query_scalar::<_, i32>("SELECT id FROM board_columns WHERE project_id = $1 ORDER BY rank")
+            .bind(project_id)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+
+        sqlx::query("SET CONSTRAINTS board_columns_project_id_rank_key DEFERRED")
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query(
+            r#"
+            UPDATE board_columns c SET rank = v.rank
+            FROM UNNEST($1::int[], $2::text[]) AS v (id, rank)
+            WHERE c.id = v.id
+            "#,
+        )
+            .bind(&ids)
+            .bind(rank::spread(ids.len()))
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,292 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::comment::{
+    CommentCreateDto, CommentListQuery, CommentReadDto, CommentRevisionDto, CommentUpdateDto,
+};
+use crate::dto::pagination::{Cursor, CursorDirection, Page};
+use crate::entities::comment::Comment;
+use crate::entities::task::Task;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::comment::CommentError;
+use crate::errors::db::DbError;
+use crate::repositories::comment::{CommentRepository, CommentRepositoryTrait};
+use crate::services::task::{TaskAccess, TaskService};
+use std::sync::Arc;
+
+/// Строка сортировки, к которой привязаны курсоры комментариев.
+const COMMENT_SORT: &str = "id";
+
+/// Сервис работы с комментариями (`CommentService`).
+///
+/// Доступ к комментариям задачи есть у тех же пользователей, что и к самой задаче
+/// (проверка делегируется `TaskService`). Изменять и удалять комментарий может только автор.
+#[derive(Clone)]
+pub struct CommentService {
+    /// `comment_repo` — репозиторий комментариев.
+    comment_repo: CommentRepository,
+
+    /// `task_service` — проверка доступа к задаче.
+    task_service: TaskService,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+}
+
+impl CommentService {
+    /// Создание нового экземпляра `CommentService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            comment_repo: CommentRepository::new(db_conn),
+            task_service: TaskService::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Страница комментариев задачи.
+    ///
+    /// Пагинация выполняется по корневым комментариям; ответы на них возвращаются
+    /// вложенными в `replies`. Удалённые комментарии остаются в ветке без текста,
+    /// только если на них есть ответы.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :param query: параметры пагинации.
+    /// :return: страница DTO комментариев или ошибка (`ApiError`).
+    pub async fn list_comments(
+        &self,
+        user: &User,
+        task_id: i32,
+        query: CommentListQuery,
+    ) -> Result<Page<CommentReadDto>, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, TaskAccess::Read).await?;
+        let cursor = match &query.cursor {
+            Some(raw) => Some(
+                Cursor::decode(raw)
+                    .filter(|cursor| cursor.sort == COMMENT_SORT && cursor.values.len() == 1)
+                    .ok_or(CommentError::InvalidCursor)?,
+            ),
+            None => None,
+        };
+
+        let total = self
+            .comment_repo
+            .count_roots(task.id)
+            .await
+            .map_err(DbError::from)?;
+
+        // Запрашиваем на одну запись больше, чтобы узнать, есть ли следующая страница
+        let mut roots = self
+            .comment_repo
+            .find_roots(task.id, cursor.as_ref(), query.limit + 1)
+            .await
+            .map_err(DbError::from)?;
+        let has_more = roots.len() as i64 > query.limit;
+        roots.truncate(query.limit as usize);
+
+        let (has_next, has_prev) = match &cursor {
+            Some(cursor) if cursor.direction == CursorDirection::Prev => {
+                roots.reverse();
+                (true, has_more)
+            }
+            Some(_) => (has_more, true),
+            None => (has_more, false),
+        };
+
+        // Для пустой страницы граница берётся из входящего курсора
+        let boundary = |direction: CursorDirection, id: Option<i32>| {
+            let values = match (id, &cursor) {
+                (Some(id), _) => vec![id.to_string()],
+                (None, Some(cursor)) => cursor.values.clone(),
+                (None, None) => return None,
+            };
+            Some(
+                Cursor {
+                    direction,
+                    sort: COMMENT_SORT.to_string(),
+                    values,
+                }
+                .encode(),
+            )
+        };
+        let next_cursor = if has_next {
+            boundary(CursorDirection::Next, roots.last().map(|root| root.comment.id))
+        } else {
+            None
+        };
+        let prev_cursor = if has_prev {
+            boundary(CursorDirection::Prev, roots.first().map(|root| root.comment.id))
+        } else {
+            None
+        };
+
+        let ids: Vec<i32> = roots.iter().map(|root| root.comment.id).collect();
+        let replies = self
+            .comment_repo
+            .find_replies(&ids)
+            .await
+            .map_err(DbError::from)?;
+        let items = CommentReadDto::threads(roots, replies);
+
+        Ok(Page {
+            items,
+            total,
+            next_cursor,
+            prev_cursor,
+        })
+    }
+
+    /// Создание комментария или ответа от имени пользователя.
+    ///
+    /// :param user: авторизованный пользователь (автор).
+    /// :param task_id: идентификатор задачи.
+    /// :param payload: текст и комментарий, на который дан ответ.
+    /// :return: DTO созданного комментария или `InvalidParentComment`.
+    pub async fn create_comment(
+        &self,
+        user: &User,
+        task_id: i32,
+        payload: CommentCreateDto,
+    ) -> Result<CommentReadDto, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, TaskAccess::Write).await?;
+        if let Some(parent_id) = payload.parent_id {
+            self.find_in_task(&task, parent_id)
+                .await
+                .map_err(|_| CommentError::InvalidParentComment)?;
+        }
+
+        let id = sqlx::query_scalar::<_, i32>(
+            "INSERT INTO task_comments (task_id, user_id, parent_id, body) VALUES ($1, $2, $3, $4) RETURNING id",
+        )
+            .bind(task.id)
+            .bind(user.id)
+            .bind(payload.parent_id)
+            .bind(payload.body)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        self.read_dto(id).await
+    }
+
+    /// Изменение текста комментария автором.
+    ///
+    /// Прежний текст сохраняется в истории правок в той же транзакции.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :param id: идентификатор комментария.
+    /// :param payload: новый текст.
+    /// :return: DTO комментария, `CommentNotFound` или `ForbiddenCommentAccess`.
+    pub async fn update_comment(
+        &self,
+        user: &User,
+        task_id: i32,
+        id: i32,
+        payload: CommentUpdateDto,
+    ) -> Result<CommentReadDto, ApiError> {
+        let comment = self.find_authored(user, task_id, id).await?;
+        if comment.body == payload.body {
+            return self.read_dto(comment.id).await;
+        }
+
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        sqlx::query("INSERT INTO task_comment_revisions (comment_id, body) VALUES ($1, $2)")
+            .bind(comment.id)
+            .bind(&comment.body)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        sqlx::query("UPDATE task_comments SET body = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2")
+            .bind(payload.body)
+            .bind(comment.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        self.read_dto(comment.id).await
+    }
+
+    /// Мягкое удаление комментария автором.
+    ///
+    /// Ответы на комментарий сохраняются.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :param id: идентификатор комментария.
+    /// :return: `()`, `CommentNotFound` или `ForbiddenCommentAccess`.
+    pub async fn delete_comment(&self, user: &User, task_id: i32, id: i32) -> Result<(), ApiError> {
+        let comment = self.find_authored(user, task_id, id).await?;
+
+        sqlx::query("UPDATE task_comments SET deleted_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(comment.id)
+            .execute(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// История правок комментария.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :param id: идентификатор комментария.
+    /// :return: предыдущие версии текста, от старых к новым.
+    pub async fn list_revisions(
+        &self,
+        user: &User,
+        task_id: i32,
+        id: i32,
+    ) -> Result<Vec<CommentRevisionDto>, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, TaskAccess::Read).await?;
+        let comment = self.find_in_task(&task, id).await?;
+        let revisions = self
+            .comment_repo
+            .find_revisions(comment.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(revisions.into_iter().map(CommentRevisionDto::from).collect())
+    }
+
+    /// Поиск неудалённого комментария задачи.
+    ///
+    /// :return: модель комментария либо `CommentNotFound`.
+    async fn find_in_task(&self, task: &Task, id: i32) -> Result<Comment, CommentError> {
+        self.comment_repo
+            .find(id)
+            .await
+            .filter(|comment| comment.task_id == task.id && comment.deleted_at.is_none())
+            .ok_or(CommentError::CommentNotFound)
+    }
+
+    /// Поиск комментария с проверкой доступа к задаче и авторства.
+    ///
+    /// :return: модель комментария, `CommentNotFound` или `ForbiddenCommentAccess`.
+    async fn find_authored(&self, user: &User, task_id: i32, id: i32) -> Result<Comment, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, TaskAccess::Write).await?;
+        let comment = self.find_in_task(&task, id).await?;
+        if comment.user_id != user.id {
+            return Err(CommentError::ForbiddenCommentAccess.into());
+        }
+
+        Ok(comment)
+    }
+
+    /// Загрузка комментария с автором и преобразование в DTO.
+    async fn read_dto(&self, id: i32) -> Result<CommentReadDto, ApiError> {
+        let comment = self
+            .comment_repo
+            .find_with_author(id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(CommentReadDto::from(comment))
+    }
+}
@@ -0,0 +1,227 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::user::UserReadDto;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::errors::user::UserError;
+use crate::mailer::mailer::{self as mailer, Mail, Mailer};
+use crate::repositories::user::{UserRepository, UserRepositoryTrait};
+use crate::services::secret_token;
+use crate::settings::settings;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use std::sync::Arc;
+
+/// Время жизни ссылки подтверждения по умолчанию (в часах).
+const DEFAULT_TTL_HOURS: i64 = 24;
+
+/// Минимальный интервал между письмами подтверждения одному пользователю (в секундах).
+const RESEND_INTERVAL_SECONDS: i64 = 60;
+
+/// Сервис подтверждения email (`EmailVerificationService`).
+///
+/// Зарегистрированный пользователь неактивен (`is_active = 0`), пока не перейдёт
+/// по ссылке из письма. В базе хранится только SHA-256 токена; у пользователя
+/// действует не больше одного токена.
+#[derive(Clone)]
+pub struct EmailVerificationService {
+    /// `user_repo` — поиск пользователя по email.
+    user_repo: UserRepository,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+
+    /// `mailer` — отправка писем.
+    mailer: Arc<dyn Mailer>,
+
+    /// `ttl` — время жизни ссылки подтверждения.
+    ttl: Duration,
+}
+
+impl EmailVerificationService {
+    /// Создание нового экземпляра `EmailVerificationService`.
+    ///
+    /// Время жизни ссылки берётся из `EMAIL_VERIFICATION_TTL_HOURS` (по умолчанию 24 часа),
+    /// почтовый транспорт — из настроек `MAILER`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    ///
+    /// # Паника
+    /// Если переменная задана, но не является положительным числом.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        let ttl_hours = settings::get_positive_or("EMAIL_VERIFICATION_TTL_HOURS", DEFAULT_TTL_HOURS);
+
+        Self {
+            user_repo: UserRepository::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+            mailer: mailer::from_settings(),
+            ttl: Duration::hours(ttl_hours),
+        }
+    }
+
+    /// Отправка письма подтверждения (после регистрации и `POST /auth/verify/resend`).
+    ///
+    /// Запрос обрабатывается в фоне: ответ не зависит от того, зарегистрирован
+    /// ли email и подтверждён ли он, а сбой почты не ломает регистрацию.
+    /// Ошибки только пишутся в лог.
+    ///
+    /// :param email: адрес пользователя.
+    pub fn request_verification(&self, email: String) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.send_verification(email).await {
+                tracing::warn!("Email verification request failed: {}", e);
+            }
+        });
+    }
+
+    /// Подтверждение email по токену (`GET /auth/verify?token=`).
+    ///
+    /// Активирует пользователя и удаляет его токены подтверждения.
+    ///
+    /// :param token: токен из письма.
+    /// :return: DTO активированного пользователя или `InvalidVerificationToken`.
+    pub async fn verify(&self, token: &str) -> Result<UserReadDto, ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let user_id = sqlx::query_as::<_, (i32, DateTime<Utc>)>(
+            "SELECT user_id, expires_at FROM email_verification_tokens WHERE token_hash = $1 FOR UPDATE",
+        )
+            .bind(secret_token::hash(token))
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(DbError::from)?
+            .filter(|(_, expires_at)| Self::is_usable(*expires_at, Utc::now()))
+            .map(|(user_id, _)| user_id)
+            .ok_or(UserError::InvalidVerificationToken)?;
+        let user = sqlx::query_as::<_, User>(
+            "UPDATE users SET is_active = 1, updated_at = CURRENT_TIMESTAMP WHERE id = $1 RETURNING *",
+        )
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query("DELETE FROM email_verification_tokens WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        tx.commit().await.map_err(DbError::from)?;
+        Ok(UserReadDto::from(user))
+    }
+
+    /// Выпуск токена подтверждения и отправка письма.
+    ///
+    /// Для неизвестного или уже подтверждённого email ничего не делает. Если письмо
+    /// уже отправлялось в последние 60 секунд, новое не отправляется; иначе прежние
+    /// токены пользователя заменяются новым.
+    async fn send_verification(&self, email: String) -> Result<(), ApiError> {
+        let Some(user) = self
+            .user_repo
+            .find_by_email(email)
+            .await
+            .filter(|user| user.is_active == 0)
+        else {
+            return Ok(());
+        };
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        // `created_at` заполняется часами базы, поэтому и текущее время берётся оттуда
+        let (last_sent_at, now) = sqlx::query_as::<_, (Option<NaiveDateTime>, NaiveDateTime)>(
+            "SELECT MAX(created_at), LOCALTIMESTAMP FROM email_verification_tokens WHERE user_id = $1",
+        )
+            .bind(user.id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if Self::is_throttled(last_sent_at, now) {
+            return Ok(());
+        }
+
+        let token = secret_token::generate();
+        sqlx::query("DELETE FROM email_verification_tokens WHERE user_id = $1")
+            .bind(user.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query("INSERT INTO email_verification_tokens (user_id, token_hash, expires_at) VALUES ($1, $2, $3)")
+            .bind(user.id)
+            .bind(secret_token::hash(&token))
+            .bind(Utc::now() + self.ttl)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        tx.commit().await.map_err(DbError::from)?;
+        self.mailer
+            .send(Mail {
+                to: user.email,
+                subject: "Confirm your email address".to_string(),
+                body: format!(
+                    "Welcome, {}!\n\nTo activate your account, open:\n{}\n\n\
+                     The link expires in {} hours. If you did not register, ignore this email.",
+                    user.user_name,
+                    Self::verify_url(&token),
+                    self.ttl.num_hours(),
+                ),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Ссылка подтверждения с токеном.
+    ///
+    /// Начинается с `PUBLIC_URL`, если переменная задана, иначе адрес относительный.
+    fn verify_url(token: &str) -> String {
+        let base = settings::get_optional("PUBLIC_URL").unwrap_or_default();
+        format!("{}/api/auth/verify?token={}", base.trim_end_matches('/'), token)
+    }
+
+    /// Действует ли ссылка подтверждения.
+    ///
+    /// :param expires_at: срок действия токена.
+    /// :param now: текущее время.
+    /// :return: `true`, если токен не истёк.
+    fn is_usable(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        expires_at > now
+    }
+
+    /// Отправлялось ли письмо подтверждения слишком недавно.
+    ///
+    /// :param last_sent_at: выпуск последнего токена.
+    /// :param now: текущее время по часам базы.
+    /// :return: `true`, если с выпуска прошло меньше `RESEND_INTERVAL_SECONDS`.
+    fn is_throttled(last_sent_at: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+        last_sent_at.is_some_and(|sent_at| now - sent_at < Duration::seconds(RESEND_INTERVAL_SECONDS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_is_usable_until_expiry() {
+        let now = Utc::now();
+
+        assert!(EmailVerificationService::is_usable(now + Duration::hours(1), now));
+        assert!(!EmailVerificationService::is_usable(now, now));
+        assert!(!EmailVerificationService::is_usable(now - Duration::seconds(1), now));
+    }
+
+    #[test]
+    fn resend_is_throttled_within_interval() {
+        let now = Utc::now().naive_utc();
+
+        assert!(!EmailVerificationService::is_throttled(None, now));
+        assert!(EmailVerificationService::is_throttled(
+            Some(now - Duration::seconds(RESEND_INTERVAL_SECONDS - 1)),
+            now
+        ));
+        assert!(!EmailVerificationService::is_throttled(
+            Some(now - Duration::seconds(RESEND_INTERVAL_SECONDS)),
+            now
+        ));
+        assert!(!EmailVerificationService::is_throttled(Some(now - Duration::days(1)), now));
+    }
+}
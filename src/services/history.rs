@@ -0,0 +1,153 @@
+use crate::entities::task::Task;
+use crate::errors::db::DbError;
+use crate::middleware::request_id;
+use serde_json::{Map, Value, json};
+use sqlx::PgConnection;
+
+/// Поля задачи, которые не попадают в разницу (служебные или неизменяемые).
+const IGNORED_FIELDS: [&str; 3] = ["id", "created_at", "updated_at"];
+
+/// Запись события в историю задачи (`task_events`).
+///
+/// Вызывается в той же транзакции, что и само изменение, поэтому история
+/// не расходится с состоянием задачи. Идентификатор текущего HTTP-запроса
+/// сохраняется вместе с событием.
+///
+/// :param conn: соединение внутри транзакции изменения.
+/// :param task_id: идентификатор задачи.
+/// :param actor_id: пользователь, выполнивший изменение (`None` — фоновая задача).
+/// :param action: тип события (`created`, `updated`, `assigned`, ...).
+/// :param changes: подробности изменения в JSON.
+/// :return: `()` или ошибка (`DbError`).
+pub(crate) async fn record(
+    conn: &mut PgConnection,
+    task_id: i32,
+    actor_id: Option<i32>,
+    action: &str,
+    changes: Value,
+) -> Result<(), DbError> {
+    sqlx::query(
+        "INSERT INTO task_events (task_id, actor_id, action, changes, request_id) VALUES ($1, $2, $3, $4, $5)",
+    )
+        .bind(task_id)
+        .bind(actor_id)
+        .bind(action)
+        .bind(changes)
+        .bind(request_id::current())
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Запись события с разницей полей задачи до и после изменения.
+///
+/// Если ни одно поле не изменилось, событие не записывается.
+///
+/// :param conn: соединение внутри транзакции изменения.
+/// :param before: задача до изменения (`None` — задача создана).
+/// :param after: задача после изменения.
+/// :param actor_id: пользователь, выполнивший изменение (`None` — фоновая задача).
+/// :param action: тип события.
+/// :return: `()` или ошибка (`DbError`).
+pub(crate) async fn record_diff(
+    conn: &mut PgConnection,
+    before: Option<&Task>,
+    after: &Task,
+    actor_id: Option<i32>,
+    action: &str,
+) -> Result<(), DbError> {
+    let changes = diff(before, after);
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    record(conn, after.id, actor_id, action, Value::Object(changes)).await
+}
+
+/// Разница полей задачи в виде `{"поле": {"from": ..., "to": ...}}`.
+///
+/// Для новой задачи (`before = None`) попадают только заполненные поля.
+fn diff(before: Option<&Task>, after: &Task) -> Map<String, Value> {
+    let to_map = |task: &Task| match serde_json::to_value(task) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    let before = before.map(to_map);
+
+    to_map(after)
+        .into_iter()
+        .filter(|(field, _)| !IGNORED_FIELDS.contains(&field.as_str()))
+        .filter_map(|(field, to)| {
+            let from = before
+                .as_ref()
+                .map(|before| before.get(&field).cloned().unwrap_or(Value::Null))
+                .unwrap_or(Value::Null);
+            (from != to).then(|| (field, json!({ "from": from, "to": to })))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task() -> Task {
+        serde_json::from_value(json!({
+            "id": 1,
+            "title": "Write report",
+            "user_id": 3,
+            "created_at": "2024-01-01T00:00:00",
+            "status": "todo",
+            "priority": "medium",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn created_task_lists_only_filled_fields() {
+        let changes = diff(None, &task());
+
+        assert_eq!(changes.get("title"), Some(&json!({ "from": null, "to": "Write report" })));
+        assert_eq!(changes.get("status"), Some(&json!({ "from": null, "to": "todo" })));
+        assert!(!changes.contains_key("description"));
+        for field in IGNORED_FIELDS {
+            assert!(!changes.contains_key(field), "`{}` must be ignored", field);
+        }
+    }
+
+    #[test]
+    fn update_lists_changed_fields_with_both_values() {
+        let before = task();
+        let mut after = before.clone();
+        after.title = "Write the report".to_string();
+        after.description = Some("quarterly".to_string());
+        after.updated_at = Some(after.created_at);
+
+        let changes = diff(Some(&before), &after);
+        assert_eq!(
+            Value::Object(changes),
+            json!({
+                "title": { "from": "Write report", "to": "Write the report" },
+                "description": { "from": null, "to": "quarterly" },
+            })
+        );
+    }
+
+    #[test]
+    fn cleared_field_is_recorded_as_null() {
+        let mut before = task();
+        before.estimated_minutes = Some(30);
+        let mut after = before.clone();
+        after.estimated_minutes = None;
+
+        let changes = diff(Some(&before), &after);
+        assert_eq!(changes.get("estimated_minutes"), Some(&json!({ "from": 30, "to": null })));
+        assert_eq!(changes.len(), 1);
+    }
+
+    #[test]
+    fn unchanged_task_has_no_diff() {
+        assert!(diff(Some(&task()), &task()).is_empty());
+    }
+}
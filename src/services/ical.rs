@@ -0,0 +1,515 @@
+use crate::entities::task::{Task, TaskPriority, TaskStatus};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+
+/// Конец календаря.
+pub const CALENDAR_END: &str = "END:VCALENDAR\r\n";
+
+/// Максимальная длина строки содержимого в октетах (RFC 5545, 3.1).
+const MAX_LINE_LEN: usize = 75;
+
+/// Формат даты-времени в UTC (`19970714T173000Z`).
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Длительность события без оценки трудозатрат (в минутах).
+const DEFAULT_EVENT_MINUTES: i64 = 30;
+
+/// Свойство компонента iCalendar.
+///
+/// - `name` — имя свойства в верхнем регистре (`SUMMARY`, `DUE`, ...).
+/// - `params` — параметры (`VALUE=DATE`, `TZID=...`); имена в верхнем регистре.
+/// - `value` — значение как есть (без снятия экранирования).
+#[derive(Clone, Debug)]
+pub struct ICalProperty {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl ICalProperty {
+    /// Значение параметра по имени.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Текстовое значение со снятым экранированием (`\n`, `\,`, `\;`, `\\`).
+    pub fn text(&self) -> String {
+        let mut text = String::with_capacity(self.value.len());
+        let mut chars = self.value.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') | Some('N') => text.push('\n'),
+                Some(other) => text.push(other),
+                None => text.push('\\'),
+            }
+        }
+        text
+    }
+
+    /// Значение даты или даты-времени в UTC.
+    ///
+    /// Дата без времени (`VALUE=DATE`) — полночь UTC. Время без `Z`
+    /// («плавающее» или с `TZID`) считается заданным в UTC.
+    ///
+    /// :return: момент времени или `None`, если значение не разбирается.
+    pub fn date_time(&self) -> Option<DateTime<Utc>> {
+        let value = self.value.trim();
+        if self.param("VALUE") == Some("DATE") || value.len() == 8 {
+            return NaiveDate::parse_from_str(value, "%Y%m%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date_time| date_time.and_utc());
+        }
+
+        NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+            .ok()
+            .map(|date_time| date_time.and_utc())
+    }
+}
+
+/// Компонент `VTODO`.
+///
+/// - `index` — порядковый номер компонента в файле (с 1).
+/// - `properties` — свойства компонента (вложенные компоненты, например `VALARM`, пропускаются).
+#[derive(Clone, Debug)]
+pub struct ICalTodo {
+    pub index: usize,
+    pub properties: Vec<ICalProperty>,
+}
+
+impl ICalTodo {
+    /// Первое свойство с указанным именем.
+    pub fn get(&self, name: &str) -> Option<&ICalProperty> {
+        self.properties.iter().find(|property| property.name == name)
+    }
+}
+
+/// Начало календаря задач.
+///
+/// :param name: название календаря (`X-WR-CALNAME`).
+/// :return: строки `BEGIN:VCALENDAR` с обязательными свойствами.
+pub fn calendar_start(name: &str) -> String {
+    let mut calendar = String::new();
+    push_line(&mut calendar, "BEGIN:VCALENDAR");
+    push_line(&mut calendar, "VERSION:2.0");
+    push_line(&mut calendar, "PRODID:-//task_manager//tasks//EN");
+    push_line(&mut calendar, "CALSCALE:GREGORIAN");
+    push_line(&mut calendar, &format!("X-WR-CALNAME:{}", escape(name)));
+    calendar
+}
+
+/// Уникальный идентификатор задачи в календаре (`UID`, `RELATED-TO`).
+///
+/// :param task_id: идентификатор задачи.
+pub fn uid(task_id: i32) -> String {
+    format!("task-{}@task_manager", task_id)
+}
+
+/// Компонент `VTODO` для задачи.
+///
+/// Статус `blocked` не имеет аналога в iCalendar: он передаётся как
+/// `IN-PROCESS`, а точный статус сохраняется в `X-TASK-STATUS`.
+///
+/// :param task: задача.
+/// :return: строки от `BEGIN:VTODO` до `END:VTODO`.
+pub fn vtodo(task: &Task) -> String {
+    let mut component = String::new();
+    push_line(&mut component, "BEGIN:VTODO");
+    push_line(&mut component, &format!("UID:{}", uid(task.id)));
+    push_line(
+        &mut component,
+        &format!("DTSTAMP:{}", task.updated_at.unwrap_or(task.created_at).format(DATE_TIME_FORMAT)),
+    );
+    push_line(&mut component, &format!("CREATED:{}", task.created_at.format(DATE_TIME_FORMAT)));
+    if let Some(updated_at) = task.updated_at {
+        push_line(&mut component, &format!("LAST-MODIFIED:{}", updated_at.format(DATE_TIME_FORMAT)));
+    }
+    push_line(&mut component, &format!("SUMMARY:{}", escape(&task.title)));
+    if let Some(description) = &task.description {
+        push_line(&mut component, &format!("DESCRIPTION:{}", escape(description)));
+    }
+    if let Some(due_at) = task.due_at {
+        push_line(&mut component, &format!("DUE:{}", due_at.format(DATE_TIME_FORMAT)));
+    }
+    push_line(&mut component, &format!("PRIORITY:{}", priority_to_ical(task.priority)));
+    push_line(&mut component, &format!("STATUS:{}", status_to_ical(task.status)));
+    push_line(&mut component, &format!("X-TASK-STATUS:{}", task.status));
+    if let Some(completed_at) = task.completed_at {
+        push_line(&mut component, &format!("COMPLETED:{}", completed_at.format(DATE_TIME_FORMAT)));
+    }
+    if let Some(recurrence) = &task.recurrence {
+        push_line(&mut component, &format!("RRULE:{}", recurrence));
+    }
+    if let Some(parent_id) = task.parent_id {
+        push_line(&mut component, &format!("RELATED-TO;RELTYPE=PARENT:{}", uid(parent_id)));
+    }
+    if let Some(estimated_minutes) = task.estimated_minutes {
+        push_line(&mut component, &format!("X-ESTIMATED-MINUTES:{}", estimated_minutes));
+    }
+    push_line(&mut component, "END:VTODO");
+    component
+}
+
+/// Компонент `VEVENT` для задачи со сроком.
+///
+/// Для календарей, которые не показывают `VTODO`: событие заканчивается
+/// в срок задачи и длится `estimated_minutes` (по умолчанию 30 минут).
+///
+/// :param task: задача.
+/// :return: строки от `BEGIN:VEVENT` до `END:VEVENT` или `None`, если у задачи нет срока.
+pub fn vevent(task: &Task) -> Option<String> {
+    let due_at = task.due_at?;
+    let minutes = task
+        .estimated_minutes
+        .map(i64::from)
+        .filter(|minutes| *minutes > 0)
+        .unwrap_or(DEFAULT_EVENT_MINUTES);
+
+    let mut component = String::new();
+    push_line(&mut component, "BEGIN:VEVENT");
+    push_line(&mut component, &format!("UID:{}", uid(task.id)));
+    push_line(
+        &mut component,
+        &format!("DTSTAMP:{}", task.updated_at.unwrap_or(task.created_at).format(DATE_TIME_FORMAT)),
+    );
+    push_line(
+        &mut component,
+        &format!("DTSTART:{}", (due_at - Duration::minutes(minutes)).format(DATE_TIME_FORMAT)),
+    );
+    push_line(&mut component, &format!("DTEND:{}", due_at.format(DATE_TIME_FORMAT)));
+    push_line(&mut component, &format!("SUMMARY:{}", escape(&task.title)));
+    if let Some(description) = &task.description {
+        push_line(&mut component, &format!("DESCRIPTION:{}", escape(description)));
+    }
+    push_line(&mut component, &format!("PRIORITY:{}", priority_to_ical(task.priority)));
+    let status = match task.status {
+        TaskStatus::Cancelled => "CANCELLED",
+        _ => "CONFIRMED",
+    };
+    push_line(&mut component, &format!("STATUS:{}", status));
+    push_line(&mut component, &format!("X-TASK-STATUS:{}", task.status));
+    push_line(&mut component, "TRANSP:TRANSPARENT");
+    push_line(&mut component, "END:VEVENT");
+    Some(component)
+}
+
+/// Разбор компонентов `VTODO` из файла iCalendar.
+///
+/// Поддерживаются свёрнутые строки (RFC 5545, 3.1) и окончания строк `\r\n` и `\n`.
+///
+/// :param text: содержимое файла.
+/// :return: компоненты `VTODO` по порядку или описание ошибки формата.
+pub fn parse_todos(text: &str) -> Result<Vec<ICalTodo>, String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    if !lines
+        .first()
+        .is_some_and(|line| line.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err("file must start with BEGIN:VCALENDAR".to_string());
+    }
+
+    let mut todos = Vec::new();
+    let mut current: Option<ICalTodo> = None;
+    // Глубина вложенных компонентов внутри VTODO (например, VALARM)
+    let mut nested = 0;
+    for (number, line) in lines.iter().enumerate() {
+        let property = parse_line(line).ok_or_else(|| format!("malformed line {}", number + 1))?;
+        match (property.name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if property.value.eq_ignore_ascii_case("VTODO") => {
+                current = Some(ICalTodo {
+                    index: todos.len() + 1,
+                    properties: Vec::new(),
+                });
+            }
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) if property.value.eq_ignore_ascii_case("VTODO") => {
+                todos.extend(current.take());
+            }
+            (_, Some(todo)) if nested == 0 => todo.properties.push(property),
+            _ => {}
+        }
+    }
+    if current.is_some() {
+        return Err("VTODO is not closed with END:VTODO".to_string());
+    }
+
+    Ok(todos)
+}
+
+/// Статус задачи по свойствам `X-TASK-STATUS` или `STATUS`.
+pub fn status_from_ical(todo: &ICalTodo) -> Option<TaskStatus> {
+    if let Some(status) = todo.get("X-TASK-STATUS").and_then(|property| property.value.parse().ok()) {
+        return Some(status);
+    }
+
+    match todo.get("STATUS")?.value.to_ascii_uppercase().as_str() {
+        "NEEDS-ACTION" => Some(TaskStatus::Todo),
+        "IN-PROCESS" => Some(TaskStatus::InProgress),
+        "COMPLETED" => Some(TaskStatus::Done),
+        "CANCELLED" => Some(TaskStatus::Cancelled),
+        _ => None,
+    }
+}
+
+/// Приоритет задачи по свойству `PRIORITY` (`0` — не задан).
+pub fn priority_from_ical(todo: &ICalTodo) -> Option<TaskPriority> {
+    match todo.get("PRIORITY")?.value.trim().parse::<u8>().ok()? {
+        1..=2 => Some(TaskPriority::Urgent),
+        3..=4 => Some(TaskPriority::High),
+        5 => Some(TaskPriority::Medium),
+        6..=9 => Some(TaskPriority::Low),
+        _ => None,
+    }
+}
+
+/// Значение `PRIORITY` для приоритета задачи (1 — наивысший).
+fn priority_to_ical(priority: TaskPriority) -> u8 {
+    match priority {
+        TaskPriority::Urgent => 1,
+        TaskPriority::High => 3,
+        TaskPriority::Medium => 5,
+        TaskPriority::Low => 9,
+    }
+}
+
+/// Значение `STATUS` для статуса задачи.
+fn status_to_ical(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "NEEDS-ACTION",
+        TaskStatus::InProgress | TaskStatus::Blocked => "IN-PROCESS",
+        TaskStatus::Done => "COMPLETED",
+        TaskStatus::Cancelled => "CANCELLED",
+    }
+}
+
+/// Разбор строки содержимого `NAME;PARAM=VALUE:value`.
+fn parse_line(line: &str) -> Option<ICalProperty> {
+    // Двоеточие внутри значения параметра в кавычках не разделяет имя и значение
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(index, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(index),
+        _ => None,
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    if name.is_empty() {
+        return None;
+    }
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.trim().to_ascii_uppercase(), value.trim_matches('"').to_string()))
+        .collect();
+
+    Some(ICalProperty {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+/// Экранирование текстового значения (RFC 5545, 3.3.11).
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Добавление строки содержимого со сворачиванием длинных строк по 75 октетов.
+fn push_line(output: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_LEN {
+            output.push_str("\r\n ");
+            // Пробел в начале продолжения входит в длину строки
+            width = 1;
+        }
+        output.push(c);
+        width += c.len_utf8();
+    }
+    output.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn task() -> Task {
+        let created_at = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap().and_hms_opt(9, 0, 0).unwrap();
+        Task {
+            id: 7,
+            title: "Релиз, этап 1; проверить \\ всё".to_string(),
+            description: Some("Первая строка\r\nвторая строка — ".repeat(4)),
+            user_id: 1,
+            created_at,
+            updated_at: None,
+            status: TaskStatus::Blocked,
+            completed_at: None,
+            due_at: Some(Utc.with_ymd_and_hms(2026, 3, 5, 18, 0, 0).unwrap()),
+            priority: TaskPriority::High,
+            estimated_minutes: Some(90),
+            parent_id: Some(3),
+            recurrence: Some("FREQ=WEEKLY;BYDAY=MO".to_string()),
+            series_id: None,
+            project_id: None,
+            column_id: None,
+            rank: None,
+            deleted_at: None,
+        }
+    }
+
+    fn calendar(components: &str) -> String {
+        format!("{}{}{}", calendar_start("Задачи"), components, CALENDAR_END)
+    }
+
+    #[test]
+    fn folds_lines_on_char_boundaries() {
+        let text = calendar(&vtodo(&task()));
+
+        for line in text.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_LEN, "{line}");
+        }
+        assert!(text.contains("\r\n "));
+        assert!(text.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn vtodo_round_trip() {
+        let task = task();
+        let todos = parse_todos(&calendar(&vtodo(&task))).unwrap();
+
+        assert_eq!(todos.len(), 1);
+        let todo = &todos[0];
+        assert_eq!(todo.get("UID").unwrap().value, "task-7@task_manager");
+        assert_eq!(todo.get("SUMMARY").unwrap().text(), task.title);
+        assert_eq!(
+            todo.get("DESCRIPTION").unwrap().text(),
+            task.description.unwrap().replace('\r', "")
+        );
+        assert_eq!(todo.get("DUE").unwrap().date_time(), task.due_at);
+        assert_eq!(todo.get("STATUS").unwrap().value, "IN-PROCESS");
+        assert_eq!(status_from_ical(todo), Some(TaskStatus::Blocked));
+        assert_eq!(priority_from_ical(todo), Some(TaskPriority::High));
+        assert_eq!(todo.get("RRULE").unwrap().value, "FREQ=WEEKLY;BYDAY=MO");
+        let related = todo.get("RELATED-TO").unwrap();
+        assert_eq!(related.param("RELTYPE"), Some("PARENT"));
+        assert_eq!(related.value, "task-3@task_manager");
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        assert_eq!(escape("a,b;c\\d\r\ne"), r"a\,b\;c\\d\ne");
+        assert_eq!(escape("«ключ: значение»"), "«ключ: значение»");
+    }
+
+    #[test]
+    fn vevent_ends_at_due_date() {
+        let mut task = task();
+        let event = vevent(&task).unwrap();
+        assert!(event.contains("DTSTART:20260305T163000Z\r\n"));
+        assert!(event.contains("DTEND:20260305T180000Z\r\n"));
+        assert!(event.contains("STATUS:CONFIRMED\r\n"));
+
+        task.estimated_minutes = Some(0);
+        task.status = TaskStatus::Cancelled;
+        let event = vevent(&task).unwrap();
+        assert!(event.contains("DTSTART:20260305T173000Z\r\n"));
+        assert!(event.contains("STATUS:CANCELLED\r\n"));
+
+        task.due_at = None;
+        assert!(vevent(&task).is_none());
+    }
+
+    #[test]
+    fn parses_foreign_todos() {
+        let text = "BEGIN:VCALENDAR\nVERSION:2.0\nBEGIN:VEVENT\nSUMMARY:skip\nEND:VEVENT\n\
+                    BEGIN:VTODO\nsummary;LANGUAGE=ru:Купить\n\t молоко\\, хлеб\nSTATUS:completed\n\
+                    DUE;VALUE=DATE:20260310\nX-LINK;ALTREP=\"http://x/a:b\":ok\nBEGIN:VALARM\n\
+                    SUMMARY:alarm\nEND:VALARM\nPRIORITY:0\nEND:VTODO\nEND:VCALENDAR\n";
+        let todos = parse_todos(text).unwrap();
+
+        assert_eq!(todos.len(), 1);
+        let todo = &todos[0];
+        assert_eq!(todo.index, 1);
+        assert_eq!(todo.get("SUMMARY").unwrap().text(), "Купить молоко, хлеб");
+        assert_eq!(todo.get("SUMMARY").unwrap().param("LANGUAGE"), Some("ru"));
+        assert_eq!(status_from_ical(todo), Some(TaskStatus::Done));
+        assert_eq!(priority_from_ical(todo), None);
+        assert_eq!(
+            todo.get("DUE").unwrap().date_time(),
+            Some(Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap())
+        );
+        assert_eq!(todo.get("X-LINK").unwrap().param("ALTREP"), Some("http://x/a:b"));
+        assert_eq!(todo.get("X-LINK").unwrap().value, "ok");
+    }
+
+    #[test]
+    fn rejects_malformed_calendars() {
+        assert!(parse_todos("").is_err());
+        assert!(parse_todos("BEGIN:VTODO\nEND:VTODO\n").is_err());
+        assert!(parse_todos("BEGIN:VCALENDAR\nBEGIN:VTODO\nSUMMARY:x\nEND:VCALENDAR\n").is_err());
+        assert_eq!(
+            parse_todos("BEGIN:VCALENDAR\nBEGIN:VTODO\nзадача без двоеточия\nEND:VTODO\n").unwrap_err(),
+            "malformed line 3"
+        );
+        assert_eq!(
+            parse_todos("BEGIN:VCALENDAR\n:value\n").unwrap_err(),
+            "malformed line 2"
+        );
+    }
+
+    #[test]
+    fn ignores_malformed_values() {
+        let todo = |lines: &str| {
+            parse_todos(&format!("BEGIN:VCALENDAR\nBEGIN:VTODO\n{}\nEND:VTODO\n", lines))
+                .unwrap()
+                .remove(0)
+        };
+
+        let malformed = todo("DUE:2026-03-10\nSTATUS:готово\nX-TASK-STATUS:Done\nPRIORITY:высокий");
+        assert_eq!(malformed.get("DUE").unwrap().date_time(), None);
+        assert_eq!(status_from_ical(&malformed), None);
+        assert_eq!(priority_from_ical(&malformed), None);
+
+        let fallback = todo("X-TASK-STATUS:архив\nSTATUS:NEEDS-ACTION\nDUE:20260310T101500\nPRIORITY:7");
+        assert_eq!(status_from_ical(&fallback), Some(TaskStatus::Todo));
+        assert_eq!(priority_from_ical(&fallback), Some(TaskPriority::Low));
+        assert_eq!(
+            fallback.get("DUE").unwrap().date_time(),
+            Some(Utc.with_ymd_and_hms(2026, 3, 10, 10, 15, 0).unwrap())
+        );
+        assert_eq!(todo("DESCRIPTION:конец\\").get("DESCRIPTION").unwrap().text(), "конец\\");
+        assert_eq!(todo("DUE:2026031é").get("DUE").unwrap().date_time(), None);
+    }
+}
@@ -0,0 +1,192 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::label::{LabelReadDto, LabelWriteDto};
+use crate::entities::label::Label;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::errors::label::LabelError;
+use crate::repositories::label::{LabelRepository, LabelRepositoryTrait};
+use std::sync::Arc;
+
+/// Сервис работы с метками (`LabelService`).
+///
+/// Содержит бизнес-логику CRUD-операций над метками пользователя.
+/// Метки хранятся отдельно от задач, поэтому переименование метки сразу видно
+/// во всех задачах, которым она назначена.
+#[derive(Clone)]
+pub struct LabelService {
+    /// `label_repo` — репозиторий меток.
+    label_repo: LabelRepository,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+}
+
+impl LabelService {
+    /// Создание нового экземпляра `LabelService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            label_repo: LabelRepository::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Все метки пользователя.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: список DTO меток, отсортированный по названию.
+    pub async fn list_labels(&self, user: &User) -> Result<Vec<LabelReadDto>, ApiError> {
+        let labels = self
+            .label_repo
+            .find_all(user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(labels.into_iter().map(LabelReadDto::from).collect())
+    }
+
+    /// Получение метки по ID.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор метки.
+    /// :return: DTO метки, `LabelNotFound` или `ForbiddenLabelAccess`.
+    pub async fn get_label(&self, user: &User, id: i32) -> Result<LabelReadDto, ApiError> {
+        let label = self.find_owned(user, id).await?;
+        Ok(LabelReadDto::from(label))
+    }
+
+    /// Создание метки.
+    ///
+    /// :param user: авторизованный пользователь (владелец метки).
+    /// :param payload: название и цвет.
+    /// :return: DTO созданной метки или `LabelAlreadyExists`.
+    pub async fn create_label(
+        &self,
+        user: &User,
+        payload: LabelWriteDto,
+    ) -> Result<LabelReadDto, ApiError> {
+        let label = sqlx::query_as::<_, Label>(
+            "INSERT INTO labels (user_id, name, colour) VALUES ($1, $2, LOWER($3)) RETURNING *",
+        )
+            .bind(user.id)
+            .bind(payload.name.trim())
+            .bind(payload.colour)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+            .map_err(Self::map_unique)?;
+
+        Ok(LabelReadDto::from(label))
+    }
+
+    /// Изменение названия и цвета метки.
+    ///
+    /// В одной транзакции с меткой обновляется `updated_at` всех задач,
+    /// которым она назначена.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор метки.
+    /// :param payload: новые название и цвет.
+    /// :return: DTO обновлённой метки или ошибка (`ApiError`).
+    pub async fn update_label(
+        &self,
+        user: &User,
+        id: i32,
+        payload: LabelWriteDto,
+    ) -> Result<LabelReadDto, ApiError> {
+        let label = self.find_owned(user, id).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let label = sqlx::query_as::<_, Label>(
+            r#"
+            UPDATE labels
+            SET name = $1,
+                colour = LOWER($2),
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $3
+            RETURNING *
+            "#,
+        )
+            .bind(payload.name.trim())
+            .bind(payload.colour)
+            .bind(label.id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(Self::map_unique)?;
+
+        Self::touch_tasks(&mut tx, label.id).await?;
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(LabelReadDto::from(label))
+    }
+
+    /// Удаление метки.
+    ///
+    /// Метка снимается со всех задач в той же транзакции, `updated_at`
+    /// затронутых задач обновляется.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор метки.
+    /// :return: `()` при успехе, `LabelNotFound` или `ForbiddenLabelAccess`.
+    pub async fn delete_label(&self, user: &User, id: i32) -> Result<(), ApiError> {
+        let label = self.find_owned(user, id).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        Self::touch_tasks(&mut tx, label.id).await?;
+        sqlx::query("DELETE FROM labels WHERE id = $1")
+            .bind(label.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Поиск метки с проверкой владельца.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор метки.
+    /// :return: модель метки, `LabelNotFound` или `ForbiddenLabelAccess`.
+    async fn find_owned(&self, user: &User, id: i32) -> Result<Label, ApiError> {
+        let label = self
+            .label_repo
+            .find(id)
+            .await
+            .ok_or(LabelError::LabelNotFound)?;
+        if label.user_id != user.id {
+            return Err(LabelError::ForbiddenLabelAccess.into());
+        }
+
+        Ok(label)
+    }
+
+    /// Обновление `updated_at` задач, которым назначена метка.
+    async fn touch_tasks(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        label_id: i32,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET updated_at = CURRENT_TIMESTAMP
+            WHERE id IN (SELECT task_id FROM task_labels WHERE label_id = $1)
+            "#,
+        )
+            .bind(label_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Преобразование ошибки `sqlx`: дубликат названия → `LabelAlreadyExists`.
+    fn map_unique(error: sqlx::Error) -> ApiError {
+        match DbError::from(error) {
+            DbError::UniqueConstraintViolation(_) => LabelError::LabelAlreadyExists.into(),
+            error => error.into(),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+/// Сколько первых байт файла достаточно для определения типа по сигнатуре.
+pub const SNIFF_BYTES: usize = 512;
+
+/// Сигнатуры форматов: смещение, байты и MIME-тип.
+const SIGNATURES: &[(usize, &[u8], &str)] = &[
+    (0, b"\x89PNG\r\n\x1a\n", "image/png"),
+    (0, b"\xff\xd8\xff", "image/jpeg"),
+    (0, b"GIF87a", "image/gif"),
+    (0, b"GIF89a", "image/gif"),
+    (8, b"WEBP", "image/webp"),
+    (0, b"II*\x00", "image/tiff"),
+    (0, b"MM\x00*", "image/tiff"),
+    (0, b"\x00\x00\x01\x00", "image/vnd.microsoft.icon"),
+    (0, b"%PDF-", "application/pdf"),
+    (0, b"PK\x03\x04", "application/zip"),
+    (0, b"PK\x05\x06", "application/zip"),
+    (0, b"PK\x07\x08", "application/zip"),
+    (0, b"\x1f\x8b", "application/gzip"),
+    (0, b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (0, b"Rar!\x1a\x07", "application/vnd.rar"),
+    (0, b"MZ", "application/x-msdownload"),
+    (0, b"\x7fELF", "application/x-executable"),
+    (0, b"\xfe\xed\xfa\xce", "application/x-mach-binary"),
+    (0, b"\xfe\xed\xfa\xcf", "application/x-mach-binary"),
+    (0, b"\xce\xfa\xed\xfe", "application/x-mach-binary"),
+    (0, b"\xcf\xfa\xed\xfe", "application/x-mach-binary"),
+];
+
+/// Тип содержимого по первым байтам файла.
+///
+/// Сначала проверяются сигнатуры двоичных форматов, затем — является ли
+/// содержимое текстом в UTF-8 без нулевых байт.
+///
+/// :param head: первые байты файла (не больше `SNIFF_BYTES` достаточно).
+/// :return: `text/plain` для текста, `application/octet-stream` для неизвестных
+///          двоичных данных, иначе тип по сигнатуре.
+pub fn sniff(head: &[u8]) -> &'static str {
+    if let Some((_, _, media_type)) = SIGNATURES
+        .iter()
+        .find(|(offset, magic, _)| head.get(*offset..).is_some_and(|rest| rest.starts_with(magic)))
+    {
+        // `WEBP` без контейнера `RIFF` — не изображение
+        if *media_type != "image/webp" || head.starts_with(b"RIFF") {
+            return media_type;
+        }
+    }
+    if is_bmp(head) {
+        return "image/bmp";
+    }
+    if let Some(media_type) = iso_media_type(head) {
+        return media_type;
+    }
+    if is_text(head) {
+        return "text/plain";
+    }
+    "application/octet-stream"
+}
+
+/// Тип, под которым сохраняется файл.
+///
+/// Заявленный клиентом тип принимается только для текстового содержимого
+/// и только если сам он текстовый; в остальных случаях решает содержимое.
+///
+/// :param declared: заявленный тип без параметров, в нижнем регистре.
+/// :param sniffed: результат `sniff()`.
+/// :return: MIME-тип для проверки по списку разрешённых и хранения.
+pub fn effective(declared: &str, sniffed: &'static str) -> String {
+    match sniffed == "text/plain" && is_textual(declared) {
+        true => declared.to_string(),
+        false => sniffed.to_string(),
+    }
+}
+
+/// Текстовый ли MIME-тип.
+fn is_textual(media_type: &str) -> bool {
+    media_type.starts_with("text/")
+        || matches!(media_type, "application/json" | "application/xml")
+        || media_type.ends_with("+json")
+        || media_type.ends_with("+xml")
+}
+
+/// BMP: сигнатура `BM` и известный размер заголовка DIB.
+fn is_bmp(head: &[u8]) -> bool {
+    head.starts_with(b"BM")
+        && head
+            .get(14..18)
+            .map(|size| u32::from_le_bytes([size[0], size[1], size[2], size[3]]))
+            .is_some_and(|size| matches!(size, 12 | 40 | 52 | 56 | 64 | 108 | 124))
+}
+
+/// Форматы ISO BMFF (контейнер `ftyp`): AVIF, HEIC и прочее видео MP4.
+fn iso_media_type(head: &[u8]) -> Option<&'static str> {
+    if head.get(4..8) != Some(b"ftyp") {
+        return None;
+    }
+    match head.get(8..12)? {
+        b"avif" | b"avis" => Some("image/avif"),
+        b"heic" | b"heix" | b"mif1" | b"msf1" => Some("image/heic"),
+        _ => Some("video/mp4"),
+    }
+}
+
+/// Текст в UTF-8 без нулевых байт; символ, обрезанный на конце `head`, допускается.
+fn is_text(head: &[u8]) -> bool {
+    if head.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_binary_signatures() {
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR"), "image/png");
+        assert_eq!(sniff(b"\xff\xd8\xff\xe0\x00\x10JFIF"), "image/jpeg");
+        assert_eq!(sniff(b"RIFF\x24\x00\x00\x00WEBPVP8 "), "image/webp");
+        assert_eq!(sniff(b"%PDF-1.7\n"), "application/pdf");
+        assert_eq!(sniff(b"PK\x03\x04\x14\x00"), "application/zip");
+        assert_eq!(sniff(b"MZ\x90\x00\x03\x00"), "application/x-msdownload");
+        assert_eq!(sniff(b"\x7fELF\x02\x01\x01"), "application/x-executable");
+        assert_eq!(sniff(b"\x00\x00\x00\x1cftypavif\x00\x00"), "image/avif");
+    }
+
+    #[test]
+    fn requires_full_container_headers() {
+        assert_eq!(sniff(b"xxxxxxxxWEBP"), "text/plain");
+        assert_eq!(sniff(b"BMW service notes, page 1"), "text/plain");
+
+        let mut bmp = b"BM\x00\x00\x00\x00\x00\x00\x00\x00\x36\x00\x00\x00\x28\x00\x00\x00".to_vec();
+        bmp.extend_from_slice(&[0; 8]);
+        assert_eq!(sniff(&bmp), "image/bmp");
+    }
+
+    #[test]
+    fn detects_text_and_unknown_binary() {
+        assert_eq!(sniff(b""), "text/plain");
+        assert_eq!(sniff("Заметка о задаче".as_bytes()), "text/plain");
+        // Последний символ обрезан на границе прочитанного фрагмента
+        assert_eq!(sniff(&"ёж".as_bytes()[..3]), "text/plain");
+        assert_eq!(sniff(b"text\x00with nul"), "application/octet-stream");
+        assert_eq!(sniff(b"\xc3\x28 invalid utf-8"), "application/octet-stream");
+    }
+
+    #[test]
+    fn content_decides_type_unless_both_are_text() {
+        assert_eq!(effective("text/csv", "text/plain"), "text/csv");
+        assert_eq!(effective("application/json", "text/plain"), "application/json");
+        assert_eq!(effective("image/png", "text/plain"), "text/plain");
+        assert_eq!(effective("text/plain", "application/x-msdownload"), "application/x-msdownload");
+        assert_eq!(effective("image/png", "application/pdf"), "application/pdf");
+        assert_eq!(effective("image/png", "application/octet-stream"), "application/octet-stream");
+    }
+}
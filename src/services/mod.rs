@@ -1,2 +1,26 @@
 pub mod user;
-pub mod token;
\ No newline at end of file
+pub mod token;
+pub mod task;
+pub mod workflow;
+pub mod label;
+pub mod comment;
+pub mod attachment;
+pub mod media_type;
+pub mod recurrence;
+pub mod series;
+pub mod project;
+pub mod rank;
+pub mod column;
+pub mod history;
+pub mod search;
+pub mod trash;
+pub mod transfer;
+pub mod ical;
+pub mod calendar;
+pub mod secret_token;
+pub mod time_entry;
+pub mod refresh_token;
+pub mod token_revocation;
+pub mod session;
+pub mod password_reset;
+pub mod email_verification;
@@ -0,0 +1,254 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::errors::password_reset::PasswordResetError;
+use crate::mailer::mailer::{self as mailer, Mail, Mailer};
+use crate::repositories::user::{UserRepository, UserRepositoryTrait};
+use crate::services::secret_token;
+use crate::services::token_revocation::TokenRevocationService;
+use crate::services::user::UserService;
+use crate::settings::settings;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use std::sync::Arc;
+
+/// Время жизни токена сброса по умолчанию (в минутах).
+const DEFAULT_TTL_MINUTES: i64 = 60;
+
+/// Минимальный интервал между письмами сброса одному пользователю (в секундах).
+const RESEND_INTERVAL_SECONDS: i64 = 60;
+
+/// Сервис сброса пароля (`PasswordResetService`).
+///
+/// Токен сброса отправляется письмом; в базе хранится только его SHA-256.
+/// Токен одноразовый, у пользователя действует не больше одного токена.
+/// После смены пароля все сессии пользователя завершаются.
+#[derive(Clone)]
+pub struct PasswordResetService {
+    /// `user_repo` — поиск пользователя по email.
+    user_repo: UserRepository,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+
+    /// `mailer` — отправка писем.
+    mailer: Arc<dyn Mailer>,
+
+    /// `ttl` — время жизни токена сброса.
+    ttl: Duration,
+}
+
+impl PasswordResetService {
+    /// Создание нового экземпляра `PasswordResetService`.
+    ///
+    /// Время жизни токена берётся из `PASSWORD_RESET_TTL_MINUTES` (по умолчанию 60 минут),
+    /// почтовый транспорт — из настроек `MAILER`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    ///
+    /// # Паника
+    /// Если переменная задана, но не является положительным числом.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        let ttl_minutes = settings::get_positive_or("PASSWORD_RESET_TTL_MINUTES", DEFAULT_TTL_MINUTES);
+
+        Self {
+            user_repo: UserRepository::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+            mailer: mailer::from_settings(),
+            ttl: Duration::minutes(ttl_minutes),
+        }
+    }
+
+    /// Запрос на сброс пароля (`POST /auth/password/forgot`).
+    ///
+    /// Запрос обрабатывается в фоне, чтобы ни ответ, ни время ответа не зависели
+    /// от того, зарегистрирован ли email. Ошибки только пишутся в лог.
+    ///
+    /// :param email: адрес пользователя.
+    pub fn request_reset(&self, email: String) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.send_reset(email).await {
+                tracing::warn!("Password reset request failed: {}", e);
+            }
+        });
+    }
+
+    /// Установка нового пароля по токену (`POST /auth/password/reset`).
+    ///
+    /// Токен помечается использованным, остальные токены пользователя удаляются,
+    /// все его JWT, refresh-токены и сессии отзываются. Токен пришёл на email
+    /// пользователя, поэтому неподтверждённый аккаунт заодно активируется.
+    ///
+    /// :param token: токен сброса из письма.
+    /// :param password: новый пароль.
+    /// :return: `()` или `InvalidResetToken`, если токен неизвестен, использован или истёк.
+    pub async fn reset_password(&self, token: &str, password: &str) -> Result<(), ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let (id, user_id) = sqlx::query_as::<_, (i32, i32, DateTime<Utc>, Option<NaiveDateTime>)>(
+            "SELECT id, user_id, expires_at, used_at FROM password_reset_tokens WHERE token_hash = $1 FOR UPDATE",
+        )
+            .bind(secret_token::hash(token))
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(DbError::from)?
+            .filter(|(_, _, expires_at, used_at)| Self::is_usable(*expires_at, *used_at, Utc::now()))
+            .map(|(id, user_id, _, _)| (id, user_id))
+            .ok_or(PasswordResetError::InvalidResetToken)?;
+        sqlx::query("UPDATE password_reset_tokens SET used_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query("UPDATE users SET password = $2, is_active = 1, updated_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(user_id)
+            .bind(UserService::hash_password(password))
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query("DELETE FROM password_reset_tokens WHERE user_id = $1 AND used_at IS NULL")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        TokenRevocationService::end_all_sessions(&mut tx, user_id).await?;
+
+        tx.commit().await.map_err(DbError::from)?;
+        Ok(())
+    }
+
+    /// Выпуск токена сброса и отправка письма.
+    ///
+    /// Для неизвестного email ничего не делает. Если письмо уже отправлялось
+    /// в последние 60 секунд, новое не отправляется; иначе прежние токены
+    /// пользователя заменяются новым.
+    async fn send_reset(&self, email: String) -> Result<(), ApiError> {
+        let Some(user) = self.user_repo.find_by_email(email).await else {
+            return Ok(());
+        };
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        // `created_at` заполняется часами базы, поэтому и текущее время берётся оттуда
+        let (last_sent_at, now) = sqlx::query_as::<_, (Option<NaiveDateTime>, NaiveDateTime)>(
+            "SELECT MAX(created_at), LOCALTIMESTAMP FROM password_reset_tokens WHERE user_id = $1 AND used_at IS NULL",
+        )
+            .bind(user.id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if Self::is_throttled(last_sent_at, now) {
+            return Ok(());
+        }
+
+        let token = secret_token::generate();
+        sqlx::query("DELETE FROM password_reset_tokens WHERE user_id = $1")
+            .bind(user.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query("INSERT INTO password_reset_tokens (user_id, token_hash, expires_at) VALUES ($1, $2, $3)")
+            .bind(user.id)
+            .bind(secret_token::hash(&token))
+            .bind(Utc::now() + self.ttl)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        tx.commit().await.map_err(DbError::from)?;
+        self.mailer
+            .send(Mail {
+                to: user.email,
+                subject: "Password reset".to_string(),
+                body: format!(
+                    "A password reset was requested for your account.\n\n{}\n\n\
+                     The token expires in {} minutes and can be used once. \
+                     If you did not request a reset, ignore this email.",
+                    Self::reset_instructions(&token),
+                    self.ttl.num_minutes(),
+                ),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Текст письма со ссылкой или токеном сброса.
+    ///
+    /// Если задан `PASSWORD_RESET_URL` (страница клиента), токен передаётся в ней
+    /// параметром `token`, иначе письмо содержит сам токен для `POST /api/auth/password/reset`.
+    fn reset_instructions(token: &str) -> String {
+        match settings::get_optional("PASSWORD_RESET_URL") {
+            Some(url) => {
+                let separator = if url.contains('?') { '&' } else { '?' };
+                format!("To choose a new password, open:\n{}{}token={}", url, separator, token)
+            }
+            None => {
+                let base = settings::get_optional("PUBLIC_URL").unwrap_or_default();
+                format!(
+                    "Reset token: {}\nSend it with a new password to POST {}/api/auth/password/reset.",
+                    token,
+                    base.trim_end_matches('/'),
+                )
+            }
+        }
+    }
+
+    /// Можно ли использовать токен сброса.
+    ///
+    /// :param expires_at: срок действия токена.
+    /// :param used_at: дата использования токена.
+    /// :param now: текущее время.
+    /// :return: `true`, если токен не использован и не истёк.
+    fn is_usable(expires_at: DateTime<Utc>, used_at: Option<NaiveDateTime>, now: DateTime<Utc>) -> bool {
+        used_at.is_none() && expires_at > now
+    }
+
+    /// Отправлялось ли письмо сброса слишком недавно.
+    ///
+    /// :param last_sent_at: выпуск последнего неиспользованного токена.
+    /// :param now: текущее время по часам базы.
+    /// :return: `true`, если с выпуска прошло меньше `RESEND_INTERVAL_SECONDS`.
+    fn is_throttled(last_sent_at: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+        last_sent_at.is_some_and(|sent_at| now - sent_at < Duration::seconds(RESEND_INTERVAL_SECONDS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_usable_until_expiry() {
+        let now = Utc::now();
+
+        assert!(PasswordResetService::is_usable(now + Duration::minutes(1), None, now));
+        assert!(!PasswordResetService::is_usable(now, None, now));
+        assert!(!PasswordResetService::is_usable(now - Duration::minutes(1), None, now));
+    }
+
+    #[test]
+    fn used_token_is_not_usable() {
+        let now = Utc::now();
+
+        assert!(!PasswordResetService::is_usable(
+            now + Duration::minutes(1),
+            Some(now.naive_utc()),
+            now
+        ));
+    }
+
+    #[test]
+    fn resend_is_throttled_within_interval() {
+        let now = Utc::now().naive_utc();
+
+        assert!(!PasswordResetService::is_throttled(None, now));
+        assert!(PasswordResetService::is_throttled(Some(now), now));
+        assert!(PasswordResetService::is_throttled(
+            Some(now - Duration::seconds(RESEND_INTERVAL_SECONDS - 1)),
+            now
+        ));
+        assert!(!PasswordResetService::is_throttled(
+            Some(now - Duration::seconds(RESEND_INTERVAL_SECONDS)),
+            now
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::project::{
+    ProjectMemberCreateDto, ProjectMemberReadDto, ProjectMemberUpdateDto, ProjectReadDto, ProjectWriteDto,
+};
+use crate::entities::project::{Project, ProjectRole, ProjectWithRole};
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::errors::project::ProjectError;
+use crate::errors::user::UserError;
+use crate::repositories::project::{ProjectRepository, ProjectRepositoryTrait};
+use crate::repositories::user::{UserRepository, UserRepositoryTrait};
+use crate::services::history;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Сервис работы с проектами (`ProjectService`).
+///
+/// Проект объединяет задачи нескольких пользователей. Права определяются ролью:
+/// владелец управляет проектом и участниками, редактор изменяет задачи,
+/// наблюдатель только просматривает их.
+#[derive(Clone)]
+pub struct ProjectService {
+    /// `project_repo` — репозиторий проектов.
+    project_repo: ProjectRepository,
+
+    /// `user_repo` — репозиторий пользователей (поиск участника по email).
+    user_repo: UserRepository,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+}
+
+impl ProjectService {
+    /// Создание нового экземпляра `ProjectService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            project_repo: ProjectRepository::new(db_conn),
+            user_repo: UserRepository::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Проекты, в которых участвует пользователь.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: список DTO проектов, отсортированный по названию.
+    pub async fn list_projects(&self, user: &User) -> Result<Vec<ProjectReadDto>, ApiError> {
+        let projects = self
+            .project_repo
+            .find_all(user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(projects.into_iter().map(ProjectReadDto::from).collect())
+    }
+
+    /// Получение проекта по ID.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор проекта.
+    /// :return: DTO проекта, `ProjectNotFound` или `ForbiddenProjectAccess`.
+    pub async fn get_project(&self, user: &User, id: i32) -> Result<ProjectReadDto, ApiError> {
+        let project = self.find_member_project(user, id).await?;
+        Ok(ProjectReadDto::from(project))
+    }
+
+    /// Создание проекта; создатель становится его владельцем.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param payload: название и описание.
+    /// :return: DTO созданного проекта или ошибка (`ApiError`).
+    pub async fn create_project(
+        &self,
+        user: &User,
+        payload: ProjectWriteDto,
+    ) -> Result<ProjectReadDto, ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let project = sqlx::query_as::<_, Project>(
+            "INSERT INTO projects (name, description) VALUES ($1, $2) RETURNING *",
+        )
+            .bind(payload.name.trim())
+            .bind(payload.description)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        sqlx::query("INSERT INTO project_members (project_id, user_id, role) VALUES ($1, $2, $3)")
+            .bind(project.id)
+            .bind(user.id)
+            .bind(ProjectRole::Owner)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(ProjectReadDto::from(ProjectWithRole {
+            project,
+            role: ProjectRole::Owner,
+        }))
+    }
+
+    /// Изменение названия и описания проекта (только владелец).
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор проекта.
+    /// :param payload: новые название и описание.
+    /// :return: DTO обновлённого проекта или ошибка (`ApiError`).
+    pub async fn update_project(
+        &self,
+        user: &User,
+        id: i32,
+        payload: ProjectWriteDto,
+    ) -> Result<ProjectReadDto, ApiError> {
+        let current = self.find_owned(user, id).await?;
+
+        let project = sqlx::query_as::<_, Project>(
+            r#"
+            UPDATE projects
+            SET name = $1,
+                description = $2,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $3
+            RETURNING *
+            "#,
+        )
+            .bind(payload.name.trim())
+            .bind(payload.description)
+            .bind(current.project.id)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(ProjectReadDto::from(ProjectWithRole {
+            project,
+            role: current.role,
+        }))
+    }
+
+    /// Удаление проекта вместе с его задачами (только владелец).
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор проекта.
+    /// :return: `()` при успехе, `ProjectNotFound` или `ForbiddenProjectAccess`.
+    pub async fn delete_project(&self, user: &User, id: i32) -> Result<(), ApiError> {
+        let project = self.find_owned(user, id).await?;
+
+        sqlx::query("DELETE FROM projects WHERE id = $1")
+            .bind(project.project.id)
+            .execute(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Участники проекта.
+    ///
+    /// :param user: авторизованный пользователь (любой участник).
+    /// :param id: идентификатор проекта.
+    /// :return: список DTO участников или ошибка (`ApiError`).
+    pub async fn list_members(&self, user: &User, id: i32) -> Result<Vec<ProjectMemberReadDto>, ApiError> {
+        let project = self.find_member_project(user, id).await?;
+        let members = self
+            .project_repo
+            .find_members(project.project.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(members.into_iter().map(ProjectMemberReadDto::from).collect())
+    }
+
+    /// Добавление участника по email (только владелец).
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор проекта.
+    /// :param payload: email пользователя и роль.
+    /// :return: DTO участника, `UserNotFound` или `MemberAlreadyExists`.
+    pub async fn add_member(
+        &self,
+        user: &User,
+        id: i32,
+        payload: ProjectMemberCreateDto,
+    ) -> Result<ProjectMemberReadDto, ApiError> {
+        let project = self.find_owned(user, id).await?;
+        let member = self
+            .user_repo
+            .find_by_email(payload.email)
+            .await
+            .ok_or(UserError::UserNotFound)?;
+
+        sqlx::query("INSERT INTO project_members (project_id, user_id, role) VALUES ($1, $2, $3)")
+            .bind(project.project.id)
+            .bind(member.id)
+            .bind(payload.role)
+            .execute(self.db_conn.get_pool())
+            .await
+            .map_err(|e| match DbError::from(e) {
+                DbError::UniqueConstraintViolation(_) => ApiError::from(ProjectError::MemberAlreadyExists),
+                error => error.into(),
+            })?;
+
+        self.find_member(project.project.id, member.id).await
+    }
+
+    /// Смена роли участника (только владелец).
+    ///
+    /// Последнего владельца понизить нельзя.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор проекта.
+    /// :param user_id: идентификатор участника.
+    /// :param payload: новая роль.
+    /// :return: DTO участника, `MemberNotFound` или `LastOwner`.
+    pub async fn update_member(
+        &self,
+        user: &User,
+        id: i32,
+        user_id: i32,
+        payload: ProjectMemberUpdateDto,
+    ) -> Result<ProjectMemberReadDto, ApiError> {
+        let project = self.find_owned(user, id).await?;
+        let member = self.find_member(project.project.id, user_id).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        if member.role == ProjectRole::Owner && payload.role != ProjectRole::Owner {
+            Self::ensure_other_owner(&mut tx, project.project.id, user_id).await?;
+        }
+
+        sqlx::query("UPDATE project_members SET role = $1 WHERE project_id = $2 AND user_id = $3")
+            .bind(payload.role)
+            .bind(project.project.id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        self.find_member(project.project.id, user_id).await
+    }
+
+    /// Удаление участника из проекта.
+    ///
+    /// Владелец может удалить любого участника, остальные — только себя
+    /// (выход из проекта). Последний владелец покинуть проект не может.
+    /// Задачи, созданные участником, остаются в проекте; с задач проекта он
+    /// снимается как исполнитель и наблюдатель.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор проекта.
+    /// :param user_id: идентификатор участника.
+    /// :return: `()` при успехе, `MemberNotFound`, `ForbiddenProjectAccess` или `LastOwner`.
+    pub async fn remove_member(&self, user: &User, id: i32, user_id: i32) -> Result<(), ApiError> {
+        let project = self.find_member_project(user, id).await?;
+        if user_id != user.id && project.role != ProjectRole::Owner {
+            return Err(ProjectError::ForbiddenProjectAccess.into());
+        }
+        let member = self.find_member(project.project.id, user_id).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        if member.role == ProjectRole::Owner {
+            Self::ensure_other_owner(&mut tx, project.project.id, user_id).await?;
+        }
+
+        sqlx::query("DELETE FROM project_members WHERE project_id = $1 AND user_id = $2")
+            .bind(project.project.id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        // Бывший участник теряет доступ к задачам проекта
+        let unassigned = sqlx::query_scalar::<_, i32>(
+            r#"
+            DELETE FROM task_assignees a USING tasks t
+            WHERE a.task_id = t.id AND t.project_id = $1 AND a.user_id = $2
+            RETURNING a.task_id
+            "#,
+        )
+            .bind(project.project.id)
+            .bind(user_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        for task_id in unassigned {
+            history::record(&mut tx, task_id, Some(user.id), "unassigned", json!({ "user_id": user_id })).await?;
+        }
+
+        sqlx::query("DELETE FROM task_watchers w USING tasks t WHERE w.task_id = t.id AND t.project_id = $1 AND w.user_id = $2")
+            .bind(project.project.id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Поиск проекта с ролью пользователя.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор проекта.
+    /// :return: проект с ролью, `ProjectNotFound` или `ForbiddenProjectAccess`.
+    pub(crate) async fn find_member_project(&self, user: &User, id: i32) -> Result<ProjectWithRole, ApiError> {
+        let project = self
+            .project_repo
+            .find_for_user(id, user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        match project {
+            Some(project) => Ok(project),
+            None if self.project_repo.exists(id).await.map_err(DbError::from)? => {
+                Err(ProjectError::ForbiddenProjectAccess.into())
+            }
+            None => Err(ProjectError::ProjectNotFound.into()),
+        }
+    }
+
+    /// Поиск проекта, владельцем которого является пользователь.
+    ///
+    /// :return: проект с ролью, `ProjectNotFound` или `ForbiddenProjectAccess`.
+    async fn find_owned(&self, user: &User, id: i32) -> Result<ProjectWithRole, ApiError> {
+        let project = self.find_member_project(user, id).await?;
+        if project.role != ProjectRole::Owner {
+            return Err(ProjectError::ForbiddenProjectAccess.into());
+        }
+
+        Ok(project)
+    }
+
+    /// Участник проекта в виде DTO.
+    ///
+    /// :return: DTO участника или `MemberNotFound`.
+    async fn find_member(&self, id: i32, user_id: i32) -> Result<ProjectMemberReadDto, ApiError> {
+        let member = self
+            .project_repo
+            .find_member(id, user_id)
+            .await
+            .map_err(DbError::from)?
+            .ok_or(ProjectError::MemberNotFound)?;
+
+        Ok(ProjectMemberReadDto::from(member))
+    }
+
+    /// Проверка, что в проекте останется владелец помимо `user_id`.
+    ///
+    /// Строки владельцев блокируются до конца транзакции, чтобы два владельца
+    /// не могли одновременно понизить друг друга.
+    ///
+    /// :return: `()` либо `LastOwner`.
+    async fn ensure_other_owner(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: i32,
+        user_id: i32,
+    ) -> Result<(), ApiError> {
+        let owners = sqlx::query_scalar::<_, i32>(
+            "SELECT user_id FROM project_members WHERE project_id = $1 AND role = 'owner' FOR UPDATE",
+        )
+            .bind(id)
+            .fetch_all(&mut **tx)
+            .await
+            .map_err(DbError::from)?;
+
+        if !owners.iter().any(|owner| *owner != user_id) {
+            return Err(ProjectError::LastOwner.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+/// Алфавит ключей порядка в порядке возрастания (совпадает с сортировкой `COLLATE "C"`).
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Основание системы счисления ключей.
+const BASE: usize = DIGITS.len();
+
+/// Длина ключа, после которой элементы списка перенумеровываются.
+///
+/// Каждая вставка между соседними ключами может удлинить ключ на символ;
+/// перенумерация возвращает ключи к короткой равномерной сетке.
+pub const MAX_RANK_LEN: usize = 32;
+
+/// Ключ порядка строго между `before` и `after` (дробная лексикографическая нумерация).
+///
+/// Ключи сравниваются как строки, поэтому перемещение элемента — это
+/// изменение только его собственного ключа. Ключи никогда не заканчиваются
+/// на `0`, поэтому между любыми двумя ключами всегда найдётся ещё один.
+///
+/// Повреждённые ключи не приводят к панике: символы вне алфавита считаются `0`,
+/// а `after`, не превышающий `before`, не учитывается.
+///
+/// :param before: ключ предыдущего элемента (`None` — начало списка).
+/// :param after: ключ следующего элемента (`None` — конец списка).
+/// :return: новый ключ.
+pub fn between(before: Option<&str>, after: Option<&str>) -> String {
+    let before = digits(before.unwrap_or_default());
+    let after = after.map(digits).filter(|after| *after > before);
+
+    midpoint(&before, after.as_deref())
+        .into_iter()
+        .map(|d| DIGITS[d] as char)
+        .collect()
+}
+
+/// Равномерно распределённые ключи для `count` элементов (для перенумерации).
+///
+/// :param count: количество элементов.
+/// :return: ключи по возрастанию.
+pub fn spread(count: usize) -> Vec<String> {
+    // Длина ключа с запасом в один разряд между соседними ключами
+    let mut len = 1;
+    let mut capacity = BASE as u128;
+    while capacity < (count as u128 + 1) * BASE as u128 {
+        len += 1;
+        capacity *= BASE as u128;
+    }
+    let step = capacity / (count as u128 + 1);
+
+    (1..=count as u128)
+        .map(|i| {
+            let mut value = i * step;
+            let mut key = vec![b'0'; len];
+            for slot in key.iter_mut().rev() {
+                *slot = DIGITS[(value % BASE as u128) as usize];
+                value /= BASE as u128;
+            }
+            while key.last() == Some(&b'0') {
+                key.pop();
+            }
+            String::from_utf8(key).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Позиция символа в алфавите ключей.
+fn digit(c: u8) -> usize {
+    DIGITS.iter().position(|d| *d == c).unwrap_or(0)
+}
+
+/// Разряды ключа без незначащих нулей в конце.
+///
+/// Без них порядок векторов разрядов совпадает с порядком дробей.
+fn digits(key: &str) -> Vec<usize> {
+    let mut digits: Vec<usize> = key.bytes().map(digit).collect();
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+/// Середина между дробями `0.a` и `0.b` (`b = None` — единица, `a < b`).
+fn midpoint(a: &[usize], b: Option<&[usize]>) -> Vec<usize> {
+    if let Some(b) = b {
+        // Общий префикс переносится в результат как есть
+        let mut n = 0;
+        while n < b.len() && a.get(n).copied().unwrap_or(0) == b[n] {
+            n += 1;
+        }
+        if n > 0 {
+            let mut key = b[..n].to_vec();
+            key.extend(midpoint(a.get(n..).unwrap_or_default(), Some(&b[n..])));
+            return key;
+        }
+    }
+
+    let digit_a = a.first().copied().unwrap_or(0);
+    let digit_b = b.and_then(|b| b.first().copied()).unwrap_or(BASE);
+    if digit_b - digit_a > 1 {
+        return vec![(digit_a + digit_b) / 2];
+    }
+
+    match b {
+        // Более длинный `b`: его первый разряд уже больше `a` и меньше `b`
+        Some(b) if b.len() > 1 => vec![b[0]],
+        _ => {
+            let mut key = vec![digit_a];
+            key.extend(midpoint(a.get(1..).unwrap_or_default(), None));
+            key
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_valid(key: &str) -> bool {
+        !key.is_empty() && !key.ends_with('0') && key.bytes().all(|c| DIGITS.contains(&c))
+    }
+
+    #[test]
+    fn first_key_is_in_the_middle() {
+        assert_eq!(between(None, None), "V");
+    }
+
+    #[test]
+    fn key_is_strictly_between_neighbours() {
+        for (before, after) in [("A", "B"), ("A", "C"), ("a", "b"), ("V", "V1"), ("1", "2"), ("z", "zz"), ("Vz", "W")] {
+            let key = between(Some(before), Some(after));
+            assert!(is_valid(&key), "{key}");
+            assert!(before < key.as_str() && key.as_str() < after, "{before} < {key} < {after}");
+        }
+    }
+
+    #[test]
+    fn keys_at_list_edges() {
+        let first = between(None, Some("1"));
+        assert!(is_valid(&first) && first.as_str() < "1", "{first}");
+
+        let last = between(Some("zzz"), None);
+        assert!(is_valid(&last) && last.as_str() > "zzz", "{last}");
+    }
+
+    #[test]
+    fn repeated_inserts_stay_ordered() {
+        let mut keys = vec![between(None, None)];
+        for i in 0..200 {
+            let index = (i * 7) % (keys.len() + 1);
+            let before = index.checked_sub(1).map(|index| keys[index].as_str());
+            let key = between(before, keys.get(index).map(String::as_str));
+            assert!(is_valid(&key), "{key}");
+            keys.insert(index, key);
+        }
+
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn inserting_at_one_spot_grows_key_until_rebalance() {
+        let (before, mut after) = ("U".to_string(), "V".to_string());
+        let mut len = after.len();
+        while after.len() <= MAX_RANK_LEN {
+            after = between(Some(&before), Some(&after));
+            assert!(after.len() <= len + 1);
+            assert!(before < after);
+            len = after.len();
+        }
+    }
+
+    #[test]
+    fn spread_keys_are_short_and_ordered() {
+        assert!(spread(0).is_empty());
+
+        let keys = spread(1000);
+        assert_eq!(keys.len(), 1000);
+        assert!(keys.iter().all(|key| is_valid(key) && key.len() <= 3));
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let key = between(Some(&keys[0]), Some(&keys[1]));
+        assert!(keys[0] < key && key < keys[1]);
+    }
+
+    #[test]
+    fn malformed_keys_do_not_panic() {
+        let cases = [
+            (Some("B"), Some("A")),
+            (Some("A"), Some("A")),
+            (Some("z"), Some("0")),
+            (Some("A"), Some("A0")),
+            (Some("zz"), Some("z")),
+            (None, Some("")),
+            (None, Some("000")),
+            (Some("ключ"), None),
+            (Some("é"), Some("z")),
+            (Some("z"), Some("é")),
+            (Some("A-B"), Some("A B")),
+        ];
+        for (before, after) in cases {
+            let key = between(before, after);
+            assert!(is_valid(&key), "{before:?} {after:?} -> {key}");
+        }
+    }
+
+    #[test]
+    fn non_ascii_characters_count_as_zero() {
+        assert_eq!(between(Some("é"), Some("1")), between(None, Some("1")));
+        assert!(between(Some("zé"), None).as_str() > "z");
+    }
+}
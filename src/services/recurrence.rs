@@ -0,0 +1,610 @@
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveDateTime, Utc, Weekday};
+use std::fmt;
+use std::ops::ControlFlow;
+use std::str::FromStr;
+
+/// Максимальное количество периодов, просматриваемых при поиске вхождений.
+///
+/// Защищает от бесконечного перебора для правил, которые никогда не срабатывают
+/// (например, `FREQ=MONTHLY;BYMONTHDAY=31;BYMONTH=2`).
+const MAX_PERIODS: u64 = 100_000;
+
+/// Частота повторения (`FREQ`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+/// День недели в `BYDAY` с необязательным порядковым номером (`MO`, `1MO`, `-1FR`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByDay {
+    pub ordinal: Option<i8>,
+    pub weekday: Weekday,
+}
+
+/// Правило повторения задачи в формате iCalendar RRULE (RFC 5545).
+///
+/// Поддерживается подмножество, достаточное для типичных расписаний:
+///
+/// - `FREQ` — `DAILY`, `WEEKLY`, `MONTHLY`, `YEARLY` (обязательно).
+/// - `INTERVAL` — шаг периода (по умолчанию 1).
+/// - `COUNT` — общее количество вхождений, включая первое.
+/// - `UNTIL` — последняя допустимая дата (`YYYYMMDD` или `YYYYMMDDTHHMMSSZ`).
+/// - `BYDAY` — дни недели; с порядковым номером (`1MO`, `-1FR`) — только для
+///   `MONTHLY` и `YEARLY` (номер считается внутри месяца, для `YEARLY` нужен `BYMONTH`).
+/// - `BYMONTHDAY` — дни месяца (`1..31`, отрицательные — от конца месяца).
+/// - `BYMONTH` — месяцы (`1..12`).
+/// - `WKST` — принимается, но неделя всегда начинается с понедельника.
+///
+/// Время вхождений совпадает со временем `DTSTART` (срока первой задачи серии), в UTC.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+    pub by_day: Vec<ByDay>,
+    pub by_month_day: Vec<i8>,
+    pub by_month: Vec<u32>,
+}
+
+impl RecurrenceRule {
+    /// Первое вхождение строго после `after`.
+    ///
+    /// :param dtstart: начало серии (первое вхождение).
+    /// :param after: момент, после которого ищется вхождение.
+    /// :return: дата вхождения или `None`, если серия закончилась.
+    pub fn next_after(&self, dtstart: DateTime<Utc>, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.between(dtstart, after, None, 1).into_iter().next()
+    }
+
+    /// Вхождения в интервале `(after, until]`.
+    ///
+    /// :param dtstart: начало серии (первое вхождение).
+    /// :param after: нижняя граница (не включительно).
+    /// :param until: верхняя граница (`None` — без ограничения).
+    /// :param limit: максимальное количество вхождений.
+    /// :return: даты вхождений по возрастанию.
+    pub fn between(
+        &self,
+        dtstart: DateTime<Utc>,
+        after: DateTime<Utc>,
+        until: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Vec<DateTime<Utc>> {
+        let mut result = Vec::new();
+        if limit == 0 {
+            return result;
+        }
+
+        self.for_each(dtstart, |occurrence| {
+            if until.is_some_and(|until| occurrence > until) {
+                return ControlFlow::Break(());
+            }
+            if occurrence > after {
+                result.push(occurrence);
+                if result.len() >= limit {
+                    return ControlFlow::Break(());
+                }
+            }
+            ControlFlow::Continue(())
+        });
+
+        result
+    }
+
+    /// Перебор вхождений серии по возрастанию, начиная с `dtstart`.
+    fn for_each<F>(&self, dtstart: DateTime<Utc>, mut visit: F)
+    where
+        F: FnMut(DateTime<Utc>) -> ControlFlow<()>,
+    {
+        let time = dtstart.time();
+        let mut emitted: u32 = 0;
+
+        let mut emit = |occurrence: DateTime<Utc>| {
+            if self.until.is_some_and(|until| occurrence > until)
+                || self.count.is_some_and(|count| emitted >= count)
+            {
+                return ControlFlow::Break(());
+            }
+            emitted += 1;
+            visit(occurrence)
+        };
+
+        // `DTSTART` всегда является первым вхождением серии
+        if emit(dtstart).is_break() {
+            return;
+        }
+
+        for period in 0..MAX_PERIODS {
+            let Some(dates) = self.period_dates(dtstart.date_naive(), period) else {
+                return;
+            };
+            for date in dates {
+                let occurrence = NaiveDateTime::new(date, time).and_utc();
+                if occurrence <= dtstart {
+                    continue;
+                }
+                if emit(occurrence).is_break() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Даты-кандидаты периода с номером `period`, по возрастанию.
+    ///
+    /// :return: `None`, если период выходит за пределы поддерживаемых дат.
+    fn period_dates(&self, start: NaiveDate, period: u64) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval as u64)?;
+
+        let mut dates = match self.freq {
+            Frequency::Daily => {
+                let date = start.checked_add_days(Days::new(step))?;
+                vec![date]
+            }
+            Frequency::Weekly => {
+                let monday = start
+                    .checked_sub_days(Days::new(start.weekday().num_days_from_monday() as u64))?
+                    .checked_add_days(Days::new(step.checked_mul(7)?))?;
+                if self.by_day.is_empty() {
+                    vec![monday.checked_add_days(Days::new(start.weekday().num_days_from_monday() as u64))?]
+                } else {
+                    let mut days: Vec<NaiveDate> = self
+                        .by_day
+                        .iter()
+                        .filter_map(|day| monday.checked_add_days(Days::new(day.weekday.num_days_from_monday() as u64)))
+                        .collect();
+                    days.sort();
+                    days
+                }
+            }
+            Frequency::Monthly => {
+                let first = start.with_day(1)?.checked_add_months(Months::new(u32::try_from(step).ok()?))?;
+                self.month_dates(first, start.day())
+            }
+            Frequency::Yearly => {
+                let year = start.year().checked_add(i32::try_from(step).ok()?)?;
+                let months = if self.by_month.is_empty() {
+                    vec![start.month()]
+                } else {
+                    self.by_month.clone()
+                };
+                months
+                    .into_iter()
+                    .filter_map(|month| NaiveDate::from_ymd_opt(year, month, 1))
+                    .flat_map(|first| self.month_dates(first, start.day()))
+                    .collect()
+            }
+        };
+
+        if !self.by_month.is_empty() {
+            dates.retain(|date| self.by_month.contains(&date.month()));
+        }
+        if self.freq == Frequency::Daily {
+            if !self.by_day.is_empty() {
+                dates.retain(|date| self.by_day.iter().any(|day| day.weekday == date.weekday()));
+            }
+            if !self.by_month_day.is_empty() {
+                dates.retain(|date| self.matches_month_day(*date));
+            }
+        }
+        dates.sort();
+        dates.dedup();
+
+        Some(dates)
+    }
+
+    /// Даты месяца, удовлетворяющие `BYMONTHDAY` / `BYDAY`.
+    ///
+    /// Без этих частей используется день месяца из `DTSTART`; месяцы,
+    /// в которых такого дня нет, пропускаются.
+    fn month_dates(&self, first: NaiveDate, default_day: u32) -> Vec<NaiveDate> {
+        let days_in_month = days_in_month(first);
+        let all = (1..=days_in_month).filter_map(|day| first.with_day(day));
+
+        if self.by_month_day.is_empty() && self.by_day.is_empty() {
+            return first.with_day(default_day).into_iter().collect();
+        }
+
+        all.filter(|date| self.by_month_day.is_empty() || self.matches_month_day(*date))
+            .filter(|date| {
+                self.by_day.is_empty()
+                    || self.by_day.iter().any(|day| matches_weekday_in_month(*day, *date, days_in_month))
+            })
+            .collect()
+    }
+
+    /// Проверка `BYMONTHDAY` для даты (с учётом отрицательных значений).
+    fn matches_month_day(&self, date: NaiveDate) -> bool {
+        let days = days_in_month(date) as i32;
+        self.by_month_day.iter().any(|day| {
+            let day = *day as i32;
+            let resolved = if day > 0 { day } else { days + day + 1 };
+            resolved == date.day() as i32
+        })
+    }
+}
+
+/// Количество дней в месяце даты.
+fn days_in_month(date: NaiveDate) -> u32 {
+    let first = date.with_day(1).unwrap_or(date);
+    first
+        .checked_add_months(Months::new(1))
+        .map(|next| (next - first).num_days() as u32)
+        .unwrap_or(31)
+}
+
+/// Проверка `BYDAY` с порядковым номером внутри месяца (`2TU`, `-1FR`).
+fn matches_weekday_in_month(day: ByDay, date: NaiveDate, days_in_month: u32) -> bool {
+    if date.weekday() != day.weekday {
+        return false;
+    }
+
+    match day.ordinal {
+        None => true,
+        Some(ordinal) if ordinal > 0 => (date.day() - 1) / 7 + 1 == ordinal as u32,
+        Some(ordinal) => (days_in_month - date.day()) / 7 + 1 == ordinal.unsigned_abs() as u32,
+    }
+}
+
+/// Разбор дня недели (`MO`..`SU`).
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Обозначение дня недели в RRULE.
+fn weekday_str(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// Разбор `UNTIL` в формате `YYYYMMDD` или `YYYYMMDDTHHMMSSZ`.
+fn parse_until(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Some(date_time.and_utc());
+    }
+
+    // Для даты без времени включается весь день
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()?
+        .and_hms_opt(23, 59, 59)
+        .map(|date_time| date_time.and_utc())
+}
+
+/// Разбор списка целых чисел через запятую с проверкой диапазона.
+fn parse_numbers<T>(name: &str, value: &str, valid: impl Fn(i64) -> bool) -> Result<Vec<T>, String>
+where
+    T: TryFrom<i64>,
+{
+    value
+        .split(',')
+        .map(|part| {
+            part.trim()
+                .parse::<i64>()
+                .ok()
+                .filter(|number| valid(*number))
+                .and_then(|number| T::try_from(number).ok())
+                .ok_or_else(|| format!("invalid {} value `{}`", name, part))
+        })
+        .collect()
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = String;
+
+    /// Разбор строки RRULE (префикс `RRULE:` необязателен).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let value = match value.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &value[6..],
+            _ => value,
+        };
+
+        let mut freq = None;
+        let mut rule = RecurrenceRule {
+            freq: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+        };
+        let mut seen = Vec::new();
+
+        for part in value.split(';').map(str::trim).filter(|part| !part.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("rule part `{}` must look like `NAME=value`", part))?;
+            let name = name.trim().to_ascii_uppercase();
+            let value = value.trim().to_ascii_uppercase();
+            if seen.contains(&name) {
+                return Err(format!("duplicate rule part `{}`", name));
+            }
+            seen.push(name.clone());
+
+            match name.as_str() {
+                "FREQ" => {
+                    freq = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(format!("unsupported FREQ `{}`", value)),
+                    })
+                }
+                "INTERVAL" => {
+                    rule.interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|interval| (1..=1000).contains(interval))
+                        .ok_or_else(|| format!("invalid INTERVAL `{}`", value))?
+                }
+                "COUNT" => {
+                    rule.count = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|count| *count >= 1)
+                            .ok_or_else(|| format!("invalid COUNT `{}`", value))?,
+                    )
+                }
+                "UNTIL" => {
+                    rule.until = Some(parse_until(&value).ok_or_else(|| format!("invalid UNTIL `{}`", value))?)
+                }
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(|day| {
+                            let day = day.trim();
+                            // День недели — последние два символа; граница ищется по символам,
+                            // чтобы не паниковать на не-ASCII значениях
+                            let split = day.char_indices().rev().nth(1).map_or(0, |(index, _)| index);
+                            let (ordinal, weekday) = day.split_at(split);
+                            let weekday = parse_weekday(weekday)
+                                .ok_or_else(|| format!("invalid BYDAY value `{}`", day))?;
+                            let ordinal = match ordinal {
+                                "" => None,
+                                ordinal => Some(
+                                    ordinal
+                                        .trim_start_matches('+')
+                                        .parse::<i8>()
+                                        .ok()
+                                        .filter(|ordinal| *ordinal != 0 && (-5..=5).contains(ordinal))
+                                        .ok_or_else(|| format!("invalid BYDAY value `{}`", day))?,
+                                ),
+                            };
+                            Ok(ByDay { ordinal, weekday })
+                        })
+                        .collect::<Result<_, String>>()?
+                }
+                "BYMONTHDAY" => {
+                    rule.by_month_day = parse_numbers("BYMONTHDAY", &value, |day| {
+                        day != 0 && (-31..=31).contains(&day)
+                    })?
+                }
+                "BYMONTH" => {
+                    rule.by_month = parse_numbers("BYMONTH", &value, |month| (1..=12).contains(&month))?
+                }
+                "WKST" => {
+                    parse_weekday(&value).ok_or_else(|| format!("invalid WKST `{}`", value))?;
+                }
+                _ => return Err(format!("unsupported rule part `{}`", name)),
+            }
+        }
+
+        rule.freq = freq.ok_or_else(|| "FREQ is required".to_string())?;
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err("COUNT and UNTIL cannot be combined".to_string());
+        }
+        if rule.by_day.iter().any(|day| day.ordinal.is_some())
+            && !matches!(rule.freq, Frequency::Monthly | Frequency::Yearly)
+        {
+            return Err("numbered BYDAY is only supported with FREQ=MONTHLY or FREQ=YEARLY".to_string());
+        }
+        if rule.freq == Frequency::Yearly && !rule.by_day.is_empty() && rule.by_month.is_empty() {
+            return Err("BYDAY with FREQ=YEARLY requires BYMONTH".to_string());
+        }
+        if rule.freq == Frequency::Weekly && !rule.by_month_day.is_empty() {
+            return Err("BYMONTHDAY cannot be used with FREQ=WEEKLY".to_string());
+        }
+
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    /// Нормализованная строка RRULE (без префикса `RRULE:`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FREQ={}", self.freq.as_str())?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|day| match day.ordinal {
+                    Some(ordinal) => format!("{}{}", ordinal, weekday_str(day.weekday)),
+                    None => weekday_str(day.weekday).to_string(),
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(i8::to_string).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if !self.by_month.is_empty() {
+            let months: Vec<String> = self.by_month.iter().map(u32::to_string).collect();
+            write!(f, ";BYMONTH={}", months.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 9, 0, 0).unwrap()
+    }
+
+    fn dates(rule: &str, dtstart: DateTime<Utc>, limit: usize) -> Vec<NaiveDate> {
+        rule.parse::<RecurrenceRule>()
+            .unwrap()
+            .between(dtstart, dtstart - chrono::Duration::seconds(1), None, limit)
+            .into_iter()
+            .map(|occurrence| occurrence.date_naive())
+            .collect()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_and_normalizes_rule() {
+        let rule: RecurrenceRule = "rrule:freq=monthly;interval=2;byday=1mo,-1FR;count=5".parse().unwrap();
+
+        assert_eq!(rule.freq, Frequency::Monthly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.count, Some(5));
+        assert_eq!(
+            rule.by_day,
+            vec![
+                ByDay { ordinal: Some(1), weekday: Weekday::Mon },
+                ByDay { ordinal: Some(-1), weekday: Weekday::Fri },
+            ]
+        );
+        assert_eq!(rule.to_string(), "FREQ=MONTHLY;INTERVAL=2;COUNT=5;BYDAY=1MO,-1FR");
+        assert_eq!(rule.to_string().parse::<RecurrenceRule>().unwrap(), rule);
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        for rule in [
+            "",
+            "INTERVAL=2",
+            "FREQ=HOURLY",
+            "FREQ=DAILY;FREQ=WEEKLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;COUNT=0",
+            "FREQ=DAILY;COUNT=2;UNTIL=20250101",
+            "FREQ=DAILY;UNTIL=2025-01-01",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=MONTHLY;BYDAY=6MO",
+            "FREQ=MONTHLY;BYMONTHDAY=0",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=YEARLY;BYMONTH=13",
+            "FREQ=YEARLY;BYDAY=MO",
+            "FREQ=WEEKLY;BYMONTHDAY=1",
+            "FREQ=DAILY;FOO=1",
+            "FREQ",
+        ] {
+            assert!(rule.parse::<RecurrenceRule>().is_err(), "`{}` must be rejected", rule);
+        }
+    }
+
+    #[test]
+    fn rejects_non_ascii_without_panicking() {
+        for rule in [
+            "FREQ=WEEKLY;BYDAY=Éa",
+            "FREQ=WEEKLY;BYDAY=É",
+            "FREQ=MONTHLY;BYDAY=1ÉMO",
+            "FREQ=MONTHLY;BYDAY=МО",
+            "FREQ=DAILY;UNTIL=2025０１０１",
+            "FREQ=DAILY;INTERVAL=２",
+            "RRULÉ:FREQ=DAILY",
+            "ЧАСТОТА=DAILY",
+        ] {
+            assert!(rule.parse::<RecurrenceRule>().is_err(), "`{}` must be rejected", rule);
+        }
+    }
+
+    #[test]
+    fn weekly_by_day() {
+        // 2025-01-06 — понедельник
+        assert_eq!(
+            dates("FREQ=WEEKLY;BYDAY=MO,WE", at(2025, 1, 6), 4),
+            vec![date(2025, 1, 6), date(2025, 1, 8), date(2025, 1, 13), date(2025, 1, 15)]
+        );
+    }
+
+    #[test]
+    fn monthly_last_friday() {
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYDAY=-1FR", at(2025, 1, 31), 3),
+            vec![date(2025, 1, 31), date(2025, 2, 28), date(2025, 3, 28)]
+        );
+    }
+
+    #[test]
+    fn monthly_day_skips_short_months() {
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYMONTHDAY=31", at(2025, 1, 31), 3),
+            vec![date(2025, 1, 31), date(2025, 3, 31), date(2025, 5, 31)]
+        );
+    }
+
+    #[test]
+    fn count_and_until_end_series() {
+        assert_eq!(dates("FREQ=DAILY;COUNT=3", at(2025, 1, 1), 10).len(), 3);
+        assert_eq!(
+            dates("FREQ=DAILY;UNTIL=20250103", at(2025, 1, 1), 10),
+            vec![date(2025, 1, 1), date(2025, 1, 2), date(2025, 1, 3)]
+        );
+
+        let rule: RecurrenceRule = "FREQ=DAILY;COUNT=2".parse().unwrap();
+        assert_eq!(rule.next_after(at(2025, 1, 1), at(2025, 1, 2)), None);
+    }
+
+    #[test]
+    fn never_matching_rule_terminates() {
+        assert_eq!(
+            dates("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30", at(2025, 1, 1), 3),
+            vec![date(2025, 1, 1)]
+        );
+    }
+}
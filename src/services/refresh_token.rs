@@ -0,0 +1,305 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::session::SessionClientDto;
+use crate::dto::token::TokenReadDto;
+use crate::entities::refresh_token::RefreshToken;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::errors::token::TokenError;
+use crate::errors::user::UserError;
+use crate::repositories::user::{UserRepository, UserRepositoryTrait};
+use crate::services::secret_token;
+use crate::services::token::{TokenService, TokenServiceTrait};
+use crate::settings::settings;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Время жизни refresh-токена по умолчанию (в днях).
+const DEFAULT_TTL_DAYS: i64 = 30;
+
+/// Максимальная длина сохраняемого `User-Agent`.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// Сервис refresh-токенов (`RefreshTokenService`).
+///
+/// Выдаёт пару «JWT + непрозрачный refresh-токен» и обменивает refresh-токен
+/// на новую пару (ротация). Обменянный токен остаётся в базе: его повторное
+/// предъявление означает утечку, и всё семейство токенов отзывается.
+/// Семейство — это сессия (`sessions`): вход создаёт её, ротация продлевает.
+#[derive(Clone)]
+pub struct RefreshTokenService {
+    /// `token_service` — генерация JWT.
+    token_service: TokenService,
+
+    /// `user_repo` — владелец токена при обновлении.
+    user_repo: UserRepository,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+
+    /// `ttl` — время жизни refresh-токена.
+    ttl: Duration,
+}
+
+impl RefreshTokenService {
+    /// Создание нового экземпляра `RefreshTokenService`.
+    ///
+    /// Время жизни берётся из `REFRESH_TOKEN_TTL_DAYS` (по умолчанию 30 дней)
+    /// и отсчитывается заново при каждой ротации.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    ///
+    /// # Паника
+    /// Если переменная задана, но не является положительным числом.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        let ttl_days = settings::get_positive_or("REFRESH_TOKEN_TTL_DAYS", DEFAULT_TTL_DAYS);
+
+        Self {
+            token_service: TokenService::new(),
+            user_repo: UserRepository::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+            ttl: Duration::days(ttl_days),
+        }
+    }
+
+    /// Выдача пары токенов при входе.
+    ///
+    /// Начинает новую сессию (семейство refresh-токенов) и удаляет истёкшие сессии пользователя.
+    ///
+    /// :param user: пользователь, прошедший проверку пароля.
+    /// :param client: `User-Agent` и IP-адрес клиента.
+    /// :return: `TokenReadDto` с JWT и refresh-токеном.
+    pub async fn issue(&self, user: User, client: SessionClientDto) -> Result<TokenReadDto, ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        sqlx::query("DELETE FROM sessions WHERE user_id = $1 AND expires_at < now()")
+            .bind(user.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        let session_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO sessions (id, user_id, user_agent, ip_address, expires_at) VALUES ($1, $2, $3, $4, $5)",
+        )
+            .bind(session_id)
+            .bind(user.id)
+            .bind(
+                client
+                    .user_agent
+                    .map(|user_agent| user_agent.chars().take(MAX_USER_AGENT_LEN).collect::<String>()),
+            )
+            .bind(client.ip_address)
+            .bind(Utc::now() + self.ttl)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        let tokens = self.issue_in_family(&mut tx, user, session_id).await?;
+
+        tx.commit().await.map_err(DbError::from)?;
+        Ok(tokens)
+    }
+
+    /// Обмен refresh-токена на новую пару (ротация).
+    ///
+    /// - Неизвестный или отозванный токен — `InvalidRefreshToken`.
+    /// - Истёкший токен — `TokenExpired`.
+    /// - Уже обменянный токен — семейство и его сессия отзываются, `RefreshTokenReused`.
+    /// - Пользователь неактивен — `UserNotActive`.
+    ///
+    /// :param refresh_token: refresh-токен из предыдущего ответа.
+    /// :return: `TokenReadDto` с новыми JWT и refresh-токеном того же семейства.
+    pub async fn refresh(&self, refresh_token: &str) -> Result<TokenReadDto, ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let current = sqlx::query_as::<_, RefreshToken>(
+            "SELECT * FROM refresh_tokens WHERE token_hash = $1 FOR UPDATE",
+        )
+            .bind(secret_token::hash(refresh_token))
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(DbError::from)?
+            .ok_or(TokenError::InvalidRefreshToken)?;
+
+        if let Err(err) = Self::check(&current, Utc::now()) {
+            if !matches!(err, TokenError::RefreshTokenReused) {
+                return Err(err.into());
+            }
+            sqlx::query(
+                "UPDATE refresh_tokens SET revoked_at = CURRENT_TIMESTAMP WHERE family_id = $1 AND revoked_at IS NULL",
+            )
+                .bind(current.family_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(DbError::from)?;
+            sqlx::query("UPDATE sessions SET revoked_at = CURRENT_TIMESTAMP WHERE id = $1 AND revoked_at IS NULL")
+                .bind(current.family_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(DbError::from)?;
+            tx.commit().await.map_err(DbError::from)?;
+
+            tracing::warn!(
+                "refresh token reuse detected for user {}, family {} revoked",
+                current.user_id,
+                current.family_id
+            );
+            return Err(err.into());
+        }
+
+        sqlx::query("UPDATE refresh_tokens SET used_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(current.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        let user = self
+            .user_repo
+            .find(current.user_id as u64)
+            .await
+            .map_err(|_| TokenError::InvalidRefreshToken)?;
+        if user.is_active == 0 {
+            return Err(UserError::UserNotActive.into());
+        }
+        let tokens = self.issue_in_family(&mut tx, user, current.family_id).await?;
+
+        tx.commit().await.map_err(DbError::from)?;
+        Ok(tokens)
+    }
+
+    /// Выдача JWT и нового refresh-токена семейства внутри транзакции.
+    ///
+    /// Срок действия сессии продлевается до срока нового refresh-токена.
+    ///
+    /// :param conn: соединение внутри транзакции.
+    /// :param user: владелец токенов.
+    /// :param family_id: семейство refresh-токена (сессия).
+    /// :return: `TokenReadDto` с JWT и refresh-токеном.
+    async fn issue_in_family(
+        &self,
+        conn: &mut PgConnection,
+        user: User,
+        family_id: Uuid,
+    ) -> Result<TokenReadDto, ApiError> {
+        let refresh_token = secret_token::generate();
+        let expires_at = Utc::now() + self.ttl;
+        let jti = Uuid::new_v4();
+
+        sqlx::query(
+            r#"
+            INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at, access_jti)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+            .bind(user.id)
+            .bind(family_id)
+            .bind(secret_token::hash(&refresh_token))
+            .bind(expires_at)
+            .bind(jti)
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query("UPDATE sessions SET expires_at = $2 WHERE id = $1")
+            .bind(family_id)
+            .bind(expires_at)
+            .execute(conn)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(TokenReadDto {
+            refresh_token: Some(refresh_token),
+            refresh_exp: Some(expires_at.timestamp()),
+            ..self.token_service.generate_token(user, family_id, jti)?
+        })
+    }
+
+    /// Проверка состояния refresh-токена перед обменом.
+    ///
+    /// Отзыв проверяется первым, затем повторное использование: обменянный токен
+    /// считается утечкой, даже если срок его действия уже истёк.
+    ///
+    /// :param token: найденный refresh-токен.
+    /// :param now: текущее время.
+    /// :return: `Ok(())`, если токен можно обменять, иначе `InvalidRefreshToken`,
+    ///          `RefreshTokenReused` или `TokenExpired`.
+    fn check(token: &RefreshToken, now: DateTime<Utc>) -> Result<(), TokenError> {
+        if token.revoked_at.is_some() {
+            return Err(TokenError::InvalidRefreshToken);
+        }
+        if token.used_at.is_some() {
+            return Err(TokenError::RefreshTokenReused);
+        }
+        if token.expires_at <= now {
+            return Err(TokenError::TokenExpired);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(expires_at: DateTime<Utc>) -> RefreshToken {
+        RefreshToken {
+            id: 1,
+            user_id: 1,
+            family_id: Uuid::new_v4(),
+            token_hash: secret_token::hash("token"),
+            expires_at,
+            created_at: Utc::now().naive_utc(),
+            used_at: None,
+            revoked_at: None,
+            access_jti: None,
+        }
+    }
+
+    #[test]
+    fn check_accepts_fresh_token() {
+        let now = Utc::now();
+        assert!(RefreshTokenService::check(&token(now + Duration::days(1)), now).is_ok());
+    }
+
+    #[test]
+    fn check_detects_reuse_of_exchanged_token() {
+        let now = Utc::now();
+        let mut used = token(now + Duration::days(1));
+        used.used_at = Some(now.naive_utc());
+        assert!(matches!(
+            RefreshTokenService::check(&used, now),
+            Err(TokenError::RefreshTokenReused)
+        ));
+
+        // Повторное использование важнее истечения: семейство всё равно отзывается
+        used.expires_at = now - Duration::days(1);
+        assert!(matches!(
+            RefreshTokenService::check(&used, now),
+            Err(TokenError::RefreshTokenReused)
+        ));
+    }
+
+    #[test]
+    fn check_rejects_revoked_token_without_reuse_alarm() {
+        let now = Utc::now();
+        let mut revoked = token(now + Duration::days(1));
+        revoked.used_at = Some(now.naive_utc());
+        revoked.revoked_at = Some(now.naive_utc());
+        assert!(matches!(
+            RefreshTokenService::check(&revoked, now),
+            Err(TokenError::InvalidRefreshToken)
+        ));
+    }
+
+    #[test]
+    fn check_rejects_expired_token() {
+        let now = Utc::now();
+        assert!(matches!(
+            RefreshTokenService::check(&token(now), now),
+            Err(TokenError::TokenExpired)
+        ));
+        assert!(matches!(
+            RefreshTokenService::check(&token(now - Duration::seconds(1)), now),
+            Err(TokenError::TokenExpired)
+        ));
+    }
+}
@@ -0,0 +1,284 @@
+use crate::db::db::Database;
+use crate::dto::search::{SearchQuery, SearchResultDto};
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::errors::search::SearchError;
+use crate::repositories::search::{SearchRepository, SearchRepositoryTrait};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Максимальное количество искомых элементов в одном запросе.
+const MAX_TERMS: usize = 20;
+
+/// Искомый элемент запроса.
+///
+/// - `Word` — слово целиком.
+/// - `Prefix` — слово по префиксу (`deploy*`).
+/// - `Phrase` — слова подряд в указанном порядке (`"release notes"`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchTerm {
+    Word(String),
+    Prefix(String),
+    Phrase(String),
+}
+
+/// Условие запроса; условия объединяются через «И».
+///
+/// - `Any` — хотя бы один из элементов (элементы, разделённые `OR`).
+/// - `Not` — элемент не должен встречаться.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchClause {
+    Any(Vec<SearchTerm>),
+    Not(SearchTerm),
+}
+
+/// Разобранный поисковый запрос.
+///
+/// Пользовательский текст не передаётся в `to_tsquery` напрямую: каждый
+/// элемент экранируется, поэтому синтаксис `tsquery` в запросе не действует.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchExpression {
+    pub clauses: Vec<SearchClause>,
+}
+
+impl SearchExpression {
+    /// Запрос в синтаксисе `to_tsquery`.
+    ///
+    /// :return: строка вида `('bug' | 'issue') & 'deploy':* & !'staging'`.
+    pub fn to_tsquery(&self) -> String {
+        self.clauses
+            .iter()
+            .map(|clause| match clause {
+                SearchClause::Any(terms) if terms.len() == 1 => lexeme(&terms[0]),
+                SearchClause::Any(terms) => {
+                    format!("({})", terms.iter().map(lexeme).collect::<Vec<_>>().join(" | "))
+                }
+                SearchClause::Not(term) => format!("!{}", lexeme(term)),
+            })
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
+}
+
+/// Элемент запроса до объединения в условия.
+enum Token {
+    Term { negated: bool, term: SearchTerm },
+    Or,
+}
+
+impl FromStr for SearchExpression {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut clauses: Vec<SearchClause> = Vec::new();
+        let mut pending_or = false;
+
+        for token in tokenize(value) {
+            match token {
+                Token::Or => {
+                    if pending_or || !matches!(clauses.last(), Some(SearchClause::Any(_))) {
+                        return Err("`OR` must be placed between two terms".to_string());
+                    }
+                    pending_or = true;
+                }
+                Token::Term { negated: true, term } => {
+                    if pending_or {
+                        return Err("excluded terms cannot be combined with `OR`".to_string());
+                    }
+                    clauses.push(SearchClause::Not(term));
+                }
+                Token::Term { negated: false, term } => match clauses.last_mut() {
+                    Some(SearchClause::Any(terms)) if pending_or => {
+                        terms.push(term);
+                        pending_or = false;
+                    }
+                    _ => clauses.push(SearchClause::Any(vec![term])),
+                },
+            }
+        }
+
+        if pending_or {
+            return Err("`OR` must be placed between two terms".to_string());
+        }
+        if !clauses.iter().any(|clause| matches!(clause, SearchClause::Any(_))) {
+            return Err("query must contain at least one term to search for".to_string());
+        }
+        let terms: usize = clauses
+            .iter()
+            .map(|clause| match clause {
+                SearchClause::Any(terms) => terms.len(),
+                SearchClause::Not(_) => 1,
+            })
+            .sum();
+        if terms > MAX_TERMS {
+            return Err(format!("query must not contain more than {} terms", MAX_TERMS));
+        }
+
+        Ok(Self { clauses })
+    }
+}
+
+/// Разбиение запроса на слова, фразы в кавычках и операторы.
+///
+/// Элементы без букв и цифр пропускаются; незакрытая кавычка продолжается до конца строки.
+fn tokenize(value: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = value.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&first) = chars.peek() else {
+            break;
+        };
+
+        let negated = first == '-';
+        if negated {
+            chars.next();
+        }
+
+        let term = if chars.next_if_eq(&'"').is_some() {
+            let phrase: String = chars.by_ref().take_while(|c| *c != '"').collect();
+            SearchTerm::Phrase(phrase.trim().to_string())
+        } else {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '"') {
+                word.push(c);
+            }
+            if !negated && word == "OR" {
+                tokens.push(Token::Or);
+                continue;
+            }
+            match word.strip_suffix('*') {
+                Some(prefix) => SearchTerm::Prefix(prefix.trim_end_matches('*').to_string()),
+                None => SearchTerm::Word(word),
+            }
+        };
+
+        let text = match &term {
+            SearchTerm::Word(text) | SearchTerm::Prefix(text) | SearchTerm::Phrase(text) => text,
+        };
+        if text.chars().any(char::is_alphanumeric) {
+            tokens.push(Token::Term { negated, term });
+        }
+    }
+
+    tokens
+}
+
+/// Элемент запроса как экранированная лексема `tsquery`.
+fn lexeme(term: &SearchTerm) -> String {
+    let quote = |text: &str| format!("'{}'", text.replace('\\', "\\\\").replace('\'', "''"));
+
+    match term {
+        SearchTerm::Word(word) => quote(word),
+        SearchTerm::Prefix(prefix) => format!("{}:*", quote(prefix)),
+        SearchTerm::Phrase(phrase) => quote(phrase),
+    }
+}
+
+/// Сервис полнотекстового поиска (`SearchService`).
+///
+/// Ищет по заголовкам и описаниям задач и по комментариям, доступным пользователю.
+#[derive(Clone)]
+pub struct SearchService {
+    /// `search_repo` — репозиторий поиска.
+    search_repo: SearchRepository,
+}
+
+impl SearchService {
+    /// Создание нового экземпляра `SearchService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            search_repo: SearchRepository::new(db_conn),
+        }
+    }
+
+    /// Поиск задач и комментариев.
+    ///
+    /// :param user: авторизованный пользователь (учитываются только доступные ему задачи).
+    /// :param query: поисковый запрос и пагинация.
+    /// :return: результаты по убыванию релевантности или `InvalidQuery`.
+    pub async fn search(&self, user: &User, query: SearchQuery) -> Result<Vec<SearchResultDto>, ApiError> {
+        let expression = query
+            .q
+            .parse::<SearchExpression>()
+            .map_err(SearchError::InvalidQuery)?;
+        let hits = self
+            .search_repo
+            .find(user.id, &expression.to_tsquery(), query.limit, query.offset)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(hits.into_iter().map(SearchResultDto::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tsquery(value: &str) -> Result<String, String> {
+        value.parse::<SearchExpression>().map(|expression| expression.to_tsquery())
+    }
+
+    #[test]
+    fn builds_tsquery_from_terms() {
+        assert_eq!(tsquery("bug deploy*").unwrap(), "'bug' & 'deploy':*");
+        assert_eq!(tsquery(r#""release notes" -staging"#).unwrap(), "'release notes' & !'staging'");
+        assert_eq!(tsquery("bug OR issue OR defect").unwrap(), "('bug' | 'issue' | 'defect')");
+        assert_eq!(tsquery("bug OR issue deploy").unwrap(), "('bug' | 'issue') & 'deploy'");
+    }
+
+    #[test]
+    fn or_is_case_sensitive_keyword() {
+        assert_eq!(tsquery("bug or issue").unwrap(), "'bug' & 'or' & 'issue'");
+        assert_eq!(tsquery("bug -OR").unwrap(), "'bug' & !'OR'");
+        assert_eq!(tsquery(r#"bug "OR""#).unwrap(), "'bug' & 'OR'");
+    }
+
+    #[test]
+    fn rejects_misplaced_or() {
+        for query in ["OR bug", "bug OR", "bug OR OR issue", "-bug OR issue", "bug OR -issue", "OR"] {
+            assert!(tsquery(query).is_err(), "{query}");
+        }
+    }
+
+    #[test]
+    fn rejects_queries_without_positive_terms() {
+        for query in ["", "   ", "-bug", "-bug -issue", "*", "\"\"", "!!! ???", "-\"\""] {
+            assert_eq!(
+                tsquery(query).unwrap_err(),
+                "query must contain at least one term to search for",
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn limits_number_of_terms() {
+        let query = vec!["word"; MAX_TERMS].join(" ");
+        assert!(tsquery(&query).is_ok());
+        assert!(tsquery(&format!("{} -extra", query)).is_err());
+    }
+
+    #[test]
+    fn escapes_tsquery_syntax() {
+        assert_eq!(tsquery("it's").unwrap(), "'it''s'");
+        assert_eq!(tsquery(r"a\b").unwrap(), r"'a\\b'");
+        assert_eq!(tsquery("a&b|!c").unwrap(), "'a&b|!c'");
+        assert_eq!(tsquery("deploy***").unwrap(), "'deploy':*");
+        assert_eq!(tsquery(r#""unterminated phrase"#).unwrap(), "'unterminated phrase'");
+    }
+
+    #[test]
+    fn handles_non_ascii_terms() {
+        assert_eq!(tsquery("ошибка OR баг -прод").unwrap(), "('ошибка' | 'баг') & !'прод'");
+        assert_eq!(tsquery("déploi* «релиз»").unwrap(), "'déploi':* & '«релиз»'");
+        assert_eq!(tsquery("задача\u{3000}тест").unwrap(), "'задача' & 'тест'");
+        assert_eq!(tsquery("bug ＯＲ issue").unwrap(), "'bug' & 'ＯＲ' & 'issue'");
+        assert!(tsquery("🚀 — …").is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Новый секретный токен: 64 hex-символа (256 бит, из них 244 случайных).
+///
+/// Токен выдаётся клиенту один раз (например, в адресе календарной ленты),
+/// в базе хранится только `hash()`.
+///
+/// :return: токен в нижнем регистре.
+pub fn generate() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// SHA-256 токена (hex) для хранения и поиска в базе.
+///
+/// :param token: токен, предъявленный клиентом.
+/// :return: 64 hex-символа в нижнем регистре.
+pub fn hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_unique_hex_tokens() {
+        let first = generate();
+        let second = generate();
+
+        assert_eq!(first.len(), 64);
+        assert!(first.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f')));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn hashes_with_sha256() {
+        assert_eq!(hash(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(hash("token").len(), 64);
+        assert_ne!(hash("token"), hash("Token"));
+    }
+}
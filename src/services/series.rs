@@ -0,0 +1,348 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::entities::series::TaskSeries;
+use crate::entities::task::Task;
+use crate::errors::db::DbError;
+use crate::services::history;
+use crate::services::recurrence::RecurrenceRule;
+use crate::services::trash::TaskTrashService;
+use crate::settings::settings;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use sqlx::PgConnection;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use tokio::task::JoinHandle;
+
+/// Горизонт предварительного создания вхождений по умолчанию (в днях).
+const DEFAULT_HORIZON_DAYS: i64 = 30;
+
+/// Период запуска фонового материализатора по умолчанию (в минутах).
+const DEFAULT_INTERVAL_MINUTES: u64 = 60;
+
+/// Максимальное количество вхождений одной серии, создаваемых за один запуск.
+const MAX_OCCURRENCES_PER_RUN: usize = 366;
+
+/// Сервис серий повторяющихся задач (`TaskSeriesService`).
+///
+/// Создаёт следующие вхождения серии: при завершении задачи и в фоне,
+/// заранее — до горизонта `RECURRENCE_HORIZON_DAYS`.
+#[derive(Clone)]
+pub struct TaskSeriesService {
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+
+    /// `horizon` — насколько вперёд создаются вхождения.
+    horizon: Duration,
+
+    /// `interval` — период запуска фонового материализатора.
+    interval: StdDuration,
+}
+
+impl TaskSeriesService {
+    /// Создание нового экземпляра `TaskSeriesService`.
+    ///
+    /// Горизонт и период берутся из `RECURRENCE_HORIZON_DAYS` (по умолчанию 30)
+    /// и `RECURRENCE_INTERVAL_MINUTES` (по умолчанию 60).
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    ///
+    /// # Паника
+    /// Если переменные заданы, но не являются положительными числами.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        let horizon_days = settings::get_positive_or("RECURRENCE_HORIZON_DAYS", DEFAULT_HORIZON_DAYS);
+        let interval_minutes = settings::get_positive_or("RECURRENCE_INTERVAL_MINUTES", DEFAULT_INTERVAL_MINUTES);
+
+        Self {
+            db_conn: Arc::clone(db_conn),
+            horizon: Duration::days(horizon_days),
+            interval: StdDuration::from_secs(interval_minutes * 60),
+        }
+    }
+
+    /// Запуск фонового материализатора.
+    ///
+    /// Первый проход выполняется сразу, затем — каждые `interval`.
+    /// Ошибки прохода логируются и не останавливают цикл.
+    ///
+    /// :return: дескриптор фоновой задачи tokio.
+    pub fn spawn_materializer(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            loop {
+                ticker.tick().await;
+                match self.materialize_upcoming().await {
+                    Ok(0) => {}
+                    Ok(created) => tracing::info!("recurrence materializer created {} task(s)", created),
+                    Err(e) => tracing::error!("recurrence materializer failed: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Создание вхождений всех серий до горизонта.
+    ///
+    /// Каждая серия обрабатывается в отдельной транзакции с блокировкой строки
+    /// серии, поэтому параллельное завершение задачи не создаёт дубликатов.
+    /// Серии без задач удаляются; серии, у которых не осталось вхождений
+    /// (исчерпан `COUNT`/`UNTIL` или правило никогда не срабатывает),
+    /// помечаются завершёнными и больше не просматриваются.
+    ///
+    /// :return: количество созданных задач или ошибка (`DbError`).
+    pub async fn materialize_upcoming(&self) -> Result<usize, DbError> {
+        let horizon_end = Utc::now() + self.horizon;
+        let pool = self.db_conn.get_pool();
+
+        sqlx::query("DELETE FROM task_series s WHERE NOT EXISTS (SELECT 1 FROM tasks t WHERE t.series_id = s.id)")
+            .execute(pool)
+            .await?;
+
+        let series_ids = sqlx::query_scalar::<_, i32>(
+            "SELECT id FROM task_series WHERE materialized_until < $1 AND finished_at IS NULL ORDER BY id",
+        )
+            .bind(horizon_end)
+            .fetch_all(pool)
+            .await?;
+
+        let mut created = 0;
+        for series_id in series_ids {
+            let mut tx = pool.begin().await?;
+
+            let Some(series) = Self::lock(&mut tx, series_id).await? else {
+                continue;
+            };
+            let Some(template) = sqlx::query_as::<_, Task>(
+                r#"
+                SELECT * FROM tasks
+                WHERE series_id = $1 AND deleted_at IS NULL
+                ORDER BY due_at DESC NULLS LAST, id DESC
+                LIMIT 1
+                "#,
+            )
+                .bind(series.id)
+                .fetch_optional(&mut *tx)
+                .await?
+            else {
+                continue;
+            };
+            let Some(rule) = Self::rule(&series) else {
+                continue;
+            };
+
+            let occurrences = rule.between(
+                series.starts_at,
+                series.materialized_until,
+                Some(horizon_end),
+                MAX_OCCURRENCES_PER_RUN,
+            );
+            if occurrences.is_empty() && rule.next_after(series.starts_at, series.materialized_until).is_none() {
+                sqlx::query("UPDATE task_series SET finished_at = CURRENT_TIMESTAMP WHERE id = $1")
+                    .bind(series.id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            created += Self::insert_occurrences(&mut tx, &series, &template, &occurrences).await?;
+
+            tx.commit().await?;
+        }
+
+        Ok(created)
+    }
+
+    /// Создание серии для задачи с правилом повторения.
+    ///
+    /// :param conn: соединение (обычно внутри транзакции).
+    /// :param user_id: владелец серии.
+    /// :param rule: правило повторения.
+    /// :param starts_at: срок первого вхождения (`DTSTART`).
+    /// :return: ID новой серии или ошибка (`DbError`).
+    pub(crate) async fn create(
+        conn: &mut PgConnection,
+        user_id: i32,
+        rule: &RecurrenceRule,
+        starts_at: DateTime<Utc>,
+    ) -> Result<i32, DbError> {
+        let id = sqlx::query_scalar::<_, i32>(
+            r#"
+            INSERT INTO task_series (user_id, recurrence, starts_at, materialized_until)
+            VALUES ($1, $2, $3, $3)
+            RETURNING id
+            "#,
+        )
+            .bind(user_id)
+            .bind(rule.to_string())
+            .bind(starts_at)
+            .fetch_one(conn)
+            .await?;
+
+        Ok(id)
+    }
+
+    /// Завершение серии начиная с задачи (правило задачи изменено или снято).
+    ///
+    /// Не начатые (`todo`) вхождения серии со сроком позже задачи перемещаются
+    /// в корзину, у остальных вхождений снимается правило, сама серия удаляется.
+    ///
+    /// :param conn: соединение (обычно внутри транзакции).
+    /// :param task: задача, с которой заканчивается серия.
+    /// :param actor_id: пользователь, изменивший правило (для истории задач).
+    /// :return: `()` или ошибка (`DbError`).
+    pub(crate) async fn end(conn: &mut PgConnection, task: &Task, actor_id: Option<i32>) -> Result<(), DbError> {
+        let Some(series_id) = task.series_id else {
+            return Ok(());
+        };
+
+        let upcoming = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT id FROM tasks
+            WHERE series_id = $1
+              AND id <> $2
+              AND due_at > $3
+              AND status = 'todo'
+              AND deleted_at IS NULL
+            "#,
+        )
+            .bind(series_id)
+            .bind(task.id)
+            .bind(task.due_at)
+            .fetch_all(&mut *conn)
+            .await?;
+        TaskTrashService::move_to_trash(&mut *conn, &upcoming, actor_id).await?;
+
+        let detached = sqlx::query_as::<_, (i32, Option<String>)>(
+            r#"
+            UPDATE tasks t SET recurrence = NULL
+            FROM tasks old
+            WHERE old.id = t.id AND t.series_id = $1 AND t.id <> $2
+            RETURNING t.id, old.recurrence
+            "#,
+        )
+            .bind(series_id)
+            .bind(task.id)
+            .fetch_all(&mut *conn)
+            .await?;
+        for (task_id, recurrence) in detached.into_iter().filter(|(_, recurrence)| recurrence.is_some()) {
+            let changes = json!({ "recurrence": { "from": recurrence, "to": null } });
+            history::record(&mut *conn, task_id, actor_id, "updated", changes).await?;
+        }
+
+        sqlx::query("DELETE FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Создание следующего вхождения после завершения задачи.
+    ///
+    /// Если следующее вхождение уже было создано (фоновым материализатором или
+    /// завершением другой задачи серии), ничего не происходит.
+    ///
+    /// :param conn: соединение внутри транзакции завершения.
+    /// :param task: завершённая задача.
+    /// :return: `true`, если вхождение создано, или ошибка (`DbError`).
+    pub(crate) async fn advance(conn: &mut PgConnection, task: &Task) -> Result<bool, DbError> {
+        let (Some(series_id), Some(due_at)) = (task.series_id, task.due_at) else {
+            return Ok(false);
+        };
+        let Some(series) = Self::lock(conn, series_id).await? else {
+            return Ok(false);
+        };
+        if due_at < series.materialized_until {
+            return Ok(false);
+        }
+        let Some(rule) = Self::rule(&series) else {
+            return Ok(false);
+        };
+
+        let occurrences: Vec<DateTime<Utc>> = rule.next_after(series.starts_at, due_at).into_iter().collect();
+        let created = Self::insert_occurrences(conn, &series, task, &occurrences).await?;
+
+        Ok(created > 0)
+    }
+
+    /// Блокировка строки серии до конца транзакции.
+    async fn lock(conn: &mut PgConnection, id: i32) -> Result<Option<TaskSeries>, DbError> {
+        let series = sqlx::query_as::<_, TaskSeries>("SELECT * FROM task_series WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(conn)
+            .await?;
+
+        Ok(series)
+    }
+
+    /// Разбор сохранённого правила серии; некорректное правило логируется.
+    fn rule(series: &TaskSeries) -> Option<RecurrenceRule> {
+        series
+            .recurrence
+            .parse::<RecurrenceRule>()
+            .inspect_err(|e| tracing::warn!("task series {} has invalid recurrence: {}", series.id, e))
+            .ok()
+    }
+
+    /// Создание вхождений копированием задачи-шаблона и сдвиг `materialized_until`.
+    ///
+    /// Копируются поля задачи, её метки и исполнители; статус, сроки завершения
+    /// и зависимости не переносятся. Создание записывается в историю без автора.
+    ///
+    /// :param conn: соединение внутри транзакции с заблокированной серией.
+    /// :param series: серия.
+    /// :param template: задача, с которой копируются поля.
+    /// :param occurrences: сроки новых вхождений по возрастанию.
+    /// :return: количество созданных задач или ошибка (`DbError`).
+    async fn insert_occurrences(
+        conn: &mut PgConnection,
+        series: &TaskSeries,
+        template: &Task,
+        occurrences: &[DateTime<Utc>],
+    ) -> Result<usize, DbError> {
+        let Some(last) = occurrences.last() else {
+            return Ok(0);
+        };
+
+        for due_at in occurrences {
+            let task = sqlx::query_as::<_, Task>(
+                r#"
+                INSERT INTO tasks (title, description, user_id, due_at, priority, estimated_minutes, parent_id, recurrence, series_id, project_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                RETURNING *
+                "#,
+            )
+                .bind(&template.title)
+                .bind(&template.description)
+                .bind(template.user_id)
+                .bind(due_at)
+                .bind(template.priority)
+                .bind(template.estimated_minutes)
+                .bind(template.parent_id)
+                .bind(&series.recurrence)
+                .bind(series.id)
+                .bind(template.project_id)
+                .fetch_one(&mut *conn)
+                .await?;
+            history::record_diff(&mut *conn, None, &task, None, "created").await?;
+
+            sqlx::query("INSERT INTO task_labels (task_id, label_id) SELECT $1, label_id FROM task_labels WHERE task_id = $2")
+                .bind(task.id)
+                .bind(template.id)
+                .execute(&mut *conn)
+                .await?;
+
+            sqlx::query(
+                "INSERT INTO task_assignees (task_id, user_id, assigned_by) SELECT $1, user_id, assigned_by FROM task_assignees WHERE task_id = $2",
+            )
+                .bind(task.id)
+                .bind(template.id)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        sqlx::query("UPDATE task_series SET materialized_until = $1 WHERE id = $2")
+            .bind(last)
+            .bind(series.id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(occurrences.len())
+    }
+}
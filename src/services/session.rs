@@ -0,0 +1,301 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::session::SessionReadDto;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::repositories::session::{SessionRepository, SessionRepositoryTrait};
+use crate::settings::settings;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Минимальный интервал между обновлениями `last_seen_at` одной сессии.
+const LAST_SEEN_DEBOUNCE: Duration = Duration::from_secs(60);
+
+/// Размер таблицы обновлений, после которого из неё удаляются устаревшие записи.
+const MAX_TRACKED_SESSIONS: usize = 10_000;
+
+/// Доверенные прокси (`TrustedProxies`), которым можно верить в `X-Forwarded-For`.
+///
+/// Задаются в `TRUSTED_PROXIES` через запятую: адреса (`10.0.0.1`) или сети (`10.0.0.0/8`).
+/// Без настройки заголовок не учитывается, и адресом клиента считается адрес соединения.
+#[derive(Clone, Debug, Default)]
+pub struct TrustedProxies {
+    networks: Vec<(IpAddr, u8)>,
+}
+
+impl TrustedProxies {
+    /// Чтение списка прокси из настроек окружения.
+    ///
+    /// # Паника
+    /// Если `TRUSTED_PROXIES` содержит некорректный адрес или сеть.
+    pub fn from_settings() -> Self {
+        settings::get_optional("TRUSTED_PROXIES")
+            .map(|value| {
+                Self::parse(&value).unwrap_or_else(|| {
+                    panic!("TRUSTED_PROXIES must be a comma-separated list of IP addresses or CIDR networks")
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    /// Разбор списка прокси.
+    ///
+    /// :param value: адреса и сети через запятую.
+    /// :return: список или `None`, если какой-то элемент некорректен.
+    fn parse(value: &str) -> Option<Self> {
+        let networks = value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| {
+                let (address, prefix) = item.split_once('/').unwrap_or((item, ""));
+                let address = address.parse::<IpAddr>().ok()?.to_canonical();
+                let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+                let prefix = match prefix {
+                    "" => max_prefix,
+                    prefix => prefix.parse::<u8>().ok().filter(|prefix| *prefix <= max_prefix)?,
+                };
+                Some((address, prefix))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self { networks })
+    }
+
+    /// Входит ли адрес в одну из доверенных сетей.
+    fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.networks.iter().any(|(network, prefix)| match (network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(*prefix)).unwrap_or(0);
+                u32::from(*network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(*prefix)).unwrap_or(0);
+                u128::from(*network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        })
+    }
+
+    /// IP-адрес клиента.
+    ///
+    /// Если соединение пришло от доверенного прокси, `X-Forwarded-For` читается
+    /// справа налево до первого адреса, не являющегося доверенным прокси:
+    /// левее него адреса мог подставить сам клиент.
+    ///
+    /// :param peer: адрес соединения.
+    /// :param forwarded_for: значение заголовка `X-Forwarded-For`.
+    /// :return: адрес клиента.
+    pub fn client_ip(&self, peer: IpAddr, forwarded_for: Option<&str>) -> IpAddr {
+        if !self.contains(peer) {
+            return peer;
+        }
+
+        let mut client = peer;
+        for address in forwarded_for.unwrap_or_default().rsplit(',') {
+            let Ok(address) = address.trim().parse::<IpAddr>() else {
+                break;
+            };
+            client = address;
+            if !self.contains(address) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+/// Моменты последнего обновления `last_seen_at` по сессиям.
+#[derive(Default)]
+struct LastSeen {
+    sessions: HashMap<Uuid, Instant>,
+}
+
+impl LastSeen {
+    /// Решение, пора ли обновлять `last_seen_at` сессии; при положительном
+    /// ответе момент запоминается.
+    ///
+    /// :param id: идентификатор сессии.
+    /// :param now: текущий момент.
+    /// :return: `false`, если сессия обновлялась меньше `LAST_SEEN_DEBOUNCE` назад.
+    fn mark(&mut self, id: Uuid, now: Instant) -> bool {
+        if self
+            .sessions
+            .get(&id)
+            .is_some_and(|seen| now.duration_since(*seen) < LAST_SEEN_DEBOUNCE)
+        {
+            return false;
+        }
+        if self.sessions.len() >= MAX_TRACKED_SESSIONS {
+            self.sessions
+                .retain(|_, seen| now.duration_since(*seen) < LAST_SEEN_DEBOUNCE);
+        }
+        self.sessions.insert(id, now);
+        true
+    }
+}
+
+/// Сервис сессий (`SessionService`).
+///
+/// Сессии создаются при входе (`RefreshTokenService`) и завершаются выходом
+/// или отзывом (`TokenRevocationService`); здесь — список сессий и отметка
+/// активности из middleware `auth`.
+#[derive(Clone)]
+pub struct SessionService {
+    /// `session_repo` — репозиторий сессий.
+    session_repo: SessionRepository,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+
+    /// `last_seen` — когда `last_seen_at` сессии обновлялся последний раз; общая для всех клонов.
+    last_seen: Arc<Mutex<LastSeen>>,
+}
+
+impl SessionService {
+    /// Создание нового экземпляра `SessionService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            session_repo: SessionRepository::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+            last_seen: Arc::new(Mutex::new(LastSeen::default())),
+        }
+    }
+
+    /// Действующие сессии пользователя.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param current_id: сессия текущего запроса (claim `sid`).
+    /// :return: список DTO сессий, начиная с последней активной.
+    pub async fn list_sessions(&self, user: &User, current_id: Uuid) -> Result<Vec<SessionReadDto>, ApiError> {
+        let sessions = self
+            .session_repo
+            .find_active(user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| SessionReadDto {
+                is_current: session.id == current_id,
+                ..SessionReadDto::from(session)
+            })
+            .collect())
+    }
+
+    /// Отметка активности сессии.
+    ///
+    /// Не ждёт базу: обновление `last_seen_at` выполняется в фоне и не чаще
+    /// раза в `LAST_SEEN_DEBOUNCE` для одной сессии. Ошибки только логируются.
+    ///
+    /// :param id: идентификатор сессии.
+    pub fn touch(&self, id: Uuid) {
+        if !self.last_seen.lock().unwrap().mark(id, Instant::now()) {
+            return;
+        }
+
+        let db_conn = Arc::clone(&self.db_conn);
+        tokio::spawn(async move {
+            let result = sqlx::query("UPDATE sessions SET last_seen_at = CURRENT_TIMESTAMP WHERE id = $1")
+                .bind(id)
+                .execute(db_conn.get_pool())
+                .await;
+            if let Err(e) = result {
+                tracing::warn!("failed to update last seen of session {}: {}", id, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_trusted_proxies() {
+        let proxies = TrustedProxies::parse(" 10.0.0.0/8, 192.168.1.5,::1 ,").unwrap();
+
+        assert!(proxies.contains(ip("10.20.30.40")));
+        assert!(proxies.contains(ip("192.168.1.5")));
+        assert!(!proxies.contains(ip("192.168.1.6")));
+        assert!(proxies.contains(ip("::1")));
+        assert!(proxies.contains(ip("::ffff:10.0.0.1")));
+        assert!(!proxies.contains(ip("11.0.0.1")));
+
+        assert!(TrustedProxies::parse("10.0.0.0/33").is_none());
+        assert!(TrustedProxies::parse("proxy.local").is_none());
+        assert!(TrustedProxies::parse("0.0.0.0/0").unwrap().contains(ip("203.0.113.7")));
+    }
+
+    #[test]
+    fn ignores_forwarded_for_from_untrusted_peer() {
+        let proxies = TrustedProxies::parse("10.0.0.1").unwrap();
+
+        assert_eq!(proxies.client_ip(ip("203.0.113.7"), Some("1.2.3.4")), ip("203.0.113.7"));
+        assert_eq!(TrustedProxies::default().client_ip(ip("10.0.0.1"), Some("1.2.3.4")), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn takes_first_untrusted_forwarded_address() {
+        let proxies = TrustedProxies::parse("10.0.0.0/8").unwrap();
+
+        assert_eq!(proxies.client_ip(ip("10.0.0.1"), Some("203.0.113.7")), ip("203.0.113.7"));
+        // Левый адрес подставлен клиентом, правые добавлены доверенными прокси
+        assert_eq!(
+            proxies.client_ip(ip("10.0.0.1"), Some("1.2.3.4, 203.0.113.7, 10.0.0.2")),
+            ip("203.0.113.7")
+        );
+        assert_eq!(proxies.client_ip(ip("10.0.0.1"), Some("garbage, 10.0.0.2")), ip("10.0.0.2"));
+        assert_eq!(proxies.client_ip(ip("10.0.0.1"), None), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn mark_debounces_repeated_touches() {
+        let mut last_seen = LastSeen::default();
+        let now = Instant::now();
+        let id = Uuid::new_v4();
+
+        assert!(last_seen.mark(id, now));
+        assert!(!last_seen.mark(id, now + Duration::from_secs(1)));
+        assert!(!last_seen.mark(id, now + LAST_SEEN_DEBOUNCE - Duration::from_millis(1)));
+        assert!(last_seen.mark(id, now + LAST_SEEN_DEBOUNCE));
+        assert!(!last_seen.mark(id, now + LAST_SEEN_DEBOUNCE + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn mark_tracks_sessions_separately() {
+        let mut last_seen = LastSeen::default();
+        let now = Instant::now();
+
+        assert!(last_seen.mark(Uuid::new_v4(), now));
+        assert!(last_seen.mark(Uuid::new_v4(), now));
+    }
+
+    #[test]
+    fn mark_drops_stale_sessions_when_full() {
+        let mut last_seen = LastSeen::default();
+        let now = Instant::now();
+        let recent = Uuid::new_v4();
+
+        for _ in 1..MAX_TRACKED_SESSIONS {
+            last_seen.mark(Uuid::new_v4(), now);
+        }
+        let later = now + LAST_SEEN_DEBOUNCE;
+        last_seen.mark(recent, later - Duration::from_secs(1));
+        assert_eq!(last_seen.sessions.len(), MAX_TRACKED_SESSIONS);
+
+        assert!(last_seen.mark(Uuid::new_v4(), later));
+        assert_eq!(last_seen.sessions.len(), 2);
+        assert!(!last_seen.mark(recent, later));
+    }
+}
@@ -0,0 +1,1796 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::label::LabelReadDto;
+use crate::dto::pagination::{parse_sort, sort_to_string, Cursor, CursorDirection, Page, SortDirection, SortKey};
+use crate::dto::task::{
+    TaskAssigneeCreateDto, TaskBulkAction, TaskBulkDto, TaskBulkItemResultDto, TaskBulkItemStatus, TaskBulkMode,
+    TaskBulkOperationDto, TaskBulkResultDto, TaskCreateDto, TaskDeleteQuery, TaskDependencyCreateDto, TaskEventReadDto,
+    TaskLabelCreateDto, TaskListQuery, TaskMoveDto, TaskPatchDto, TaskReadDto, TaskSortField, TaskTransitionDto, TaskTreeDto,
+    TaskUpcomingQuery, TaskUpdateDto, TaskWatcherCreateDto, TaskWatcherReadDto,
+};
+use crate::entities::column::BoardColumn;
+use crate::entities::project::ProjectRole;
+use crate::entities::task::{Task, TaskStatus};
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::column::ColumnError;
+use crate::errors::db::DbError;
+use crate::errors::label::LabelError;
+use crate::errors::project::ProjectError;
+use crate::errors::request::{RequestError, ValidatedRequest};
+use crate::errors::task::TaskError;
+use crate::repositories::label::{LabelRepository, LabelRepositoryTrait};
+use crate::repositories::project::{ProjectRepository, ProjectRepositoryTrait};
+use crate::repositories::task::{TaskRepository, TaskRepositoryTrait};
+use crate::services::history;
+use crate::services::rank;
+use crate::services::recurrence::RecurrenceRule;
+use crate::services::series::TaskSeriesService;
+use crate::services::trash::TaskTrashService;
+use crate::services::workflow::TaskWorkflow;
+use chrono::Utc;
+use axum::response::IntoResponse;
+use serde_json::{json, Value};
+use sqlx::{Connection, PgConnection};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// Уровень доступа к задаче.
+///
+/// - `Read` — просмотр задачи и связанных с ней данных.
+/// - `Write` — изменение задачи.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TaskAccess {
+    Read,
+    Write,
+}
+
+impl TaskAccess {
+    /// Разрешён ли пользователю такой доступ к задаче.
+    ///
+    /// :param task: задача.
+    /// :param user_id: идентификатор пользователя.
+    /// :param role: роль пользователя в проекте задачи (`None`, если он не участник
+    /// или задача личная).
+    /// :return: `true`, если доступ есть.
+    fn allows(self, task: &Task, user_id: i32, role: Option<ProjectRole>) -> bool {
+        match task.project_id {
+            None => task.user_id == user_id,
+            Some(_) => role.is_some_and(|role| self == TaskAccess::Read || role.can_edit()),
+        }
+    }
+}
+
+/// Разобранный элемент массовой обработки (`POST /tasks/bulk`).
+enum BulkOperation {
+    UpdateStatus(i32, TaskTransitionDto),
+    AddLabel(i32, TaskLabelCreateDto),
+    Move(i32, TaskMoveDto),
+    Delete(i32, TaskDeleteQuery),
+}
+
+/// Сервис работы с задачами (`TaskService`).
+///
+/// Содержит бизнес-логику CRUD-операций над задачами и проверку прав доступа:
+/// личные задачи доступны только владельцу, задачи проекта — его участникам
+/// в соответствии с ролью.
+#[derive(Clone)]
+pub struct TaskService {
+    /// `task_repo` — репозиторий задач.
+    task_repo: TaskRepository,
+
+    /// `label_repo` — репозиторий меток.
+    label_repo: LabelRepository,
+
+    /// `project_repo` — репозиторий проектов (роли участников).
+    project_repo: ProjectRepository,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+
+    /// `workflow` — правила переходов между статусами задачи.
+    workflow: TaskWorkflow,
+}
+
+impl TaskService {
+    /// Создание нового экземпляра `TaskService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            task_repo: TaskRepository::new(db_conn),
+            label_repo: LabelRepository::new(db_conn),
+            project_repo: ProjectRepository::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+            workflow: TaskWorkflow::from_settings(),
+        }
+    }
+
+    /// Страница задач текущего пользователя.
+    ///
+    /// Поддерживает offset- и keyset-пагинацию (курсоры), фильтры и сортировку
+    /// по нескольким полям. Для стабильного порядка к ключам сортировки
+    /// всегда добавляется `id`.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param query: параметры списка из query string.
+    /// :return: страница DTO задач или ошибка (`ApiError`).
+    pub async fn list_tasks(
+        &self,
+        user: &User,
+        query: TaskListQuery,
+    ) -> Result<Page<TaskReadDto>, ApiError> {
+        let mut sort = parse_sort::<TaskSortField>(query.sort.as_deref().unwrap_or("created_at"))
+            .map_err(TaskError::InvalidListQuery)?;
+        if !sort.iter().any(|key| key.field == TaskSortField::Id) {
+            sort.push(SortKey {
+                field: TaskSortField::Id,
+                direction: SortDirection::Asc,
+            });
+        }
+        let sort_key = sort_to_string(&sort);
+
+        let cursor = match &query.cursor {
+            Some(_) if query.offset.is_some() => {
+                return Err(TaskError::InvalidListQuery(
+                    "`cursor` and `offset` cannot be combined".to_string(),
+                )
+                .into());
+            }
+            Some(raw) => match Cursor::decode(raw) {
+                Some(cursor) if cursor.sort == sort_key && cursor.values.len() == sort.len() => {
+                    Some(cursor)
+                }
+                Some(_) => {
+                    return Err(TaskError::InvalidListQuery(
+                        "cursor does not match the requested sort".to_string(),
+                    )
+                    .into());
+                }
+                None => {
+                    return Err(TaskError::InvalidListQuery("malformed cursor".to_string()).into());
+                }
+            },
+            None => None,
+        };
+
+        let total = self
+            .task_repo
+            .count(user.id, &query)
+            .await
+            .map_err(DbError::from)?;
+
+        // Запрашиваем на одну запись больше, чтобы узнать, есть ли следующая страница
+        let mut tasks = self
+            .task_repo
+            .find_page(user.id, &query, &sort, cursor.as_ref(), query.limit + 1)
+            .await
+            .map_err(DbError::from)?;
+        let has_more = tasks.len() as i64 > query.limit;
+        tasks.truncate(query.limit as usize);
+
+        let (has_next, has_prev) = match &cursor {
+            Some(cursor) if cursor.direction == CursorDirection::Prev => {
+                tasks.reverse();
+                (true, has_more)
+            }
+            Some(_) => (has_more, true),
+            None => (has_more, query.offset.unwrap_or(0) > 0),
+        };
+
+        let cursor_at = |task: &Task, direction: CursorDirection| Cursor {
+            direction,
+            sort: sort_key.clone(),
+            values: sort.iter().map(|key| key.field.cursor_value(task)).collect(),
+        };
+        // Для пустой страницы граница берётся из входящего курсора
+        let boundary = |direction: CursorDirection, task: Option<&Task>| match task {
+            Some(task) => Some(cursor_at(task, direction).encode()),
+            None => cursor.as_ref().map(|cursor| {
+                Cursor {
+                    direction,
+                    ..cursor.clone()
+                }
+                .encode()
+            }),
+        };
+
+        let next_cursor = if has_next {
+            boundary(CursorDirection::Next, tasks.last())
+        } else {
+            None
+        };
+        let prev_cursor = if has_prev {
+            boundary(CursorDirection::Prev, tasks.first())
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items: self.read_dtos(tasks).await?,
+            total,
+            next_cursor,
+            prev_cursor,
+        })
+    }
+
+    /// Просроченные задачи пользователя.
+    ///
+    /// Открытые задачи, срок которых уже наступил.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: список DTO задач, отсортированный по сроку.
+    pub async fn list_overdue(&self, user: &User) -> Result<Vec<TaskReadDto>, ApiError> {
+        let tasks = self
+            .task_repo
+            .find_due(user.id, None, Utc::now())
+            .await
+            .map_err(DbError::from)?;
+
+        self.read_dtos(tasks).await
+    }
+
+    /// Предстоящие задачи пользователя.
+    ///
+    /// Открытые задачи со сроком от текущего момента до `now + within`.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param query: горизонт выборки.
+    /// :return: список DTO задач, отсортированный по сроку.
+    pub async fn list_upcoming(
+        &self,
+        user: &User,
+        query: TaskUpcomingQuery,
+    ) -> Result<Vec<TaskReadDto>, ApiError> {
+        let now = Utc::now();
+        let tasks = self
+            .task_repo
+            .find_due(user.id, Some(now), now + query.within)
+            .await
+            .map_err(DbError::from)?;
+
+        self.read_dtos(tasks).await
+    }
+
+    /// Получение задачи по ID.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :return: DTO задачи, `TaskNotFound` или `ForbiddenTaskAccess`.
+    pub async fn get_task(&self, user: &User, id: i32) -> Result<TaskReadDto, ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Read).await?;
+        self.read_dto(task).await
+    }
+
+    /// Получение задачи вместе со всеми подзадачами в виде дерева.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор корневой задачи.
+    /// :return: дерево DTO задач, `TaskNotFound` или `ForbiddenTaskAccess`.
+    pub async fn get_task_tree(&self, user: &User, id: i32) -> Result<TaskTreeDto, ApiError> {
+        let root = self.find_accessible(user, id, TaskAccess::Read).await?;
+        let descendants = self
+            .task_repo
+            .find_descendants(root.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(TaskTreeDto::build(
+            self.read_dto(root).await?,
+            self.read_dtos(descendants).await?,
+        ))
+    }
+
+    /// Создание новой задачи от имени пользователя.
+    ///
+    /// :param user: авторизованный пользователь (владелец задачи).
+    /// :param payload: данные новой задачи.
+    /// :return: DTO созданной задачи или ошибка (`ApiError`).
+    pub async fn create_task(
+        &self,
+        user: &User,
+        payload: TaskCreateDto,
+    ) -> Result<TaskReadDto, ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        let task = self.apply_create(&mut tx, user, payload).await?;
+        tx.commit().await.map_err(DbError::from)?;
+
+        self.read_dto(task).await
+    }
+
+    /// Полная замена задачи (PUT).
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param payload: новые значения всех полей.
+    /// :return: DTO обновлённой задачи или ошибка (`ApiError`).
+    pub async fn update_task(
+        &self,
+        user: &User,
+        id: i32,
+        payload: TaskUpdateDto,
+    ) -> Result<TaskReadDto, ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Write).await?;
+        let rule = Self::parse_recurrence(payload.recurrence)?;
+
+        self.save(user, task.id, Some(rule), move |task| {
+            task.title = payload.title;
+            task.description = payload.description;
+            task.due_at = payload.due_at;
+            task.priority = payload.priority;
+            task.estimated_minutes = payload.estimated_minutes;
+            task.parent_id = payload.parent_id;
+        })
+            .await
+    }
+
+    /// Частичное обновление задачи (PATCH).
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param payload: изменяемые поля.
+    /// :return: DTO обновлённой задачи или ошибка (`ApiError`).
+    pub async fn patch_task(
+        &self,
+        user: &User,
+        id: i32,
+        payload: TaskPatchDto,
+    ) -> Result<TaskReadDto, ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Write).await?;
+        let rule = payload.recurrence.map(Self::parse_recurrence).transpose()?;
+
+        self.save(user, task.id, rule, move |task| {
+            if let Some(title) = payload.title {
+                task.title = title;
+            }
+            if let Some(description) = payload.description {
+                task.description = description;
+            }
+            if let Some(due_at) = payload.due_at {
+                task.due_at = due_at;
+            }
+            if let Some(priority) = payload.priority {
+                task.priority = priority;
+            }
+            if let Some(estimated_minutes) = payload.estimated_minutes {
+                task.estimated_minutes = estimated_minutes;
+            }
+            if let Some(parent_id) = payload.parent_id {
+                task.parent_id = parent_id;
+            }
+        })
+            .await
+    }
+
+    /// Удаление задачи в корзину.
+    ///
+    /// По умолчанию подзадачи удаляются вместе с задачей. С `keep_children`
+    /// непосредственные подзадачи переносятся к родителю удаляемой задачи.
+    /// Задачи из корзины можно восстановить, пока их не удалит фоновая очистка.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param query: правила обработки подзадач.
+    /// :return: `()` при успехе, `TaskNotFound` или `ForbiddenTaskAccess`.
+    pub async fn delete_task(
+        &self,
+        user: &User,
+        id: i32,
+        query: TaskDeleteQuery,
+    ) -> Result<(), ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        self.apply_delete(&mut tx, user, id, query.keep_children).await?;
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Задачи в корзине, доступные пользователю.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: список DTO задач, начиная с последних удалённых.
+    pub async fn list_trash(&self, user: &User) -> Result<Vec<TaskReadDto>, ApiError> {
+        let tasks = self
+            .task_repo
+            .find_trash(user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        self.read_dtos(tasks).await
+    }
+
+    /// Восстановление задачи из корзины.
+    ///
+    /// Вместе с задачей восстанавливаются подзадачи, удалённые вместе с ней.
+    /// Если родитель задачи всё ещё в корзине, задача становится корневой.
+    ///
+    /// :param user: авторизованный пользователь (право изменения задачи).
+    /// :param id: идентификатор задачи.
+    /// :return: DTO восстановленной задачи, `TaskNotFound` или `ForbiddenTaskAccess`.
+    pub async fn restore_task(&self, user: &User, id: i32) -> Result<TaskReadDto, ApiError> {
+        let task = self
+            .task_repo
+            .find_deleted(id)
+            .await
+            .ok_or(TaskError::TaskNotFound)?;
+        self.ensure_access(user, &task, TaskAccess::Write).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let restored = sqlx::query_scalar::<_, i32>(
+            r#"
+            WITH RECURSIVE subtree AS (
+                SELECT id FROM tasks WHERE id = $1 AND deleted_at = $2
+                UNION ALL
+                SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id WHERE t.deleted_at = $2
+            )
+            UPDATE tasks SET deleted_at = NULL
+            WHERE id IN (SELECT id FROM subtree)
+            RETURNING id
+            "#,
+        )
+            .bind(task.id)
+            .bind(task.deleted_at)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        for task_id in restored {
+            let changes = json!({ "deleted_at": { "from": task.deleted_at, "to": null } });
+            history::record(&mut tx, task_id, Some(user.id), "restored", changes).await?;
+        }
+
+        let detached = sqlx::query(
+            r#"
+            UPDATE tasks SET parent_id = NULL
+            WHERE id = $1 AND parent_id IN (SELECT id FROM tasks WHERE deleted_at IS NOT NULL)
+            "#,
+        )
+            .bind(task.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if detached.rows_affected() > 0 {
+            let changes = json!({ "parent_id": { "from": task.parent_id, "to": null } });
+            history::record(&mut tx, task.id, Some(user.id), "updated", changes).await?;
+        }
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        let task = self.task_repo.find(task.id).await.ok_or(TaskError::TaskNotFound)?;
+        self.read_dto(task).await
+    }
+
+    /// Задачи, от которых зависит задача.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :return: список блокирующих задач или ошибка (`ApiError`).
+    pub async fn list_dependencies(&self, user: &User, id: i32) -> Result<Vec<TaskReadDto>, ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Read).await?;
+        let dependencies = self
+            .task_repo
+            .find_dependencies(task.id)
+            .await
+            .map_err(DbError::from)?;
+
+        self.read_dtos(dependencies).await
+    }
+
+    /// Добавление зависимости «задача `id` заблокирована задачей `depends_on_id`».
+    ///
+    /// Обе задачи должны быть доступны пользователю и относиться к одному проекту
+    /// (или обе быть личными). Повторное добавление существующей зависимости
+    /// ничего не меняет. Проверка цикла и вставка выполняются под advisory-блокировкой
+    /// проекта (владельца личных задач), чтобы параллельные запросы не замкнули цикл.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор зависимой задачи.
+    /// :param payload: блокирующая задача.
+    /// :return: актуальный список блокирующих задач или `DependencyCycle`.
+    pub async fn add_dependency(
+        &self,
+        user: &User,
+        id: i32,
+        payload: TaskDependencyCreateDto,
+    ) -> Result<Vec<TaskReadDto>, ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Write).await?;
+        let blocker = self.find_accessible(user, payload.depends_on_id, TaskAccess::Read).await?;
+        if task.id == blocker.id {
+            return Err(TaskError::DependencyCycle.into());
+        }
+        if task.project_id != blocker.project_id {
+            return Err(TaskError::ProjectMismatch.into());
+        }
+
+        // Граф зависимостей не выходит за пределы проекта (или личных задач владельца)
+        let (scope, key) = match task.project_id {
+            Some(project_id) => ("project_task_dependencies", project_id),
+            None => ("task_dependencies", task.user_id),
+        };
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1), $2)")
+            .bind(scope)
+            .bind(key)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+
+        // Цикл возникает, если блокирующая задача (транзитивно) уже зависит от текущей
+        let cycle = sqlx::query_scalar::<_, bool>(
+            r#"
+            WITH RECURSIVE reachable AS (
+                SELECT depends_on_id AS id FROM task_dependencies WHERE task_id = $1
+                UNION
+                SELECT d.depends_on_id FROM task_dependencies d JOIN reachable r ON d.task_id = r.id
+            )
+            SELECT EXISTS (SELECT 1 FROM reachable WHERE id = $2)
+            "#,
+        )
+            .bind(blocker.id)
+            .bind(task.id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if cycle {
+            return Err(TaskError::DependencyCycle.into());
+        }
+
+        let inserted = sqlx::query(
+            "INSERT INTO task_dependencies (task_id, depends_on_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        )
+            .bind(task.id)
+            .bind(blocker.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if inserted.rows_affected() > 0 {
+            let changes = json!({ "depends_on_id": blocker.id });
+            history::record(&mut tx, task.id, Some(user.id), "dependency_added", changes).await?;
+        }
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        self.list_dependencies(user, task.id).await
+    }
+
+    /// Удаление зависимости.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор зависимой задачи.
+    /// :param depends_on_id: идентификатор блокирующей задачи.
+    /// :return: `()` при успехе или ошибка (`ApiError`).
+    pub async fn remove_dependency(
+        &self,
+        user: &User,
+        id: i32,
+        depends_on_id: i32,
+    ) -> Result<(), ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Write).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let deleted = sqlx::query("DELETE FROM task_dependencies WHERE task_id = $1 AND depends_on_id = $2")
+            .bind(task.id)
+            .bind(depends_on_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if deleted.rows_affected() > 0 {
+            let changes = json!({ "depends_on_id": depends_on_id });
+            history::record(&mut tx, task.id, Some(user.id), "dependency_removed", changes).await?;
+        }
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Открытые задачи пользователя в топологическом порядке зависимостей.
+    ///
+    /// Каждая задача идёт после всех открытых задач, от которых она зависит.
+    /// Среди готовых к выполнению задач первыми идут более приоритетные,
+    /// затем с более ранним сроком.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: упорядоченный список DTO задач.
+    pub async fn list_in_dependency_order(&self, user: &User) -> Result<Vec<TaskReadDto>, ApiError> {
+        let tasks = self.task_repo.find_open(user.id).await.map_err(DbError::from)?;
+        let edges = self
+            .task_repo
+            .find_dependency_edges(user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        self.read_dtos(Self::dependency_order(tasks, edges)).await
+    }
+
+    /// Топологическая сортировка задач по зависимостям (алгоритм Кана).
+    ///
+    /// Среди готовых к выполнению задач первыми идут более приоритетные, затем
+    /// с более ранним сроком, затем с меньшим `id`. Рёбра к задачам вне списка
+    /// не учитываются; задачи, входящие в цикл, в результат не попадают.
+    ///
+    /// :param tasks: задачи для упорядочивания.
+    /// :param edges: пары `(task_id, depends_on_id)`.
+    /// :return: задачи в порядке выполнения.
+    fn dependency_order(tasks: Vec<Task>, edges: Vec<(i32, i32)>) -> Vec<Task> {
+        let mut tasks: HashMap<i32, Task> = tasks.into_iter().map(|task| (task.id, task)).collect();
+        let mut pending: HashMap<i32, usize> = tasks.keys().map(|id| (*id, 0)).collect();
+        let mut dependents: HashMap<i32, Vec<i32>> = HashMap::new();
+        for (task_id, depends_on_id) in edges {
+            if tasks.contains_key(&task_id) && tasks.contains_key(&depends_on_id) {
+                *pending.entry(task_id).or_default() += 1;
+                dependents.entry(depends_on_id).or_default().push(task_id);
+            }
+        }
+
+        let key = |task: &Task| (Reverse(task.priority), task.due_at.is_none(), task.due_at, task.id);
+        let mut ready: BTreeSet<_> = pending
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(id, _)| key(&tasks[id]))
+            .collect();
+
+        let mut ordered = Vec::with_capacity(tasks.len());
+        while let Some(next) = ready.pop_first() {
+            let id = next.3;
+            for dependent in dependents.remove(&id).unwrap_or_default() {
+                let count = pending.entry(dependent).or_default();
+                *count -= 1;
+                if *count == 0 {
+                    ready.insert(key(&tasks[&dependent]));
+                }
+            }
+            ordered.extend(tasks.remove(&id));
+        }
+
+        ordered
+    }
+
+    /// Назначение метки задаче.
+    ///
+    /// Метка должна принадлежать пользователю, задача — быть доступной
+    /// ему для изменения. Повторное назначение ничего не меняет.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param payload: назначаемая метка.
+    /// :return: DTO задачи, `LabelNotFound` или `ForbiddenLabelAccess`.
+    pub async fn add_label(
+        &self,
+        user: &User,
+        id: i32,
+        payload: TaskLabelCreateDto,
+    ) -> Result<TaskReadDto, ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        let task = self.apply_label(&mut tx, user, id, payload.label_id).await?;
+        tx.commit().await.map_err(DbError::from)?;
+
+        self.read_dto(task).await
+    }
+
+    /// Снятие метки с задачи.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param label_id: идентификатор метки.
+    /// :return: `()` при успехе или ошибка (`ApiError`).
+    pub async fn remove_label(&self, user: &User, id: i32, label_id: i32) -> Result<(), ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Write).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let deleted = sqlx::query("DELETE FROM task_labels WHERE task_id = $1 AND label_id = $2")
+            .bind(task.id)
+            .bind(label_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if deleted.rows_affected() > 0 {
+            history::record(&mut tx, task.id, Some(user.id), "label_removed", json!({ "label_id": label_id })).await?;
+        }
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Открытые задачи, назначенные текущему пользователю.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: список DTO задач, отсортированный по сроку.
+    pub async fn list_assigned(&self, user: &User) -> Result<Vec<TaskReadDto>, ApiError> {
+        let tasks = self
+            .task_repo
+            .find_assigned(user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        self.read_dtos(tasks).await
+    }
+
+    /// Назначение исполнителя задачи.
+    ///
+    /// Исполнитель должен иметь доступ к задаче: для личной задачи это только
+    /// её владелец, для задачи проекта — любой участник. Назначение
+    /// записывается в историю задачи; повторное назначение ничего не меняет.
+    ///
+    /// :param user: авторизованный пользователь (право изменения задачи).
+    /// :param id: идентификатор задачи.
+    /// :param payload: назначаемый пользователь.
+    /// :return: DTO задачи или `UserWithoutAccess`.
+    pub async fn assign_user(
+        &self,
+        user: &User,
+        id: i32,
+        payload: TaskAssigneeCreateDto,
+    ) -> Result<TaskReadDto, ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Write).await?;
+        if !self.has_access(&task, payload.user_id, TaskAccess::Read).await? {
+            return Err(TaskError::UserWithoutAccess.into());
+        }
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let inserted = sqlx::query(
+            "INSERT INTO task_assignees (task_id, user_id, assigned_by) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+        )
+            .bind(task.id)
+            .bind(payload.user_id)
+            .bind(user.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if inserted.rows_affected() > 0 {
+            history::record(&mut tx, task.id, Some(user.id), "assigned", json!({ "user_id": payload.user_id })).await?;
+        }
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        self.read_dto(task).await
+    }
+
+    /// Снятие исполнителя с задачи.
+    ///
+    /// Снятие записывается в историю задачи.
+    ///
+    /// :param user: авторизованный пользователь (право изменения задачи).
+    /// :param id: идентификатор задачи.
+    /// :param user_id: идентификатор исполнителя.
+    /// :return: `()` при успехе или ошибка (`ApiError`).
+    pub async fn unassign_user(&self, user: &User, id: i32, user_id: i32) -> Result<(), ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Write).await?;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let deleted = sqlx::query("DELETE FROM task_assignees WHERE task_id = $1 AND user_id = $2")
+            .bind(task.id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        if deleted.rows_affected() > 0 {
+            history::record(&mut tx, task.id, Some(user.id), "unassigned", json!({ "user_id": user_id })).await?;
+        }
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// История изменений задачи.
+    ///
+    /// :param user: авторизованный пользователь (право чтения задачи).
+    /// :param id: идентификатор задачи.
+    /// :return: список DTO событий в порядке записи или ошибка (`ApiError`).
+    pub async fn list_history(&self, user: &User, id: i32) -> Result<Vec<TaskEventReadDto>, ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Read).await?;
+        let events = self
+            .task_repo
+            .find_events(task.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(events.into_iter().map(TaskEventReadDto::from).collect())
+    }
+
+    /// Наблюдатели задачи.
+    ///
+    /// :param user: авторизованный пользователь (право чтения задачи).
+    /// :param id: идентификатор задачи.
+    /// :return: список DTO наблюдателей или ошибка (`ApiError`).
+    pub async fn list_watchers(&self, user: &User, id: i32) -> Result<Vec<TaskWatcherReadDto>, ApiError> {
+        let task = self.find_accessible(user, id, TaskAccess::Read).await?;
+        let watchers = self
+            .task_repo
+            .find_watchers(task.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(watchers.into_iter().map(TaskWatcherReadDto::from).collect())
+    }
+
+    /// Добавление наблюдателя задачи.
+    ///
+    /// Подписать себя может любой пользователь с доступом к задаче, других —
+    /// только пользователь с правом изменения. Наблюдатель должен иметь доступ к задаче.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param payload: подписываемый пользователь.
+    /// :return: актуальный список наблюдателей или `UserWithoutAccess`.
+    pub async fn add_watcher(
+        &self,
+        user: &User,
+        id: i32,
+        payload: TaskWatcherCreateDto,
+    ) -> Result<Vec<TaskWatcherReadDto>, ApiError> {
+        let task = self.find_accessible(user, id, Self::watcher_access(user, payload.user_id)).await?;
+        if !self.has_access(&task, payload.user_id, TaskAccess::Read).await? {
+            return Err(TaskError::UserWithoutAccess.into());
+        }
+
+        sqlx::query("INSERT INTO task_watchers (task_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+            .bind(task.id)
+            .bind(payload.user_id)
+            .execute(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        self.list_watchers(user, task.id).await
+    }
+
+    /// Удаление наблюдателя задачи.
+    ///
+    /// Отписаться может любой наблюдатель, удалить другого — только
+    /// пользователь с правом изменения задачи.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param user_id: идентификатор наблюдателя.
+    /// :return: `()` при успехе или ошибка (`ApiError`).
+    pub async fn remove_watcher(&self, user: &User, id: i32, user_id: i32) -> Result<(), ApiError> {
+        let task = self.find_accessible(user, id, Self::watcher_access(user, user_id)).await?;
+
+        sqlx::query("DELETE FROM task_watchers WHERE task_id = $1 AND user_id = $2")
+            .bind(task.id)
+            .bind(user_id)
+            .execute(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Смена статуса задачи.
+    ///
+    /// Переход проверяется правилами `TaskWorkflow`. При переходе в `done`
+    /// фиксируется `completed_at`, при выходе из `done` — сбрасывается.
+    /// Строка задачи блокируется на время проверки, чтобы параллельные
+    /// переходы не обошли правила.
+    ///
+    /// Переход в `done` или `cancelled` каскадно применяется ко всем открытым
+    /// подзадачам; если хотя бы для одной из них переход запрещён, операция отклоняется.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param payload: целевой статус.
+    /// :return: DTO обновлённой задачи, `TaskNotFound`, `ForbiddenTaskAccess`
+    /// или `InvalidStatusTransition`.
+    pub async fn transition_task(
+        &self,
+        user: &User,
+        id: i32,
+        payload: TaskTransitionDto,
+    ) -> Result<TaskReadDto, ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        self.apply_transition(&mut tx, user, id, payload.status).await?;
+        tx.commit().await.map_err(DbError::from)?;
+
+        let task = self.task_repo.find(id).await.ok_or(TaskError::TaskNotFound)?;
+        self.read_dto(task).await
+    }
+
+    /// Перемещение задачи на доске проекта.
+    ///
+    /// Задача ставится в колонку сразу после `after_id` (или первой, если якорь не
+    /// указан). Меняется только ключ `rank` самой задачи; строка колонки
+    /// блокируется, поэтому параллельные перемещения в одну колонку выполняются
+    /// последовательно и не получают одинаковых ключей. Если ключ стал слишком
+    /// длинным, задачи колонки перенумеровываются.
+    ///
+    /// :param user: авторизованный пользователь (право изменения задачи).
+    /// :param id: идентификатор задачи.
+    /// :param payload: целевая колонка и задача-якорь.
+    /// :return: DTO задачи, `ColumnNotFound`, `ProjectMismatch` или `InvalidPosition`.
+    pub async fn move_task(
+        &self,
+        user: &User,
+        id: i32,
+        payload: TaskMoveDto,
+    ) -> Result<TaskReadDto, ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        self.apply_move(&mut tx, user, id, payload).await?;
+        tx.commit().await.map_err(DbError::from)?;
+
+        let task = self.task_repo.find(id).await.ok_or(TaskError::TaskNotFound)?;
+        self.read_dto(task).await
+    }
+
+    /// Массовая обработка задач в одной транзакции.
+    ///
+    /// Каждая операция выполняется в собственной точке сохранения (savepoint),
+    /// поэтому ошибка отменяет только её изменения. В режиме `AllOrNothing`
+    /// первая ошибка отменяет всю транзакцию, а оставшиеся операции пропускаются;
+    /// в режиме `PerItem` остальные операции применяются.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param payload: режим и список операций.
+    /// :return: результаты операций или ошибка базы данных (`ApiError`).
+    pub async fn bulk(&self, user: &User, payload: TaskBulkDto) -> Result<TaskBulkResultDto, ApiError> {
+        let atomic = payload.mode == TaskBulkMode::AllOrNothing;
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        let mut results = Vec::with_capacity(payload.operations.len());
+        let mut failed = false;
+
+        for (index, value) in payload.operations.into_iter().enumerate() {
+            let task_id = value
+                .get("task_id")
+                .and_then(Value::as_i64)
+                .and_then(|id| i32::try_from(id).ok());
+            let mut result = TaskBulkItemResultDto {
+                index,
+                task_id,
+                status: TaskBulkItemStatus::Skipped,
+                code: None,
+                message: None,
+            };
+            if failed && atomic {
+                results.push(result);
+                continue;
+            }
+
+            let outcome = match Self::parse_bulk_operation(value) {
+                Ok(operation) => {
+                    let mut savepoint = Connection::begin(&mut *tx).await.map_err(DbError::from)?;
+                    match self.apply_bulk_operation(&mut savepoint, user, operation).await {
+                        Ok(()) => savepoint.commit().await.map_err(DbError::from).map_err(Self::error_report),
+                        Err(e) => {
+                            savepoint.rollback().await.map_err(DbError::from)?;
+                            Err(Self::error_report(e))
+                        }
+                    }
+                }
+                Err(e) => Err(Self::error_report(e)),
+            };
+            match outcome {
+                Ok(()) => result.status = TaskBulkItemStatus::Applied,
+                Err((code, message)) => {
+                    failed = true;
+                    result.status = TaskBulkItemStatus::Failed;
+                    result.code = Some(code);
+                    result.message = Some(message);
+                }
+            }
+            results.push(result);
+        }
+
+        let committed = !(failed && atomic);
+        if committed {
+            tx.commit().await.map_err(DbError::from)?;
+        } else {
+            tx.rollback().await.map_err(DbError::from)?;
+            for result in results.iter_mut().filter(|result| result.status == TaskBulkItemStatus::Applied) {
+                result.status = TaskBulkItemStatus::RolledBack;
+            }
+        }
+
+        Ok(TaskBulkResultDto { committed, results })
+    }
+
+    /// Поиск задачи с проверкой прав доступа.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param access: требуемый уровень доступа.
+    /// :return: модель задачи, `TaskNotFound` или `ForbiddenTaskAccess`.
+    pub(crate) async fn find_accessible(&self, user: &User, id: i32, access: TaskAccess) -> Result<Task, ApiError> {
+        let task = self
+            .task_repo
+            .find(id)
+            .await
+            .ok_or(TaskError::TaskNotFound)?;
+        self.ensure_access(user, &task, access).await?;
+
+        Ok(task)
+    }
+
+    /// Блокировка задачи до конца транзакции с проверкой прав доступа.
+    ///
+    /// :param conn: соединение внутри транзакции.
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор задачи.
+    /// :param access: требуемый уровень доступа.
+    /// :return: модель задачи, `TaskNotFound` или `ForbiddenTaskAccess`.
+    async fn lock_accessible(
+        &self,
+        conn: &mut PgConnection,
+        user: &User,
+        id: i32,
+        access: TaskAccess,
+    ) -> Result<Task, ApiError> {
+        let task = sqlx::query_as::<_, Task>("SELECT * FROM tasks WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+            .bind(id)
+            .fetch_optional(conn)
+            .await
+            .map_err(DbError::from)?
+            .ok_or(TaskError::TaskNotFound)?;
+        self.ensure_access(user, &task, access).await?;
+
+        Ok(task)
+    }
+
+    /// Создание задачи внутри транзакции (см. `create_task`).
+    ///
+    /// :param conn: соединение внутри транзакции.
+    /// :param user: авторизованный пользователь (владелец задачи).
+    /// :param payload: данные новой задачи.
+    /// :return: модель созданной задачи или ошибка (`ApiError`).
+    pub(crate) async fn apply_create(
+        &self,
+        conn: &mut PgConnection,
+        user: &User,
+        payload: TaskCreateDto,
+    ) -> Result<Task, ApiError> {
+        let parent = match payload.parent_id {
+            Some(parent_id) => Some(self.lock_accessible(conn, user, parent_id, TaskAccess::Write).await?),
+            None => None,
+        };
+        // Подзадача создаётся в проекте родителя
+        let project_id = match (&parent, payload.project_id) {
+            (Some(parent), Some(project_id)) if parent.project_id != Some(project_id) => {
+                return Err(TaskError::ProjectMismatch.into());
+            }
+            (Some(parent), _) => parent.project_id,
+            (None, project_id) => project_id,
+        };
+        if let Some(project_id) = project_id {
+            self.ensure_project_editor(user, project_id).await?;
+        }
+        let rule = Self::parse_recurrence(payload.recurrence)?;
+
+        let series_id = match (&rule, payload.due_at) {
+            (Some(rule), Some(due_at)) => Some(TaskSeriesService::create(&mut *conn, user.id, rule, due_at).await?),
+            (Some(_), None) => return Err(Self::recurrence_without_due_at().into()),
+            (None, _) => None,
+        };
+
+        let task = sqlx::query_as::<_, Task>(
+            r#"
+            INSERT INTO tasks (title, description, user_id, due_at, priority, estimated_minutes, parent_id, recurrence, series_id, project_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+            .bind(payload.title)
+            .bind(payload.description)
+            .bind(user.id)
+            .bind(payload.due_at)
+            .bind(payload.priority)
+            .bind(payload.estimated_minutes)
+            .bind(payload.parent_id)
+            .bind(rule.map(|rule| rule.to_string()))
+            .bind(series_id)
+            .bind(project_id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        history::record_diff(&mut *conn, None, &task, Some(user.id), "created").await?;
+
+        Ok(task)
+    }
+
+    /// Удаление задачи в корзину внутри транзакции (см. `delete_task`).
+    async fn apply_delete(
+        &self,
+        conn: &mut PgConnection,
+        user: &User,
+        id: i32,
+        keep_children: bool,
+    ) -> Result<(), ApiError> {
+        let task = self.lock_accessible(conn, user, id, TaskAccess::Write).await?;
+
+        if keep_children {
+            let children = sqlx::query_scalar::<_, i32>(
+                "UPDATE tasks SET parent_id = $1 WHERE parent_id = $2 AND deleted_at IS NULL RETURNING id",
+            )
+                .bind(task.parent_id)
+                .bind(task.id)
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(DbError::from)?;
+            for child_id in children {
+                let changes = json!({ "parent_id": { "from": task.id, "to": task.parent_id } });
+                history::record(&mut *conn, child_id, Some(user.id), "updated", changes).await?;
+            }
+        }
+
+        TaskTrashService::move_to_trash(&mut *conn, &[task.id], Some(user.id)).await?;
+
+        Ok(())
+    }
+
+    /// Назначение метки задаче внутри транзакции (см. `add_label`).
+    async fn apply_label(
+        &self,
+        conn: &mut PgConnection,
+        user: &User,
+        id: i32,
+        label_id: i32,
+    ) -> Result<Task, ApiError> {
+        let task = self.lock_accessible(conn, user, id, TaskAccess::Write).await?;
+        let label = self
+            .label_repo
+            .find(label_id)
+            .await
+            .ok_or(LabelError::LabelNotFound)?;
+        if label.user_id != user.id {
+            return Err(LabelError::ForbiddenLabelAccess.into());
+        }
+
+        let inserted = sqlx::query("INSERT INTO task_labels (task_id, label_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+            .bind(task.id)
+            .bind(label.id)
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        if inserted.rows_affected() > 0 {
+            history::record(&mut *conn, task.id, Some(user.id), "label_added", json!({ "label_id": label.id })).await?;
+        }
+
+        Ok(task)
+    }
+
+    /// Смена статуса задачи внутри транзакции (см. `transition_task`).
+    pub(crate) async fn apply_transition(
+        &self,
+        conn: &mut PgConnection,
+        user: &User,
+        id: i32,
+        status: TaskStatus,
+    ) -> Result<(), ApiError> {
+        let task = self.lock_accessible(conn, user, id, TaskAccess::Write).await?;
+        self.workflow.check(task.status, status)?;
+
+        let mut ids = vec![task.id];
+        let mut locked = vec![task.clone()];
+        let mut recurring: Vec<Task> = task.series_id.map(|_| task.clone()).into_iter().collect();
+        if matches!(status, TaskStatus::Done | TaskStatus::Cancelled) {
+            let open_subtasks = sqlx::query_as::<_, Task>(
+                r#"
+                WITH RECURSIVE subtree AS (
+                    SELECT id FROM tasks WHERE parent_id = $1
+                    UNION ALL
+                    SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+                )
+                SELECT * FROM tasks
+                WHERE id IN (SELECT id FROM subtree)
+                  AND status NOT IN ('done', 'cancelled')
+                  AND deleted_at IS NULL
+                FOR UPDATE
+                "#,
+            )
+                .bind(task.id)
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(DbError::from)?;
+
+            for subtask in open_subtasks {
+                self.workflow.check(subtask.status, status)?;
+                ids.push(subtask.id);
+                if subtask.series_id.is_some() {
+                    recurring.push(subtask.clone());
+                }
+                locked.push(subtask);
+            }
+        }
+
+        let updated = sqlx::query_as::<_, Task>(
+            r#"
+            UPDATE tasks
+            SET status = $1,
+                completed_at = CASE WHEN $1 = 'done'::task_status THEN CURRENT_TIMESTAMP END,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = ANY($2)
+            RETURNING *
+            "#,
+        )
+            .bind(status)
+            .bind(&ids)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        for after in &updated {
+            let before = locked.iter().find(|task| task.id == after.id);
+            history::record_diff(&mut *conn, before, after, Some(user.id), "status_changed").await?;
+        }
+
+        // Завершённое вхождение серии порождает следующее
+        if status == TaskStatus::Done {
+            for task in &recurring {
+                TaskSeriesService::advance(&mut *conn, task).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Перемещение задачи на доске внутри транзакции (см. `move_task`).
+    async fn apply_move(
+        &self,
+        conn: &mut PgConnection,
+        user: &User,
+        id: i32,
+        payload: TaskMoveDto,
+    ) -> Result<(), ApiError> {
+        let task = self.lock_accessible(conn, user, id, TaskAccess::Write).await?;
+
+        let column = sqlx::query_as::<_, BoardColumn>("SELECT * FROM board_columns WHERE id = $1 FOR UPDATE")
+            .bind(payload.column_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(DbError::from)?
+            .ok_or(ColumnError::ColumnNotFound)?;
+        if task.project_id != Some(column.project_id) {
+            return Err(TaskError::ProjectMismatch.into());
+        }
+
+        let before = match payload.after_id {
+            Some(after_id) if after_id == task.id => {
+                return Err(ColumnError::InvalidPosition("task cannot be placed after itself".to_string()).into());
+            }
+            Some(after_id) => Some(
+                sqlx::query_scalar::<_, String>(
+                    "SELECT rank FROM tasks WHERE id = $1 AND column_id = $2 AND deleted_at IS NULL",
+                )
+                    .bind(after_id)
+                    .bind(column.id)
+                    .fetch_optional(&mut *conn)
+                    .await
+                    .map_err(DbError::from)?
+                    .ok_or_else(|| ColumnError::InvalidPosition("anchor task is not in this column".to_string()))?,
+            ),
+            None => None,
+        };
+        let after = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT rank FROM tasks
+            WHERE column_id = $1 AND id <> $2 AND deleted_at IS NULL AND ($3::text IS NULL OR rank > $3)
+            ORDER BY rank
+            LIMIT 1
+            "#,
+        )
+            .bind(column.id)
+            .bind(task.id)
+            .bind(&before)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        let mut rank = rank::between(before.as_deref(), after.as_deref());
+        // Задачи в корзине сохраняют ключ для восстановления, но соседями не считаются,
+        // поэтому новый ключ может совпасть с ключом одной из них
+        while sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM tasks WHERE column_id = $1 AND rank = $2 AND id <> $3)",
+        )
+            .bind(column.id)
+            .bind(&rank)
+            .bind(task.id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(DbError::from)?
+        {
+            rank = rank::between(Some(&rank), after.as_deref());
+        }
+
+        let moved = sqlx::query_as::<_, Task>(
+            "UPDATE tasks SET column_id = $1, rank = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $3 RETURNING *",
+        )
+            .bind(column.id)
+            .bind(&rank)
+            .bind(task.id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        history::record_diff(&mut *conn, Some(&task), &moved, Some(user.id), "moved").await?;
+
+        if rank.len() > rank::MAX_RANK_LEN {
+            Self::rebalance_column(&mut *conn, column.id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Разбор и валидация элемента массовой обработки.
+    ///
+    /// Сначала читаются общие поля (`TaskBulkOperationDto`), затем тот же элемент
+    /// разбирается как DTO выбранного действия.
+    fn parse_bulk_operation(value: Value) -> Result<BulkOperation, RequestError> {
+        let ValidatedRequest(operation) = ValidatedRequest::<TaskBulkOperationDto>::from_value(value.clone())?;
+        let task_id = operation.task_id;
+
+        Ok(match operation.action {
+            TaskBulkAction::UpdateStatus => BulkOperation::UpdateStatus(task_id, ValidatedRequest::from_value(value)?.0),
+            TaskBulkAction::AddLabel => BulkOperation::AddLabel(task_id, ValidatedRequest::from_value(value)?.0),
+            TaskBulkAction::Move => BulkOperation::Move(task_id, ValidatedRequest::from_value(value)?.0),
+            TaskBulkAction::Delete => BulkOperation::Delete(task_id, ValidatedRequest::from_value(value)?.0),
+        })
+    }
+
+    /// Выполнение элемента массовой обработки внутри транзакции.
+    async fn apply_bulk_operation(
+        &self,
+        conn: &mut PgConnection,
+        user: &User,
+        operation: BulkOperation,
+    ) -> Result<(), ApiError> {
+        match operation {
+            BulkOperation::UpdateStatus(id, payload) => self.apply_transition(conn, user, id, payload.status).await,
+            BulkOperation::AddLabel(id, payload) => self.apply_label(conn, user, id, payload.label_id).await.map(|_| ()),
+            BulkOperation::Move(id, payload) => self.apply_move(conn, user, id, payload).await,
+            BulkOperation::Delete(id, query) => self.apply_delete(conn, user, id, query.keep_children).await,
+        }
+    }
+
+    /// HTTP-код и текст ошибки для построчных отчётов (как в обычном ответе API).
+    pub(crate) fn error_report<E: IntoResponse + ToString>(error: E) -> (u16, String) {
+        let message = error.to_string();
+        (error.into_response().status().as_u16(), message)
+    }
+
+    /// Проверка новой родительской задачи.
+    ///
+    /// Родитель должен быть доступен пользователю для изменения, относиться к тому же
+    /// проекту и не может быть самой задачей или её подзадачей (иначе образуется цикл).
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task: перемещаемая задача.
+    /// :param parent_id: новый родитель (`None` — задача становится корневой).
+    /// :return: `()`, `TaskNotFound`, `ForbiddenTaskAccess`, `ProjectMismatch` или `ParentCycle`.
+    async fn check_parent(&self, user: &User, task: &Task, parent_id: Option<i32>) -> Result<(), ApiError> {
+        let Some(parent_id) = parent_id else {
+            return Ok(());
+        };
+
+        let parent = self.find_accessible(user, parent_id, TaskAccess::Write).await?;
+        if parent.project_id != task.project_id {
+            return Err(TaskError::ProjectMismatch.into());
+        }
+        let cycle = self
+            .task_repo
+            .is_ancestor(task.id, parent_id)
+            .await
+            .map_err(DbError::from)?;
+        if cycle {
+            return Err(TaskError::ParentCycle.into());
+        }
+
+        Ok(())
+    }
+
+    /// Перенумерация задач колонки равномерными короткими ключами.
+    ///
+    /// Ограничение уникальности откладывается до конца транзакции, чтобы
+    /// промежуточные совпадения ключей не мешали обновлению.
+    async fn rebalance_column(conn: &mut PgConnection, column_id: i32) -> Result<(), ApiError> {
+        let ids = sqlx::query_scalar::<_, i32>("SELECT id FROM tasks WHERE column_id = $1 ORDER BY rank")
+            .bind(column_id)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+
+        sqlx::query("SET CONSTRAINTS tasks_column_id_rank_key DEFERRED")
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query(
+            r#"
+            UPDATE tasks t SET rank = v.rank
+            FROM UNNEST($1::int[], $2::text[]) AS v (id, rank)
+            WHERE t.id = v.id
+            "#,
+        )
+            .bind(&ids)
+            .bind(rank::spread(ids.len()))
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Уровень доступа для изменения подписки `user_id`: себя — чтение, других — изменение.
+    fn watcher_access(user: &User, user_id: i32) -> TaskAccess {
+        if user_id == user.id {
+            TaskAccess::Read
+        } else {
+            TaskAccess::Write
+        }
+    }
+
+    /// Преобразование моделей задач в DTO с вычисляемыми полями.
+    ///
+    /// Заполняет `progress` по подзадачам, `is_blocked` по зависимостям, `labels`,
+    /// `assignee_ids` и `tracked_seconds` отдельными запросами на весь список.
+    ///
+    /// :param tasks: модели задач.
+    /// :return: список DTO в том же порядке.
+    pub(crate) async fn read_dtos(&self, tasks: Vec<Task>) -> Result<Vec<TaskReadDto>, ApiError> {
+        let ids: Vec<i32> = tasks.iter().map(|task| task.id).collect();
+        let progress = self
+            .task_repo
+            .progress(&ids)
+            .await
+            .map_err(DbError::from)?;
+        let blocked = self
+            .task_repo
+            .find_blocked(&ids)
+            .await
+            .map_err(DbError::from)?;
+        let mut labels = self
+            .label_repo
+            .find_by_tasks(&ids)
+            .await
+            .map_err(DbError::from)?;
+        let mut assignees = self
+            .task_repo
+            .find_assignees(&ids)
+            .await
+            .map_err(DbError::from)?;
+        let tracked = self
+            .task_repo
+            .tracked_seconds(&ids)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(tasks
+            .into_iter()
+            .map(|task| {
+                let mut dto = TaskReadDto::from(task);
+                dto.progress = progress.get(&dto.id).copied();
+                dto.is_blocked = blocked.contains(&dto.id);
+                dto.labels = labels
+                    .remove(&dto.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(LabelReadDto::from)
+                    .collect();
+                dto.assignee_ids = assignees.remove(&dto.id).unwrap_or_default();
+                dto.tracked_seconds = tracked.get(&dto.id).copied().unwrap_or_default();
+                dto
+            })
+            .collect())
+    }
+
+    /// Преобразование одной модели задачи в DTO с вычисляемыми полями.
+    async fn read_dto(&self, task: Task) -> Result<TaskReadDto, ApiError> {
+        let mut dtos = self.read_dtos(vec![task]).await?;
+        Ok(dtos.remove(0))
+    }
+
+    /// Проверка прав пользователя на задачу.
+    ///
+    /// Личная задача доступна только владельцу. Задачу проекта может читать
+    /// любой участник проекта, а изменять — только `owner` и `editor`.
+    ///
+    /// :return: `()` либо `ForbiddenTaskAccess`.
+    async fn ensure_access(&self, user: &User, task: &Task, access: TaskAccess) -> Result<(), ApiError> {
+        if !self.has_access(task, user.id, access).await? {
+            return Err(TaskError::ForbiddenTaskAccess.into());
+        }
+
+        Ok(())
+    }
+
+    /// Есть ли у пользователя доступ к задаче.
+    ///
+    /// :param task: задача.
+    /// :param user_id: идентификатор пользователя.
+    /// :param access: требуемый уровень доступа.
+    /// :return: `true`, если доступ есть, либо ошибка (`ApiError`).
+    async fn has_access(&self, task: &Task, user_id: i32, access: TaskAccess) -> Result<bool, ApiError> {
+        let role = match task.project_id {
+            None => None,
+            Some(project_id) => self
+                .project_repo
+                .find_role(project_id, user_id)
+                .await
+                .map_err(DbError::from)?,
+        };
+
+        Ok(access.allows(task, user_id, role))
+    }
+
+    /// Проверка, что пользователь может создавать задачи в проекте.
+    ///
+    /// :return: `()`, `ProjectNotFound` или `ForbiddenProjectAccess`.
+    async fn ensure_project_editor(&self, user: &User, project_id: i32) -> Result<(), ApiError> {
+        let role = self
+            .project_repo
+            .find_role(project_id, user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        match role {
+            Some(role) if role.can_edit() => Ok(()),
+            Some(_) => Err(ProjectError::ForbiddenProjectAccess.into()),
+            None if self.project_repo.exists(project_id).await.map_err(DbError::from)? => {
+                Err(ProjectError::ForbiddenProjectAccess.into())
+            }
+            None => Err(ProjectError::ProjectNotFound.into()),
+        }
+    }
+
+    /// Разбор правила повторения из DTO.
+    ///
+    /// :param recurrence: строка RRULE или `None`.
+    /// :return: правило, `None` или `InvalidRecurrence`.
+    fn parse_recurrence(recurrence: Option<String>) -> Result<Option<RecurrenceRule>, TaskError> {
+        recurrence
+            .map(|recurrence| recurrence.parse::<RecurrenceRule>())
+            .transpose()
+            .map_err(TaskError::InvalidRecurrence)
+    }
+
+    /// Ошибка для повторяющейся задачи без срока выполнения.
+    fn recurrence_without_due_at() -> TaskError {
+        TaskError::InvalidRecurrence("recurring task requires `due_at`".to_string())
+    }
+
+    /// Сохранение изменённых полей задачи и обновление `updated_at`.
+    ///
+    /// Изменения применяются к строке, заблокированной в транзакции, поэтому
+    /// параллельные правки других полей не теряются, а задача, удалённая
+    /// в корзину после проверки доступа, не изменяется. Новый родитель
+    /// проверяется уже после блокировки. Если правило повторения изменилось,
+    /// текущая серия завершается на этой задаче, а с новым правилом задача
+    /// начинает новую серию. Разница с сохранённой версией записывается
+    /// в историю задачи.
+    ///
+    /// :param user: пользователь, выполняющий изменение.
+    /// :param id: идентификатор задачи.
+    /// :param recurrence: новое правило (`None` — правило не меняется).
+    /// :param apply: перенос новых значений в модель задачи.
+    /// :return: DTO сохранённой задачи, `TaskNotFound` или ошибка (`ApiError`).
+    async fn save(
+        &self,
+        user: &User,
+        id: i32,
+        recurrence: Option<Option<RecurrenceRule>>,
+        apply: impl FnOnce(&mut Task) + Send,
+    ) -> Result<TaskReadDto, ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        let before = sqlx::query_as::<_, Task>("SELECT * FROM tasks WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(DbError::from)?
+            .ok_or(TaskError::TaskNotFound)?;
+        let mut task = before.clone();
+        apply(&mut task);
+
+        if task.parent_id != before.parent_id {
+            self.check_parent(user, &task, task.parent_id).await?;
+        }
+        // Правило учитывается, только если оно отличается от текущего
+        let recurrence = recurrence.filter(|rule| rule.as_ref().map(ToString::to_string) != task.recurrence);
+        let recurring = recurrence.as_ref().map_or(task.recurrence.is_some(), Option::is_some);
+        if recurring && task.due_at.is_none() {
+            return Err(Self::recurrence_without_due_at().into());
+        }
+
+        if let Some(rule) = recurrence {
+            TaskSeriesService::end(&mut tx, &task, Some(user.id)).await?;
+            task.recurrence = None;
+            task.series_id = None;
+            if let (Some(rule), Some(due_at)) = (rule, task.due_at) {
+                task.series_id = Some(TaskSeriesService::create(&mut tx, task.user_id, &rule, due_at).await?);
+                task.recurrence = Some(rule.to_string());
+            }
+        }
+
+        let task = sqlx::query_as::<_, Task>(
+            r#"
+            UPDATE tasks
+            SET title = $1,
+                description = $2,
+                due_at = $3,
+                priority = $4,
+                estimated_minutes = $5,
+                parent_id = $6,
+                recurrence = $7,
+                series_id = $8,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $9
+            RETURNING *
+            "#,
+        )
+            .bind(task.title)
+            .bind(task.description)
+            .bind(task.due_at)
+            .bind(task.priority)
+            .bind(task.estimated_minutes)
+            .bind(task.parent_id)
+            .bind(task.recurrence)
+            .bind(task.series_id)
+            .bind(task.id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        history::record_diff(&mut tx, Some(&before), &task, Some(user.id), "updated").await?;
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        self.read_dto(task).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::task::TaskPriority;
+    use validator::Validate;
+
+    fn task(id: i32, priority: TaskPriority, due_at: Option<&str>) -> Task {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": format!("Task {}", id),
+            "user_id": 1,
+            "created_at": "2024-01-01T00:00:00",
+            "status": "todo",
+            "priority": priority,
+            "due_at": due_at,
+        }))
+        .unwrap()
+    }
+
+    fn order(tasks: Vec<Task>, edges: Vec<(i32, i32)>) -> Vec<i32> {
+        TaskService::dependency_order(tasks, edges).iter().map(|task| task.id).collect()
+    }
+
+    #[test]
+    fn dependencies_come_first() {
+        let tasks = vec![
+            task(1, TaskPriority::Urgent, None),
+            task(2, TaskPriority::Low, None),
+            task(3, TaskPriority::Medium, None),
+        ];
+        assert_eq!(order(tasks, vec![(1, 2), (2, 3)]), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn ready_tasks_ordered_by_priority_then_due_date() {
+        let tasks = vec![
+            task(1, TaskPriority::Medium, None),
+            task(2, TaskPriority::Medium, Some("2024-03-01T00:00:00Z")),
+            task(3, TaskPriority::High, None),
+            task(4, TaskPriority::Medium, Some("2024-02-01T00:00:00Z")),
+        ];
+        assert_eq!(order(tasks, vec![]), vec![3, 4, 2, 1]);
+    }
+
+    #[test]
+    fn ignores_edges_to_unknown_tasks_and_skips_cycles() {
+        let tasks = vec![
+            task(1, TaskPriority::Medium, None),
+            task(2, TaskPriority::Medium, None),
+            task(3, TaskPriority::Medium, None),
+        ];
+        assert_eq!(order(tasks, vec![(1, 99), (2, 3), (3, 2)]), vec![1]);
+    }
+
+    fn project_task(project_id: i32) -> Task {
+        let mut task = task(1, TaskPriority::Medium, None);
+        task.project_id = Some(project_id);
+        task
+    }
+
+    #[test]
+    fn personal_task_is_accessible_only_to_owner() {
+        let task = task(1, TaskPriority::Medium, None);
+        for access in [TaskAccess::Read, TaskAccess::Write] {
+            assert!(access.allows(&task, 1, None));
+            assert!(!access.allows(&task, 2, None));
+            assert!(!access.allows(&task, 2, Some(ProjectRole::Owner)));
+        }
+    }
+
+    #[test]
+    fn project_task_access_follows_role() {
+        let task = project_task(7);
+        assert!(TaskAccess::Read.allows(&task, 2, Some(ProjectRole::Viewer)));
+        assert!(!TaskAccess::Write.allows(&task, 2, Some(ProjectRole::Viewer)));
+        assert!(TaskAccess::Write.allows(&task, 2, Some(ProjectRole::Editor)));
+        assert!(TaskAccess::Write.allows(&task, 2, Some(ProjectRole::Owner)));
+    }
+
+    #[test]
+    fn project_task_denied_to_non_members_including_creator() {
+        let task = project_task(7);
+        assert!(!TaskAccess::Read.allows(&task, 1, None));
+        assert!(!TaskAccess::Write.allows(&task, 2, None));
+    }
+
+    #[test]
+    fn viewer_manages_only_own_watch_subscription() {
+        let user: User = serde_json::from_value(serde_json::json!({
+            "id": 2,
+            "user_name": "viewer",
+            "email": "viewer@example.com",
+            "password": "",
+            "created_at": "2024-01-01T00:00:00",
+            "is_active": 1,
+        }))
+        .unwrap();
+        let task = project_task(7);
+        let viewer = Some(ProjectRole::Viewer);
+
+        assert!(TaskService::watcher_access(&user, 2).allows(&task, user.id, viewer));
+        assert!(!TaskService::watcher_access(&user, 3).allows(&task, user.id, viewer));
+        assert!(TaskService::watcher_access(&user, 3).allows(&task, user.id, Some(ProjectRole::Editor)));
+    }
+
+    fn parse(value: Value) -> Result<BulkOperation, (u16, String)> {
+        TaskService::parse_bulk_operation(value).map_err(TaskService::error_report)
+    }
+
+    #[test]
+    fn parses_each_bulk_action() {
+        assert!(matches!(
+            parse(json!({ "action": "update_status", "task_id": 1, "status": "done" })),
+            Ok(BulkOperation::UpdateStatus(1, TaskTransitionDto { status: TaskStatus::Done }))
+        ));
+        assert!(matches!(
+            parse(json!({ "action": "add_label", "task_id": 2, "label_id": 5 })),
+            Ok(BulkOperation::AddLabel(2, TaskLabelCreateDto { label_id: 5 }))
+        ));
+        assert!(matches!(
+            parse(json!({ "action": "move", "task_id": 3, "column_id": 4, "after_id": null })),
+            Ok(BulkOperation::Move(3, TaskMoveDto { column_id: 4, after_id: None }))
+        ));
+        assert!(matches!(
+            parse(json!({ "action": "delete", "task_id": 4 })),
+            Ok(BulkOperation::Delete(4, TaskDeleteQuery { keep_children: false }))
+        ));
+    }
+
+    #[test]
+    fn malformed_bulk_items_fail_with_request_error_codes() {
+        for value in [
+            json!({ "action": "update_status", "status": "done" }),
+            json!({ "action": "archive", "task_id": 1 }),
+            json!({ "action": "update_status", "task_id": 1, "status": "finished" }),
+            json!({ "action": "move", "task_id": 1 }),
+        ] {
+            let Err((code, _)) = parse(value.clone()) else {
+                panic!("`{}` must be rejected", value);
+            };
+            assert_eq!(code, 400, "`{}`", value);
+        }
+    }
+
+    #[test]
+    fn error_report_keeps_api_status_and_message() {
+        let (code, message) = TaskService::error_report(TaskError::TaskNotFound);
+        assert_eq!(code, 404);
+        assert_eq!(message, TaskError::TaskNotFound.to_string());
+    }
+
+    #[test]
+    fn bulk_payload_limits_operation_count() {
+        let empty: TaskBulkDto = serde_json::from_value(json!({ "operations": [] })).unwrap();
+        assert_eq!(empty.mode, TaskBulkMode::AllOrNothing);
+        assert!(empty.validate().is_err());
+
+        let operations = vec![json!({ "action": "delete", "task_id": 1 }); 101];
+        let oversized: TaskBulkDto =
+            serde_json::from_value(json!({ "mode": "per_item", "operations": operations })).unwrap();
+        assert_eq!(oversized.mode, TaskBulkMode::PerItem);
+        assert!(oversized.validate().is_err());
+    }
+}
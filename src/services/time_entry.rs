@@ -0,0 +1,337 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::time_entry::{TimeEntryReadDto, TimeEntryWriteDto, TimeReportDto, TimeReportQuery, TimeReportRowDto};
+use crate::entities::time_entry::TimeEntry;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::errors::time_entry::TimeEntryError;
+use crate::repositories::time_entry::{TimeEntryRepository, TimeEntryRepositoryTrait};
+use crate::services::task::{TaskAccess, TaskService};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+
+/// Максимальная длина периода отчёта (в днях).
+const MAX_REPORT_DAYS: i64 = 366;
+
+/// Сервис учёта времени (`TimeEntryService`).
+///
+/// Учитывать время можно по задачам, которые пользователь может изменять
+/// (проверка делегируется `TaskService`). Записи принадлежат пользователю:
+/// он видит, изменяет и удаляет только свои записи, а у задачи отображается
+/// общая сумма (`TaskReadDto.tracked_seconds`).
+#[derive(Clone)]
+pub struct TimeEntryService {
+    /// `time_entry_repo` — репозиторий записей учёта времени.
+    time_entry_repo: TimeEntryRepository,
+
+    /// `task_service` — проверка доступа к задаче.
+    task_service: TaskService,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+}
+
+impl TimeEntryService {
+    /// Создание нового экземпляра `TimeEntryService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            time_entry_repo: TimeEntryRepository::new(db_conn),
+            task_service: TaskService::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Записи пользователя по задаче.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :return: список DTO записей, начиная с последней.
+    pub async fn list_entries(&self, user: &User, task_id: i32) -> Result<Vec<TimeEntryReadDto>, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, TaskAccess::Read).await?;
+        let entries = self
+            .time_entry_repo
+            .find_by_task(task.id, user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(entries.into_iter().map(TimeEntryReadDto::from).collect())
+    }
+
+    /// Запущенный таймер пользователя.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: DTO записи или `None`, если таймер не запущен.
+    pub async fn get_running(&self, user: &User) -> Result<Option<TimeEntryReadDto>, ApiError> {
+        let entry = self
+            .time_entry_repo
+            .find_running(user.id)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(entry.map(TimeEntryReadDto::from))
+    }
+
+    /// Запуск таймера по задаче.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :return: DTO запущенной записи или `TimerAlreadyRunning`, если у пользователя
+    ///          уже идёт таймер (по любой задаче).
+    pub async fn start_timer(&self, user: &User, task_id: i32) -> Result<TimeEntryReadDto, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, TaskAccess::Write).await?;
+
+        let entry = sqlx::query_as::<_, TimeEntry>(
+            "INSERT INTO time_entries (task_id, user_id, started_at) VALUES ($1, $2, now()) RETURNING *",
+        )
+            .bind(task.id)
+            .bind(user.id)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+            .map_err(Self::map_unique)?;
+
+        Ok(TimeEntryReadDto::from(entry))
+    }
+
+    /// Остановка таймера по задаче.
+    ///
+    /// Доступ к задаче не проверяется: остановить свой таймер можно и после
+    /// потери доступа к ней.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param task_id: идентификатор задачи.
+    /// :return: DTO завершённой записи или `TimerNotRunning`.
+    pub async fn stop_timer(&self, user: &User, task_id: i32) -> Result<TimeEntryReadDto, ApiError> {
+        let entry = sqlx::query_as::<_, TimeEntry>(
+            r#"
+            UPDATE time_entries SET ended_at = now(), updated_at = CURRENT_TIMESTAMP
+            WHERE task_id = $1 AND user_id = $2 AND ended_at IS NULL
+            RETURNING *
+            "#,
+        )
+            .bind(task_id)
+            .bind(user.id)
+            .fetch_optional(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?
+            .ok_or(TimeEntryError::TimerNotRunning)?;
+
+        Ok(TimeEntryReadDto::from(entry))
+    }
+
+    /// Ручная запись времени по задаче.
+    ///
+    /// :param user: авторизованный пользователь (владелец записи).
+    /// :param task_id: идентификатор задачи.
+    /// :param payload: завершённый интервал и комментарий.
+    /// :return: DTO созданной записи или `InvalidTimeRange`.
+    pub async fn create_entry(
+        &self,
+        user: &User,
+        task_id: i32,
+        payload: TimeEntryWriteDto,
+    ) -> Result<TimeEntryReadDto, ApiError> {
+        let task = self.task_service.find_accessible(user, task_id, TaskAccess::Write).await?;
+        Self::check_range(&payload)?;
+
+        let entry = sqlx::query_as::<_, TimeEntry>(
+            r#"
+            INSERT INTO time_entries (task_id, user_id, started_at, ended_at, note)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+            .bind(task.id)
+            .bind(user.id)
+            .bind(payload.started_at)
+            .bind(payload.ended_at)
+            .bind(payload.note)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(TimeEntryReadDto::from(entry))
+    }
+
+    /// Изменение интервала и комментария записи.
+    ///
+    /// Запущенный таймер при этом останавливается в `ended_at`.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор записи.
+    /// :param payload: новый интервал и комментарий.
+    /// :return: DTO записи, `TimeEntryNotFound`, `ForbiddenTimeEntryAccess` или `InvalidTimeRange`.
+    pub async fn update_entry(
+        &self,
+        user: &User,
+        id: i32,
+        payload: TimeEntryWriteDto,
+    ) -> Result<TimeEntryReadDto, ApiError> {
+        let entry = self.find_owned(user, id).await?;
+        Self::check_range(&payload)?;
+
+        let entry = sqlx::query_as::<_, TimeEntry>(
+            r#"
+            UPDATE time_entries
+            SET started_at = $2, ended_at = $3, note = $4, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+            .bind(entry.id)
+            .bind(payload.started_at)
+            .bind(payload.ended_at)
+            .bind(payload.note)
+            .fetch_one(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(TimeEntryReadDto::from(entry))
+    }
+
+    /// Удаление записи.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param id: идентификатор записи.
+    /// :return: `()`, `TimeEntryNotFound` или `ForbiddenTimeEntryAccess`.
+    pub async fn delete_entry(&self, user: &User, id: i32) -> Result<(), ApiError> {
+        let entry = self.find_owned(user, id).await?;
+
+        sqlx::query("DELETE FROM time_entries WHERE id = $1")
+            .bind(entry.id)
+            .execute(self.db_conn.get_pool())
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Отчёт по учтённому пользователем времени за период.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param query: период (даты в UTC, включительно) и группировка.
+    /// :return: DTO отчёта или `InvalidReportRange`.
+    pub async fn report(&self, user: &User, query: TimeReportQuery) -> Result<TimeReportDto, ApiError> {
+        let (from, to) = Self::report_range(&query)?;
+        let rows = self
+            .time_entry_repo
+            .report(user.id, from, to, query.group_by)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(TimeReportDto {
+            from: query.from,
+            to: query.to,
+            group_by: query.group_by,
+            total_seconds: rows.iter().map(|row| row.seconds).sum(),
+            rows: rows.into_iter().map(TimeReportRowDto::from).collect(),
+        })
+    }
+
+    /// Поиск записи с проверкой владельца.
+    ///
+    /// :return: модель записи, `TimeEntryNotFound` или `ForbiddenTimeEntryAccess`.
+    async fn find_owned(&self, user: &User, id: i32) -> Result<TimeEntry, ApiError> {
+        let entry = self
+            .time_entry_repo
+            .find(id)
+            .await
+            .ok_or(TimeEntryError::TimeEntryNotFound)?;
+        if entry.user_id != user.id {
+            return Err(TimeEntryError::ForbiddenTimeEntryAccess.into());
+        }
+
+        Ok(entry)
+    }
+
+    /// Границы периода отчёта в UTC: начало первого дня и начало дня после последнего.
+    ///
+    /// :return: полуоткрытый интервал `[from, to)` или `InvalidReportRange`.
+    fn report_range(query: &TimeReportQuery) -> Result<(DateTime<Utc>, DateTime<Utc>), TimeEntryError> {
+        let days = (query.to - query.from).num_days() + 1;
+        if !(1..=MAX_REPORT_DAYS).contains(&days) {
+            return Err(TimeEntryError::InvalidReportRange);
+        }
+
+        let from = query.from.and_time(Default::default()).and_utc();
+        Ok((from, from + Duration::days(days)))
+    }
+
+    /// Проверка интервала: конец позже начала и не в будущем.
+    fn check_range(payload: &TimeEntryWriteDto) -> Result<(), TimeEntryError> {
+        if payload.ended_at <= payload.started_at || payload.ended_at > Utc::now() {
+            return Err(TimeEntryError::InvalidTimeRange);
+        }
+
+        Ok(())
+    }
+
+    /// Преобразование ошибки `sqlx`: второй запущенный таймер → `TimerAlreadyRunning`.
+    fn map_unique(error: sqlx::Error) -> ApiError {
+        match DbError::from(error) {
+            DbError::UniqueConstraintViolation(_) => TimeEntryError::TimerAlreadyRunning.into(),
+            error => error.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn entry(started_at: DateTime<Utc>, ended_at: DateTime<Utc>) -> TimeEntryWriteDto {
+        TimeEntryWriteDto {
+            started_at,
+            ended_at,
+            note: None,
+        }
+    }
+
+    fn query(from: &str, to: &str) -> TimeReportQuery {
+        TimeReportQuery {
+            from: from.parse::<NaiveDate>().unwrap(),
+            to: to.parse::<NaiveDate>().unwrap(),
+            group_by: Default::default(),
+        }
+    }
+
+    #[test]
+    fn manual_entry_must_end_after_start_and_not_in_future() {
+        let now = Utc::now();
+        assert!(TimeEntryService::check_range(&entry(now - Duration::hours(2), now - Duration::hours(1))).is_ok());
+
+        for (started_at, ended_at) in [
+            (now - Duration::hours(1), now - Duration::hours(1)),
+            (now - Duration::hours(1), now - Duration::hours(2)),
+            (now - Duration::hours(1), now + Duration::hours(1)),
+        ] {
+            assert!(matches!(
+                TimeEntryService::check_range(&entry(started_at, ended_at)),
+                Err(TimeEntryError::InvalidTimeRange)
+            ));
+        }
+    }
+
+    #[test]
+    fn report_range_includes_both_days() {
+        let (from, to) = TimeEntryService::report_range(&query("2026-03-01", "2026-03-01")).unwrap();
+        assert_eq!(from.to_rfc3339(), "2026-03-01T00:00:00+00:00");
+        assert_eq!(to - from, Duration::days(1));
+
+        let (from, to) = TimeEntryService::report_range(&query("2026-01-01", "2026-12-31")).unwrap();
+        assert_eq!(to - from, Duration::days(365));
+    }
+
+    #[test]
+    fn report_range_is_limited() {
+        assert!(TimeEntryService::report_range(&query("2024-01-01", "2024-12-31")).is_ok());
+        for (from, to) in [("2026-03-02", "2026-03-01"), ("2024-01-01", "2025-01-01")] {
+            assert!(matches!(
+                TimeEntryService::report_range(&query(from, to)),
+                Err(TimeEntryError::InvalidReportRange)
+            ));
+        }
+    }
+}
@@ -4,6 +4,7 @@ use crate::entities::user::User;
 use crate::errors::token::TokenError;
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
+use uuid::Uuid;
 
 /// Сервис работы с JWT-токенами (`TokenService`).
 ///
@@ -38,9 +39,13 @@ pub trait TokenServiceTrait {
 
     /// Генерация нового JWT-токена для пользователя.
     ///
+    /// Refresh-токен выдаёт `RefreshTokenService`, здесь он не заполняется.
+    ///
     /// :param user: сущность пользователя.
+    /// :param sid: сессия, в которой выдаётся токен (claim `sid`).
+    /// :param jti: идентификатор токена (claim `jti`), по которому токен можно отозвать.
     /// :return: `TokenReadDto` (токен + время iat/exp) или ошибка.
-    fn generate_token(&self, user: User) -> Result<TokenReadDto, TokenError>;
+    fn generate_token(&self, user: User, sid: Uuid, jti: Uuid) -> Result<TokenReadDto, TokenError>;
 
     const TOKEN_EXPIRATION: i64;
 }
@@ -66,7 +71,7 @@ impl TokenServiceTrait for TokenService {
     }
 
     /// Генерирует JWT-токен с заданным временем жизни.
-    fn generate_token(&self, user: User) -> Result<TokenReadDto, TokenError> {
+    fn generate_token(&self, user: User, sid: Uuid, jti: Uuid) -> Result<TokenReadDto, TokenError> {
         let iat = Utc::now().timestamp();
         let exp = Utc::now()
             .checked_add_signed(Duration::minutes(Self::TOKEN_EXPIRATION))
@@ -78,6 +83,8 @@ impl TokenServiceTrait for TokenService {
             email: user.email,
             iat,
             exp,
+            jti,
+            sid,
         };
 
         let token = encode(
@@ -87,7 +94,13 @@ impl TokenServiceTrait for TokenService {
         )
             .map_err(|e| TokenError::TokenCreationError(e.to_string()))?;
 
-        Ok(TokenReadDto { token, iat, exp })
+        Ok(TokenReadDto {
+            token,
+            iat,
+            exp,
+            refresh_token: None,
+            refresh_exp: None,
+        })
     }
 
     /// Время жизни токена: 30 минут.
@@ -0,0 +1,406 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::token::TokenClaimsDto;
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::errors::session::SessionError;
+use crate::services::token::{TokenService, TokenServiceTrait};
+use crate::settings::settings;
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Время хранения отрицательного ответа («не отозван») в кэше по умолчанию (в секундах).
+const DEFAULT_CACHE_SECONDS: u64 = 30;
+
+/// Размер кэша, после которого из него удаляются устаревшие записи.
+const MAX_CACHE_ENTRIES: usize = 10_000;
+
+/// Запись кэша отзыва.
+///
+/// - `revoked` — токен отозван.
+/// - `until` — до какого момента ответ считается актуальным.
+#[derive(Clone, Copy)]
+struct CacheEntry {
+    revoked: bool,
+    until: Instant,
+}
+
+/// Кэш результатов проверки отзыва по `jti`.
+///
+/// Размер ограничен `MAX_CACHE_ENTRIES`: при заполнении сначала удаляются
+/// устаревшие записи, а если их нет — кэш очищается целиком.
+#[derive(Default)]
+struct RevocationCache {
+    entries: HashMap<Uuid, CacheEntry>,
+}
+
+impl RevocationCache {
+    /// Актуальный ответ для токена.
+    ///
+    /// :param jti: идентификатор токена.
+    /// :param now: текущий момент.
+    /// :return: `Some(revoked)`, если запись есть и не устарела, иначе `None`.
+    fn get(&self, jti: &Uuid, now: Instant) -> Option<bool> {
+        self.entries
+            .get(jti)
+            .filter(|entry| entry.until > now)
+            .map(|entry| entry.revoked)
+    }
+
+    /// Сохранение ответа; нулевое время хранения ничего не сохраняет.
+    ///
+    /// :param jti: идентификатор токена.
+    /// :param revoked: токен отозван.
+    /// :param ttl: время хранения ответа.
+    /// :param now: текущий момент.
+    fn insert(&mut self, jti: Uuid, revoked: bool, ttl: Duration, now: Instant) {
+        if ttl.is_zero() {
+            return;
+        }
+        if self.entries.len() >= MAX_CACHE_ENTRIES {
+            self.entries.retain(|_, entry| entry.until > now);
+        }
+        if self.entries.len() >= MAX_CACHE_ENTRIES {
+            self.entries.clear();
+        }
+        self.entries.insert(jti, CacheEntry { revoked, until: now + ttl });
+    }
+}
+
+/// Сервис отзыва JWT (`TokenRevocationService`).
+///
+/// Отозванные токены хранятся в `revoked_tokens` до истечения их срока.
+/// Middleware `auth` проверяет каждый запрос, поэтому перед таблицей стоит
+/// кэш в памяти: отзыв навсегда, так что положительный ответ хранится до
+/// истечения токена, а отрицательный — `TOKEN_REVOCATION_CACHE_SECONDS`.
+/// Отзыв через этот экземпляр попадает в кэш сразу; другие экземпляры
+/// приложения узнают о нём не позже чем через это время.
+#[derive(Clone)]
+pub struct TokenRevocationService {
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+
+    /// `cache` — результаты проверок по `jti`, общие для всех клонов сервиса.
+    cache: Arc<RwLock<RevocationCache>>,
+
+    /// `cache_ttl` — время хранения отрицательного ответа.
+    cache_ttl: Duration,
+}
+
+impl TokenRevocationService {
+    /// Создание нового экземпляра `TokenRevocationService`.
+    ///
+    /// Время хранения отрицательного ответа берётся из `TOKEN_REVOCATION_CACHE_SECONDS`
+    /// (по умолчанию 30; `0` отключает кэширование отрицательных ответов).
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    ///
+    /// # Паника
+    /// Если переменная задана, но не является неотрицательным числом.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        let cache_seconds = settings::get_non_negative_or("TOKEN_REVOCATION_CACHE_SECONDS", DEFAULT_CACHE_SECONDS);
+
+        Self {
+            db_conn: Arc::clone(db_conn),
+            cache: Arc::new(RwLock::new(RevocationCache::default())),
+            cache_ttl: Duration::from_secs(cache_seconds),
+        }
+    }
+
+    /// Проверка, отозван ли токен.
+    ///
+    /// Учитывает отзыв по `jti` и выход пользователя со всех устройств.
+    ///
+    /// :param user: владелец токена.
+    /// :param claims: claims токена.
+    /// :return: `true`, если токен отозван, либо `DbError`.
+    pub async fn is_revoked(&self, user: &User, claims: &TokenClaimsDto) -> Result<bool, DbError> {
+        if Self::issued_before_logout(user, claims) {
+            return Ok(true);
+        }
+
+        let cached = self.cache.read().unwrap().get(&claims.jti, Instant::now());
+        if let Some(revoked) = cached {
+            return Ok(revoked);
+        }
+
+        let revoked = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM revoked_tokens WHERE jti = $1)")
+            .bind(claims.jti)
+            .fetch_one(self.db_conn.get_pool())
+            .await?;
+        self.remember(claims.jti, claims.exp, revoked);
+
+        Ok(revoked)
+    }
+
+    /// Отзыв токена (выход из системы).
+    ///
+    /// Вместе с JWT завершается его сессия и отзывается её цепочка refresh-токенов.
+    ///
+    /// :param user: владелец токена.
+    /// :param claims: claims отзываемого токена.
+    /// :return: `()` либо ошибка (`ApiError`).
+    pub async fn revoke(&self, user: &User, claims: &TokenClaimsDto) -> Result<(), ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        Self::insert(&mut tx, user.id, claims.jti, claims.exp).await?;
+        Self::end_session(&mut tx, user.id, claims.sid).await?;
+
+        tx.commit().await.map_err(DbError::from)?;
+        self.remember(claims.jti, claims.exp, true);
+        Ok(())
+    }
+
+    /// Завершение сессии пользователя (`DELETE /profile/sessions/:id`).
+    ///
+    /// Отзываются refresh-токены сессии и ещё не истёкшие JWT, выданные вместе с ними.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param session_id: идентификатор сессии.
+    /// :return: `()` или `SessionNotFound`, если сессии нет, она чужая или уже завершена.
+    pub async fn revoke_session(&self, user: &User, session_id: Uuid) -> Result<(), ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        if !Self::end_session(&mut tx, user.id, session_id).await? {
+            return Err(SessionError::SessionNotFound.into());
+        }
+        // JWT выдаётся в одной транзакции с refresh-токеном, поэтому его срок
+        // отсчитывается от `created_at` записи
+        let access_tokens = sqlx::query_as::<_, (Uuid, i64)>(
+            r#"
+            SELECT access_jti, EXTRACT(EPOCH FROM created_at + make_interval(mins => $2::int))::bigint
+            FROM refresh_tokens
+            WHERE family_id = $1 AND access_jti IS NOT NULL
+              AND created_at + make_interval(mins => $2::int) > CURRENT_TIMESTAMP
+            "#,
+        )
+            .bind(session_id)
+            .bind(TokenService::TOKEN_EXPIRATION as i32)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(DbError::from)?;
+        for (jti, exp) in &access_tokens {
+            Self::insert(&mut tx, user.id, *jti, *exp).await?;
+        }
+
+        tx.commit().await.map_err(DbError::from)?;
+        for (jti, exp) in access_tokens {
+            self.remember(jti, exp, true);
+        }
+        Ok(())
+    }
+
+    /// Выход со всех устройств.
+    ///
+    /// Все JWT пользователя, выпущенные до этого момента (включая текущий),
+    /// и все его refresh-токены становятся недействительными.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :return: `()` либо ошибка (`ApiError`).
+    pub async fn revoke_all(&self, user: &User) -> Result<(), ApiError> {
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+
+        Self::end_all_sessions(&mut tx, user.id).await?;
+
+        tx.commit().await.map_err(DbError::from)?;
+        Ok(())
+    }
+
+    /// Завершение всех сессий пользователя внутри транзакции.
+    ///
+    /// JWT, выпущенные до этого момента или в ту же секунду, отклоняются по `users.tokens_revoked_at`,
+    /// refresh-токены и сессии отзываются. Используется также при сбросе пароля.
+    ///
+    /// :param conn: соединение внутри транзакции.
+    /// :param user_id: идентификатор пользователя.
+    /// :return: `()` либо ошибка (`ApiError`).
+    pub(crate) async fn end_all_sessions(conn: &mut PgConnection, user_id: i32) -> Result<(), ApiError> {
+        sqlx::query("UPDATE users SET tokens_revoked_at = now() WHERE id = $1")
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query("UPDATE refresh_tokens SET revoked_at = CURRENT_TIMESTAMP WHERE user_id = $1 AND revoked_at IS NULL")
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query("UPDATE sessions SET revoked_at = CURRENT_TIMESTAMP WHERE user_id = $1 AND revoked_at IS NULL")
+            .bind(user_id)
+            .execute(conn)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Завершение сессии и отзыв её refresh-токенов внутри транзакции.
+    ///
+    /// :return: `true`, если действующая сессия пользователя была завершена.
+    async fn end_session(conn: &mut PgConnection, user_id: i32, session_id: Uuid) -> Result<bool, ApiError> {
+        let ended = sqlx::query(
+            "UPDATE sessions SET revoked_at = CURRENT_TIMESTAMP WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+        )
+            .bind(session_id)
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?
+            .rows_affected()
+            > 0;
+        sqlx::query("UPDATE refresh_tokens SET revoked_at = CURRENT_TIMESTAMP WHERE family_id = $1 AND revoked_at IS NULL")
+            .bind(session_id)
+            .execute(conn)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(ended)
+    }
+
+    /// Запись токена в `revoked_tokens` с удалением истёкших записей.
+    async fn insert(conn: &mut PgConnection, user_id: i32, jti: Uuid, exp: i64) -> Result<(), ApiError> {
+        sqlx::query("DELETE FROM revoked_tokens WHERE expires_at < now()")
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::from)?;
+        sqlx::query(
+            r#"
+            INSERT INTO revoked_tokens (jti, user_id, expires_at) VALUES ($1, $2, $3)
+            ON CONFLICT (jti) DO NOTHING
+            "#,
+        )
+            .bind(jti)
+            .bind(user_id)
+            .bind(DateTime::<Utc>::from_timestamp(exp, 0).unwrap_or_else(Utc::now))
+            .execute(conn)
+            .await
+            .map_err(DbError::from)?;
+
+        Ok(())
+    }
+
+    /// Выпущен ли токен до выхода пользователя со всех устройств.
+    ///
+    /// :param user: владелец токена.
+    /// :param claims: claims токена.
+    /// :return: `true`, если токен выпущен не позже секунды `tokens_revoked_at`.
+    fn issued_before_logout(user: &User, claims: &TokenClaimsDto) -> bool {
+        // `iat` хранится в целых секундах, поэтому токен, выпущенный в ту же секунду,
+        // что и выход, мог быть выпущен и до него и тоже считается отозванным
+        user.tokens_revoked_at
+            .is_some_and(|revoked_at| claims.iat <= revoked_at.timestamp())
+    }
+
+    /// Сохранение результата проверки в кэше.
+    ///
+    /// :param jti: идентификатор токена.
+    /// :param exp: срок действия токена (Unix timestamp).
+    /// :param revoked: токен отозван.
+    fn remember(&self, jti: Uuid, exp: i64, revoked: bool) {
+        let ttl = match revoked {
+            true => Duration::from_secs((exp - Utc::now().timestamp()).max(0) as u64),
+            false => self.cache_ttl,
+        };
+        self.cache.write().unwrap().insert(jti, revoked, ttl, Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn user(tokens_revoked_at: Option<DateTime<Utc>>) -> User {
+        serde_json::from_value(json!({
+            "id": 1,
+            "user_name": "user",
+            "email": "user@example.com",
+            "password": "hash",
+            "created_at": "2025-01-01T00:00:00",
+            "is_active": 1,
+            "tokens_revoked_at": tokens_revoked_at,
+        }))
+        .unwrap()
+    }
+
+    fn claims(iat: i64) -> TokenClaimsDto {
+        TokenClaimsDto {
+            sub: 1,
+            email: "user@example.com".to_string(),
+            iat,
+            exp: iat + 3600,
+            jti: Uuid::new_v4(),
+            sid: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn cache_returns_fresh_entries_only() {
+        let mut cache = RevocationCache::default();
+        let now = Instant::now();
+        let jti = Uuid::new_v4();
+
+        cache.insert(jti, true, Duration::from_secs(30), now);
+        assert_eq!(cache.get(&jti, now), Some(true));
+        assert_eq!(cache.get(&jti, now + Duration::from_secs(29)), Some(true));
+        assert_eq!(cache.get(&jti, now + Duration::from_secs(30)), None);
+        assert_eq!(cache.get(&Uuid::new_v4(), now), None);
+    }
+
+    #[test]
+    fn cache_skips_zero_ttl() {
+        let mut cache = RevocationCache::default();
+        let jti = Uuid::new_v4();
+
+        cache.insert(jti, false, Duration::ZERO, Instant::now());
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn cache_drops_stale_entries_when_full() {
+        let mut cache = RevocationCache::default();
+        let now = Instant::now();
+        let kept = Uuid::new_v4();
+
+        cache.insert(kept, true, Duration::from_secs(3600), now);
+        for _ in 1..MAX_CACHE_ENTRIES {
+            cache.insert(Uuid::new_v4(), false, Duration::from_secs(1), now);
+        }
+        assert_eq!(cache.entries.len(), MAX_CACHE_ENTRIES);
+
+        let later = now + Duration::from_secs(2);
+        let jti = Uuid::new_v4();
+        cache.insert(jti, false, Duration::from_secs(30), later);
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.get(&kept, later), Some(true));
+        assert_eq!(cache.get(&jti, later), Some(false));
+    }
+
+    #[test]
+    fn cache_is_cleared_when_full_of_fresh_entries() {
+        let mut cache = RevocationCache::default();
+        let now = Instant::now();
+
+        for _ in 0..MAX_CACHE_ENTRIES {
+            cache.insert(Uuid::new_v4(), false, Duration::from_secs(30), now);
+        }
+        let jti = Uuid::new_v4();
+        cache.insert(jti, true, Duration::from_secs(30), now);
+        assert_eq!(cache.entries.len(), 1);
+        assert_eq!(cache.get(&jti, now), Some(true));
+    }
+
+    #[test]
+    fn tokens_issued_before_logout_are_revoked() {
+        let logout = DateTime::<Utc>::from_timestamp(1_700_000_000, 500_000_000).unwrap();
+
+        assert!(!TokenRevocationService::issued_before_logout(&user(None), &claims(1_699_999_000)));
+        assert!(TokenRevocationService::issued_before_logout(&user(Some(logout)), &claims(1_699_999_999)));
+        assert!(TokenRevocationService::issued_before_logout(&user(Some(logout)), &claims(1_700_000_000)));
+        assert!(!TokenRevocationService::issued_before_logout(&user(Some(logout)), &claims(1_700_000_001)));
+    }
+}
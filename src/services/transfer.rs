@@ -0,0 +1,564 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::dto::transfer::{TaskExportDto, TaskImportDto, TaskImportErrorDto, TaskImportResultDto, TransferFormat};
+use crate::entities::task::{Task, TaskStatus};
+use crate::entities::user::User;
+use crate::errors::api::ApiError;
+use crate::errors::db::DbError;
+use crate::errors::request::{RequestError, ValidatedRequest};
+use crate::errors::transfer::TransferError;
+use crate::repositories::task::{TaskRepository, TaskRepositoryTrait};
+use crate::services::ical;
+use crate::services::task::TaskService;
+use bytes::Bytes;
+use futures::stream::{self, Stream};
+use serde_json::{json, Map, Value};
+use sqlx::{Connection, PgConnection};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Количество задач, читаемых из базы за один шаг экспорта.
+const EXPORT_BATCH_SIZE: i64 = 500;
+
+/// Максимальное количество строк в файле импорта.
+const MAX_IMPORT_ROWS: usize = 5000;
+
+/// Поля `TaskImportDto`, которые читаются из строки импорта (и статус).
+const IMPORT_FIELDS: [&str; 8] = [
+    "title",
+    "description",
+    "due_at",
+    "priority",
+    "estimated_minutes",
+    "recurrence",
+    "project_id",
+    "status",
+];
+
+/// Колонки CSV-экспорта (в порядке полей `TaskExportDto`).
+const CSV_COLUMNS: [&str; 12] = [
+    "id",
+    "title",
+    "description",
+    "status",
+    "priority",
+    "due_at",
+    "estimated_minutes",
+    "recurrence",
+    "parent_id",
+    "project_id",
+    "created_at",
+    "completed_at",
+];
+
+/// Числовые поля строки импорта (в CSV приходят строками).
+const NUMERIC_FIELDS: [&str; 2] = ["estimated_minutes", "project_id"];
+
+/// Строка файла импорта, приведённая к полям `TaskImportDto`.
+///
+/// - `row` — номер строки файла (для iCalendar — порядковый номер `VTODO`).
+/// - `key` — идентификатор задачи в файле (`id` или `UID`).
+/// - `parent_key` — идентификатор родителя в файле (`parent_id` или `RELATED-TO`).
+/// - `fields` — поля задачи или HTTP-код и текст ошибки разбора строки.
+struct ImportRow {
+    row: usize,
+    key: Option<String>,
+    parent_key: Option<String>,
+    fields: Result<Map<String, Value>, (u16, String)>,
+}
+
+/// Состояние потока экспорта.
+enum ExportStep {
+    Start,
+    Batch(Option<i32>),
+    Done,
+}
+
+/// Сервис импорта и экспорта задач (`TransferService`).
+///
+/// Экспорт отдаётся потоком порциями по `EXPORT_BATCH_SIZE` задач, поэтому
+/// размер файла не ограничен памятью сервера. Импорт проверяет каждую строку
+/// по правилам `TaskImportDto` и создаёт задачи в одной транзакции; строки
+/// с ошибками пропускаются и попадают в отчёт.
+#[derive(Clone)]
+pub struct TransferService {
+    /// `task_repo` — репозиторий задач.
+    task_repo: TaskRepository,
+
+    /// `task_service` — создание задач и проверка прав доступа.
+    task_service: TaskService,
+
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+}
+
+impl TransferService {
+    /// Создание нового экземпляра `TransferService`.
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            task_repo: TaskRepository::new(db_conn),
+            task_service: TaskService::new(db_conn),
+            db_conn: Arc::clone(db_conn),
+        }
+    }
+
+    /// Экспорт задач пользователя потоком.
+    ///
+    /// Выгружаются все задачи, доступные пользователю (личные и задачи его
+    /// проектов), кроме задач в корзине, по возрастанию `id`.
+    ///
+    /// :param user: авторизованный пользователь.
+    /// :param format: формат файла.
+    /// :return: поток частей файла; ошибка базы данных прерывает поток.
+    pub fn export_tasks(
+        &self,
+        user: &User,
+        format: TransferFormat,
+    ) -> impl Stream<Item = Result<Bytes, DbError>> + Send + 'static {
+        let task_repo = self.task_repo.clone();
+        let user_id = user.id;
+
+        stream::unfold(ExportStep::Start, move |step| {
+            let task_repo = task_repo.clone();
+            async move {
+                match step {
+                    ExportStep::Start => Some((Ok(Bytes::from(Self::export_start(format))), ExportStep::Batch(None))),
+                    ExportStep::Batch(after_id) => {
+                        match task_repo.find_export_batch(user_id, after_id, EXPORT_BATCH_SIZE).await {
+                            Ok(tasks) if tasks.is_empty() => {
+                                Some((Ok(Bytes::from(Self::export_end(format))), ExportStep::Done))
+                            }
+                            Ok(tasks) => {
+                                let last_id = tasks.last().map(|task| task.id);
+                                Some((Self::export_batch(format, tasks), ExportStep::Batch(last_id)))
+                            }
+                            Err(e) => Some((Err(DbError::from(e)), ExportStep::Done)),
+                        }
+                    }
+                    ExportStep::Done => None,
+                }
+            }
+        })
+    }
+
+    /// Импорт задач из файла.
+    ///
+    /// Каждая строка создаётся в собственной точке сохранения (savepoint):
+    /// ошибка строки отменяет только её. Подзадачи создаются после родителей
+    /// независимо от порядка строк в файле.
+    ///
+    /// :param user: авторизованный пользователь (владелец новых задач).
+    /// :param format: формат файла.
+    /// :param body: содержимое файла.
+    /// :return: отчёт импорта, `InvalidFile`, `TooManyRows` или ошибка базы данных.
+    pub async fn import_tasks(
+        &self,
+        user: &User,
+        format: TransferFormat,
+        body: Bytes,
+    ) -> Result<TaskImportResultDto, ApiError> {
+        let text = std::str::from_utf8(&body)
+            .map_err(|_| TransferError::InvalidFile("file must be UTF-8 encoded".to_string()))?;
+        let rows = match format {
+            TransferFormat::Csv => Self::parse_csv(text)?,
+            TransferFormat::Jsonl => Self::parse_jsonl(text),
+            TransferFormat::Ics => Self::parse_ics(text)?,
+        };
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(TransferError::TooManyRows(MAX_IMPORT_ROWS).into());
+        }
+
+        let keys: HashSet<String> = rows.iter().filter_map(|row| row.key.clone()).collect();
+        let mut imported: HashMap<String, i32> = HashMap::new();
+        let mut failed: HashSet<String> = HashSet::new();
+        let mut task_ids = Vec::new();
+        let mut errors = Vec::new();
+
+        let mut tx = self.db_conn.get_pool().begin().await.map_err(DbError::from)?;
+        let mut pending = rows;
+        // Строки, чей родитель из того же файла ещё не обработан, откладываются до следующего прохода
+        while !pending.is_empty() {
+            let count = pending.len();
+            let mut deferred = Vec::new();
+            for row in pending {
+                let waiting = row.parent_key.as_ref().is_some_and(|parent_key| {
+                    keys.contains(parent_key) && !imported.contains_key(parent_key) && !failed.contains(parent_key)
+                });
+                if waiting {
+                    deferred.push(row);
+                    continue;
+                }
+
+                let key = row.key.clone();
+                let number = row.row;
+                let mut savepoint = Connection::begin(&mut *tx).await.map_err(DbError::from)?;
+                match self.import_row(&mut savepoint, user, row, &imported).await {
+                    Ok(task_id) => {
+                        savepoint.commit().await.map_err(DbError::from)?;
+                        task_ids.push(task_id);
+                        if let Some(key) = key {
+                            imported.insert(key, task_id);
+                        }
+                    }
+                    Err((code, message)) => {
+                        savepoint.rollback().await.map_err(DbError::from)?;
+                        errors.push(TaskImportErrorDto { row: number, code, message });
+                        failed.extend(key);
+                    }
+                }
+            }
+
+            // Цикл ссылок на родителей: ни одна отложенная строка не может быть создана
+            if deferred.len() == count {
+                for row in deferred {
+                    errors.push(TaskImportErrorDto {
+                        row: row.row,
+                        code: 422,
+                        message: "parent reference forms a cycle".to_string(),
+                    });
+                }
+                break;
+            }
+            pending = deferred;
+        }
+
+        tx.commit().await.map_err(DbError::from)?;
+
+        errors.sort_by_key(|error| error.row);
+        Ok(TaskImportResultDto {
+            imported: task_ids.len(),
+            task_ids,
+            errors,
+        })
+    }
+
+    /// Создание задачи из строки импорта.
+    ///
+    /// Поля проверяются как `TaskImportDto` (срок может быть в прошлом); статус,
+    /// отличный от `todo`, устанавливается обычным переходом: с проверкой правил
+    /// переходов, записью в историю и созданием следующего вхождения серии.
+    ///
+    /// :return: идентификатор созданной задачи или HTTP-код и текст ошибки строки.
+    async fn import_row(
+        &self,
+        conn: &mut PgConnection,
+        user: &User,
+        row: ImportRow,
+        imported: &HashMap<String, i32>,
+    ) -> Result<i32, (u16, String)> {
+        let mut fields = row.fields?;
+        if let Some(parent_key) = row.parent_key {
+            let parent_id = imported
+                .get(&parent_key)
+                .ok_or_else(|| (422, format!("parent task `{}` was not imported", parent_key)))?;
+            fields.insert("parent_id".to_string(), json!(parent_id));
+        }
+        let status = Self::take_status(&mut fields)?;
+        let ValidatedRequest(payload) = ValidatedRequest::<TaskImportDto>::from_value(Value::Object(fields))
+            .map_err(TaskService::error_report)?;
+
+        let task = self
+            .task_service
+            .apply_create(conn, user, payload.into_create_dto())
+            .await
+            .map_err(TaskService::error_report)?;
+        if let Some(status) = status.filter(|status| *status != task.status) {
+            self.task_service
+                .apply_transition(conn, user, task.id, status)
+                .await
+                .map_err(TaskService::error_report)?;
+        }
+
+        Ok(task.id)
+    }
+
+    /// Извлечение статуса из полей строки импорта.
+    ///
+    /// Статус не входит в `TaskImportDto`: задача создаётся в начальном статусе,
+    /// а затем переводится в указанный через правила переходов.
+    ///
+    /// :param fields: поля строки (статус из них удаляется).
+    /// :return: статус (`None`, если не указан) или HTTP-код и текст ошибки.
+    fn take_status(fields: &mut Map<String, Value>) -> Result<Option<TaskStatus>, (u16, String)> {
+        fields
+            .remove("status")
+            .filter(|status| !status.is_null())
+            .map(serde_json::from_value::<TaskStatus>)
+            .transpose()
+            .map_err(|e| TaskService::error_report(RequestError::from(e)))
+    }
+
+    /// Начало файла экспорта (заголовок CSV или начало календаря).
+    fn export_start(format: TransferFormat) -> String {
+        match format {
+            TransferFormat::Csv => format!("{}\r\n", CSV_COLUMNS.join(",")),
+            TransferFormat::Jsonl => String::new(),
+            TransferFormat::Ics => ical::calendar_start("Tasks"),
+        }
+    }
+
+    /// Конец файла экспорта.
+    fn export_end(format: TransferFormat) -> String {
+        match format {
+            TransferFormat::Csv | TransferFormat::Jsonl => String::new(),
+            TransferFormat::Ics => ical::CALENDAR_END.to_string(),
+        }
+    }
+
+    /// Порция задач в формате экспорта.
+    fn export_batch(format: TransferFormat, tasks: Vec<Task>) -> Result<Bytes, DbError> {
+        let encoding_error = |e: &dyn std::fmt::Display| DbError::SomethingWentWrong(format!("export failed: {}", e));
+
+        match format {
+            TransferFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(false)
+                    .terminator(csv::Terminator::CRLF)
+                    .from_writer(Vec::new());
+                for task in tasks {
+                    writer
+                        .serialize(TaskExportDto::from(task))
+                        .map_err(|e| encoding_error(&e))?;
+                }
+                let bytes = writer.into_inner().map_err(|e| encoding_error(&e))?;
+                Ok(Bytes::from(bytes))
+            }
+            TransferFormat::Jsonl => {
+                let mut bytes = Vec::new();
+                for task in tasks {
+                    serde_json::to_writer(&mut bytes, &TaskExportDto::from(task)).map_err(|e| encoding_error(&e))?;
+                    bytes.push(b'\n');
+                }
+                Ok(Bytes::from(bytes))
+            }
+            TransferFormat::Ics => Ok(Bytes::from(tasks.iter().map(ical::vtodo).collect::<String>())),
+        }
+    }
+
+    /// Разбор CSV: первая строка — заголовок, колонка `title` обязательна.
+    fn parse_csv(text: &str) -> Result<Vec<ImportRow>, TransferError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+        let headers: Vec<String> = reader
+            .headers()
+            .map_err(|e| TransferError::InvalidFile(e.to_string()))?
+            .iter()
+            .map(|header| header.to_ascii_lowercase())
+            .collect();
+        if !headers.iter().any(|header| header == "title") {
+            return Err(TransferError::InvalidFile("missing `title` column".to_string()));
+        }
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    let row = e.position().map_or(rows.len() + 2, |position| position.line() as usize);
+                    rows.push(ImportRow {
+                        row,
+                        key: None,
+                        parent_key: None,
+                        fields: Err((400, format!("Invalid CSV: {}", e))),
+                    });
+                    continue;
+                }
+            };
+            let row = record.position().map_or(rows.len() + 2, |position| position.line() as usize);
+
+            let mut key = None;
+            let mut parent_key = None;
+            let mut fields = Map::new();
+            for (header, value) in headers.iter().zip(record.iter()) {
+                if value.is_empty() {
+                    continue;
+                }
+                match header.as_str() {
+                    "id" => key = Some(value.to_string()),
+                    "parent_id" => parent_key = Some(value.to_string()),
+                    field if NUMERIC_FIELDS.contains(&field) => {
+                        let number = value.parse::<i64>().map(Value::from).unwrap_or_else(|_| json!(value));
+                        fields.insert(field.to_string(), number);
+                    }
+                    field if IMPORT_FIELDS.contains(&field) => {
+                        fields.insert(field.to_string(), json!(value));
+                    }
+                    _ => {}
+                }
+            }
+            rows.push(ImportRow {
+                row,
+                key,
+                parent_key,
+                fields: Ok(fields),
+            });
+        }
+
+        Ok(rows)
+    }
+
+    /// Разбор JSON Lines: каждая непустая строка — объект с полями `TaskExportDto`.
+    fn parse_jsonl(text: &str) -> Vec<ImportRow> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let object = serde_json::from_str::<Map<String, Value>>(line)
+                    .map_err(|e| TaskService::error_report(RequestError::from(e)));
+                let key_of = |object: &Map<String, Value>, field: &str| match object.get(field) {
+                    Some(Value::Number(number)) => Some(number.to_string()),
+                    Some(Value::String(value)) => Some(value.clone()),
+                    _ => None,
+                };
+                let (key, parent_key) = match &object {
+                    Ok(object) => (key_of(object, "id"), key_of(object, "parent_id")),
+                    Err(_) => (None, None),
+                };
+                let fields = object.map(|object| {
+                    object
+                        .into_iter()
+                        .filter(|(field, value)| IMPORT_FIELDS.contains(&field.as_str()) && !value.is_null())
+                        .collect()
+                });
+
+                ImportRow {
+                    row: index + 1,
+                    key,
+                    parent_key,
+                    fields,
+                }
+            })
+            .collect()
+    }
+
+    /// Разбор iCalendar: каждая задача — компонент `VTODO`.
+    fn parse_ics(text: &str) -> Result<Vec<ImportRow>, TransferError> {
+        let todos = ical::parse_todos(text).map_err(TransferError::InvalidFile)?;
+
+        Ok(todos
+            .into_iter()
+            .map(|todo| {
+                let mut fields = Map::new();
+                if let Some(summary) = todo.get("SUMMARY") {
+                    fields.insert("title".to_string(), json!(summary.text()));
+                }
+                if let Some(description) = todo.get("DESCRIPTION") {
+                    fields.insert("description".to_string(), json!(description.text()));
+                }
+                if let Some(due) = todo.get("DUE") {
+                    // Неразбираемая дата передаётся как есть и попадает в отчёт как ошибка строки
+                    let due_at = due.date_time().map_or_else(|| json!(due.value), |due_at| json!(due_at));
+                    fields.insert("due_at".to_string(), due_at);
+                }
+                if let Some(priority) = ical::priority_from_ical(&todo) {
+                    fields.insert("priority".to_string(), json!(priority));
+                }
+                if let Some(status) = ical::status_from_ical(&todo) {
+                    fields.insert("status".to_string(), json!(status));
+                }
+                if let Some(rule) = todo.get("RRULE") {
+                    fields.insert("recurrence".to_string(), json!(rule.value));
+                }
+                if let Some(minutes) = todo.get("X-ESTIMATED-MINUTES") {
+                    let number = minutes.value.parse::<i64>().map(Value::from).unwrap_or_else(|_| json!(minutes.value));
+                    fields.insert("estimated_minutes".to_string(), number);
+                }
+                // RELATED-TO без RELTYPE по умолчанию указывает на родителя
+                let parent_key = todo
+                    .properties
+                    .iter()
+                    .find(|property| {
+                        property.name == "RELATED-TO"
+                            && property.param("RELTYPE").is_none_or(|reltype| reltype.eq_ignore_ascii_case("PARENT"))
+                    })
+                    .map(|property| property.value.clone());
+
+                ImportRow {
+                    row: todo.index,
+                    key: todo.get("UID").map(|uid| uid.value.clone()),
+                    parent_key,
+                    fields: Ok(fields),
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    fn fields(row: &ImportRow) -> Map<String, Value> {
+        row.fields.clone().unwrap()
+    }
+
+    #[test]
+    fn takes_status_out_of_import_fields() {
+        let mut row = json!({ "title": "Task", "status": "in_progress" }).as_object().unwrap().clone();
+        assert_eq!(TransferService::take_status(&mut row), Ok(Some(TaskStatus::InProgress)));
+        assert!(!row.contains_key("status"));
+
+        let mut row = json!({ "title": "Task", "status": null }).as_object().unwrap().clone();
+        assert_eq!(TransferService::take_status(&mut row), Ok(None));
+
+        let mut row = json!({ "title": "Task", "status": "finished" }).as_object().unwrap().clone();
+        let (code, _) = TransferService::take_status(&mut row).unwrap_err();
+        assert_eq!(code, 400);
+    }
+
+    #[test]
+    fn csv_import_keeps_past_due_date_and_status() {
+        let rows = TransferService::parse_csv(
+            "id,title,status,due_at,estimated_minutes,parent_id\r\n\
+             1,Overdue report,done,2020-01-01T09:00:00Z,45,\r\n\
+             2,Follow up,,,,1\r\n",
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let mut first = fields(&rows[0]);
+        assert_eq!(TransferService::take_status(&mut first), Ok(Some(TaskStatus::Done)));
+        assert_eq!(first.get("estimated_minutes"), Some(&json!(45)));
+        let dto: TaskImportDto = serde_json::from_value(Value::Object(first)).unwrap();
+        assert!(dto.validate().is_ok());
+        assert_eq!(dto.into_create_dto().due_at.unwrap().to_rfc3339(), "2020-01-01T09:00:00+00:00");
+
+        assert_eq!(rows[1].key.as_deref(), Some("2"));
+        assert_eq!(rows[1].parent_key.as_deref(), Some("1"));
+        assert!(!fields(&rows[1]).contains_key("status"));
+    }
+
+    #[test]
+    fn ics_import_maps_status_and_priority() {
+        let rows = TransferService::parse_ics(
+            "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:a\r\nSUMMARY:Done long ago\r\n\
+             DUE:20200101T090000Z\r\nSTATUS:COMPLETED\r\nPRIORITY:1\r\nEND:VTODO\r\n\
+             BEGIN:VTODO\r\nUID:b\r\nSUMMARY:Cancelled\r\nSTATUS:CANCELLED\r\n\
+             RELATED-TO:a\r\nEND:VTODO\r\nEND:VCALENDAR\r\n",
+        )
+        .unwrap();
+
+        let mut first = fields(&rows[0]);
+        assert_eq!(TransferService::take_status(&mut first), Ok(Some(TaskStatus::Done)));
+        assert_eq!(first.get("due_at"), Some(&json!("2020-01-01T09:00:00Z")));
+        assert_eq!(first.get("priority"), Some(&json!("urgent")));
+
+        let mut second = fields(&rows[1]);
+        assert_eq!(TransferService::take_status(&mut second), Ok(Some(TaskStatus::Cancelled)));
+        assert_eq!(rows[1].parent_key.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn jsonl_import_reports_malformed_lines() {
+        let text = [r#"{"id": 7, "title": "Task", "status": "todo"}"#, "", "not json"].join("\n");
+        let rows = TransferService::parse_jsonl(&text);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].key.as_deref(), Some("7"));
+        assert_eq!(fields(&rows[0]).get("status"), Some(&json!("todo")));
+        assert_eq!(rows[1].row, 3);
+        assert_eq!(rows[1].fields.as_ref().unwrap_err().0, 400);
+    }
+}
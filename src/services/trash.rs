@@ -0,0 +1,155 @@
+use crate::db::db::{Database, DatabaseTrait};
+use crate::errors::db::DbError;
+use crate::services::history;
+use crate::settings::settings;
+use crate::storage::storage::{self as file_storage, FileStorage};
+use chrono::{Duration, SubsecRound, Utc};
+use serde_json::json;
+use sqlx::PgConnection;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use tokio::task::JoinHandle;
+
+/// Срок хранения задач в корзине по умолчанию (в днях).
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// Период запуска фоновой очистки по умолчанию (в минутах).
+const DEFAULT_INTERVAL_MINUTES: u64 = 60;
+
+/// Сервис корзины задач (`TaskTrashService`).
+///
+/// Окончательно удаляет задачи, пролежавшие в корзине дольше
+/// `TRASH_RETENTION_DAYS`, вместе с их комментариями и файлами вложений.
+#[derive(Clone)]
+pub struct TaskTrashService {
+    /// `db_conn` — подключение к базе данных.
+    db_conn: Arc<Database>,
+
+    /// `storage` — хранилище файлов вложений.
+    storage: Arc<dyn FileStorage>,
+
+    /// `retention` — срок хранения задач в корзине.
+    retention: Duration,
+
+    /// `interval` — период запуска фоновой очистки.
+    interval: StdDuration,
+}
+
+impl TaskTrashService {
+    /// Создание нового экземпляра `TaskTrashService`.
+    ///
+    /// Срок хранения и период берутся из `TRASH_RETENTION_DAYS` (по умолчанию 30)
+    /// и `TRASH_PURGE_INTERVAL_MINUTES` (по умолчанию 60).
+    ///
+    /// :param db_conn: Подключение к базе данных (`Arc<Database>`).
+    ///
+    /// # Паника
+    /// Если переменные заданы, но не являются положительными числами.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        let retention_days = settings::get_positive_or("TRASH_RETENTION_DAYS", DEFAULT_RETENTION_DAYS);
+        let interval_minutes = settings::get_positive_or("TRASH_PURGE_INTERVAL_MINUTES", DEFAULT_INTERVAL_MINUTES);
+
+        Self {
+            db_conn: Arc::clone(db_conn),
+            storage: file_storage::from_settings(),
+            retention: Duration::days(retention_days),
+            interval: StdDuration::from_secs(interval_minutes * 60),
+        }
+    }
+
+    /// Запуск фоновой очистки корзины.
+    ///
+    /// Первый проход выполняется сразу, затем — каждые `interval`.
+    /// Ошибки прохода логируются и не останавливают цикл.
+    ///
+    /// :return: дескриптор фоновой задачи tokio.
+    pub fn spawn_purger(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            loop {
+                ticker.tick().await;
+                match self.purge_expired().await {
+                    Ok(0) => {}
+                    Ok(purged) => tracing::info!("trash purger removed {} task(s)", purged),
+                    Err(e) => tracing::error!("trash purger failed: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Перемещение задач в корзину вместе с подзадачами.
+    ///
+    /// Задача и её подзадачи получают одну метку времени `deleted_at`, по которой
+    /// восстанавливаются вместе; удаление записывается в историю.
+    ///
+    /// :param conn: соединение (обычно внутри транзакции).
+    /// :param ids: идентификаторы удаляемых задач (уже удалённые пропускаются).
+    /// :param actor_id: пользователь, удаливший задачи (`None` — система).
+    /// :return: `()` или ошибка (`DbError`).
+    pub(crate) async fn move_to_trash(conn: &mut PgConnection, ids: &[i32], actor_id: Option<i32>) -> Result<(), DbError> {
+        let deleted_at = Utc::now().naive_utc().trunc_subsecs(6);
+        let deleted = sqlx::query_scalar::<_, i32>(
+            r#"
+            WITH RECURSIVE subtree AS (
+                SELECT id FROM tasks WHERE id = ANY($1) AND deleted_at IS NULL
+                UNION
+                SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id WHERE t.deleted_at IS NULL
+            )
+            UPDATE tasks SET deleted_at = $2
+            WHERE id IN (SELECT id FROM subtree)
+            RETURNING id
+            "#,
+        )
+            .bind(ids)
+            .bind(deleted_at)
+            .fetch_all(&mut *conn)
+            .await?;
+        for task_id in deleted {
+            let changes = json!({ "deleted_at": { "from": null, "to": deleted_at } });
+            history::record(&mut *conn, task_id, actor_id, "deleted", changes).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Окончательное удаление задач с истёкшим сроком хранения.
+    ///
+    /// Подзадачи, комментарии и записи вложений удаляются каскадно. Файлы
+    /// вложений удаляются из хранилища после фиксации транзакции; ошибки
+    /// хранилища только логируются.
+    ///
+    /// :return: количество удалённых задач (без подзадач) или ошибка (`DbError`).
+    pub async fn purge_expired(&self) -> Result<usize, DbError> {
+        let cutoff = (Utc::now() - self.retention).naive_utc();
+        let mut tx = self.db_conn.get_pool().begin().await?;
+
+        let storage_keys = sqlx::query_scalar::<_, String>(
+            r#"
+            WITH RECURSIVE subtree AS (
+                SELECT id FROM tasks WHERE deleted_at < $1
+                UNION
+                SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+            )
+            SELECT storage_key FROM task_attachments WHERE task_id IN (SELECT id FROM subtree)
+            "#,
+        )
+            .bind(cutoff)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let purged = sqlx::query("DELETE FROM tasks WHERE deleted_at < $1")
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        for key in storage_keys {
+            if let Err(e) = self.storage.delete(&key).await {
+                tracing::warn!("failed to delete attachment `{}` of purged task: {}", key, e);
+            }
+        }
+
+        Ok(purged.rows_affected() as usize)
+    }
+}
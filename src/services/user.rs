@@ -36,7 +36,7 @@ impl UserService {
     ///
     /// - Проверяет наличие пользователя по email.
     /// - Хеширует пароль.
-    /// - Сохраняет пользователя в базу данных.
+    /// - Сохраняет пользователя в базу данных неактивным (до подтверждения email).
     ///
     /// :param payload: данные регистрации пользователя.
     /// :return: DTO созданного пользователя или ошибка (`ApiError`).
@@ -64,26 +64,27 @@ impl UserService {
 
     /// Добавление нового пользователя в базу данных.
     ///
-    /// Выполняет SQL-запрос с `RETURNING *`, возвращает созданного пользователя.
+    /// Выполняет SQL-запрос с `RETURNING *`, возвращает созданного пользователя
+    /// с `is_active = 0`.
     ///
     /// :param payload: регистрационные данные.
     /// :return: модель пользователя из базы.
     async fn add_user(&self, payload: UserRegisterDto) -> Result<User, SqlxError> {
-        let hashed_password = bcrypt::hash(payload.password, 4).unwrap();
+        let hashed_password = Self::hash_password(&payload.password);
 
         let user = query_as!(
             User,
             r#"
             INSERT INTO users (first_name, last_name, user_name, email, password, is_active)
             VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, first_name, last_name, user_name, email, password, created_at, updated_at, is_active
+            RETURNING id, first_name, last_name, user_name, email, password, created_at, updated_at, is_active, tokens_revoked_at
             "#,
             payload.first_name,
             payload.last_name,
             payload.user_name,
             payload.email,
             hashed_password,
-            1i32
+            0i32
         )
             .fetch_one(self.db_conn.get_pool())
             .await?;
@@ -91,6 +92,14 @@ impl UserService {
         Ok(user)
     }
 
+    /// Хеширование пароля (bcrypt).
+    ///
+    /// :param password: пароль в открытом виде.
+    /// :return: хеш для поля `users.password`.
+    pub(crate) fn hash_password(password: &str) -> String {
+        bcrypt::hash(password, 4).unwrap()
+    }
+
     /// Проверка пароля пользователя.
     ///
     /// :param user: модель пользователя из базы.
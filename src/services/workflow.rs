@@ -0,0 +1,153 @@
+use crate::entities::task::TaskStatus;
+use crate::errors::task::TaskError;
+use crate::settings::settings;
+use std::collections::{HashMap, HashSet};
+
+/// Машина состояний статусов задачи (`TaskWorkflow`).
+///
+/// Хранит таблицу разрешённых переходов `из статуса → в статусы`.
+/// По умолчанию используются правила `TaskWorkflow::default()`, их можно
+/// переопределить переменной окружения `TASK_TRANSITIONS` в формате
+/// `todo:in_progress|done;in_progress:todo|done;done:todo`.
+#[derive(Clone, Debug)]
+pub struct TaskWorkflow {
+    transitions: HashMap<TaskStatus, HashSet<TaskStatus>>,
+}
+
+impl TaskWorkflow {
+    /// Создание машины состояний из настроек окружения.
+    ///
+    /// # Паника
+    /// Если `TASK_TRANSITIONS` задана, но не разбирается.
+    pub fn from_settings() -> Self {
+        match settings::get_optional("TASK_TRANSITIONS") {
+            Some(rules) => Self::parse(&rules)
+                .unwrap_or_else(|e| panic!("TASK_TRANSITIONS is invalid: {}", e)),
+            None => Self::default(),
+        }
+    }
+
+    /// Разбор правил переходов из строки `from:to|to;from:to`.
+    ///
+    /// :param rules: строка с правилами.
+    /// :return: машина состояний или описание ошибки.
+    pub fn parse(rules: &str) -> Result<Self, String> {
+        let mut transitions: HashMap<TaskStatus, HashSet<TaskStatus>> = HashMap::new();
+
+        for rule in rules.split(';').map(str::trim).filter(|rule| !rule.is_empty()) {
+            let (from, targets) = rule
+                .split_once(':')
+                .ok_or_else(|| format!("rule `{}` must look like `from:to|to`", rule))?;
+            let from = from.trim().parse::<TaskStatus>()?;
+            let targets = targets
+                .split('|')
+                .map(|target| target.trim().parse::<TaskStatus>())
+                .collect::<Result<HashSet<_>, _>>()?;
+
+            transitions.entry(from).or_default().extend(targets);
+        }
+
+        Ok(Self { transitions })
+    }
+
+    /// Проверка допустимости перехода.
+    ///
+    /// :param from: текущий статус задачи.
+    /// :param to: запрошенный статус.
+    /// :return: `()` либо `TaskError::InvalidStatusTransition`.
+    pub fn check(&self, from: TaskStatus, to: TaskStatus) -> Result<(), TaskError> {
+        let allowed = self
+            .transitions
+            .get(&from)
+            .is_some_and(|targets| targets.contains(&to));
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(TaskError::InvalidStatusTransition { from, to })
+        }
+    }
+}
+
+impl Default for TaskWorkflow {
+    /// Правила переходов по умолчанию.
+    ///
+    /// - `todo` → `in_progress`, `blocked`, `done`, `cancelled`
+    /// - `in_progress` → `todo`, `blocked`, `done`, `cancelled`
+    /// - `blocked` → `todo`, `in_progress`, `cancelled`
+    /// - `done` → `todo`, `in_progress` (повторное открытие)
+    /// - `cancelled` → `todo`
+    fn default() -> Self {
+        Self::parse(
+            "todo:in_progress|blocked|done|cancelled;\
+             in_progress:todo|blocked|done|cancelled;\
+             blocked:todo|in_progress|cancelled;\
+             done:todo|in_progress;\
+             cancelled:todo",
+        )
+        .expect("default task transitions are valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TaskStatus::*;
+
+    fn allowed(workflow: &TaskWorkflow, from: TaskStatus, to: TaskStatus) -> bool {
+        workflow.check(from, to).is_ok()
+    }
+
+    #[test]
+    fn default_rules() {
+        let workflow = TaskWorkflow::default();
+
+        assert!(allowed(&workflow, Todo, InProgress));
+        assert!(allowed(&workflow, InProgress, Done));
+        assert!(allowed(&workflow, Done, Todo));
+        assert!(allowed(&workflow, Blocked, Cancelled));
+        assert!(allowed(&workflow, Cancelled, Todo));
+
+        assert!(!allowed(&workflow, Blocked, Done));
+        assert!(!allowed(&workflow, Done, Cancelled));
+        assert!(!allowed(&workflow, Cancelled, Done));
+        assert!(!allowed(&workflow, Todo, Todo));
+    }
+
+    #[test]
+    fn rejected_transition_names_both_statuses() {
+        let error = TaskWorkflow::default().check(Cancelled, Done).unwrap_err();
+
+        assert!(matches!(error, TaskError::InvalidStatusTransition { from: Cancelled, to: Done }));
+    }
+
+    #[test]
+    fn parses_custom_rules() {
+        let workflow = TaskWorkflow::parse(" todo : in_progress | done ; done:todo;; todo:blocked ").unwrap();
+
+        assert!(allowed(&workflow, Todo, InProgress));
+        assert!(allowed(&workflow, Todo, Done));
+        assert!(allowed(&workflow, Todo, Blocked));
+        assert!(allowed(&workflow, Done, Todo));
+        assert!(!allowed(&workflow, InProgress, Done));
+        assert!(!allowed(&workflow, Done, InProgress));
+    }
+
+    #[test]
+    fn empty_rules_forbid_everything() {
+        let workflow = TaskWorkflow::parse("").unwrap();
+
+        assert!(!allowed(&workflow, Todo, InProgress));
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        assert!(TaskWorkflow::parse("todo").is_err());
+        assert!(TaskWorkflow::parse("todo:").is_err());
+        assert!(TaskWorkflow::parse("todo:finished").is_err());
+        assert!(TaskWorkflow::parse("Todo:done").is_err());
+        assert!(TaskWorkflow::parse("todo:done|").is_err());
+        assert!(TaskWorkflow::parse("сделать:done").is_err());
+        assert!(TaskWorkflow::parse("todo：done").is_err());
+    }
+}
@@ -1 +1,2 @@
+#[allow(clippy::module_inception)]
 pub mod settings;
\ No newline at end of file
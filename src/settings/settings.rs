@@ -1,4 +1,5 @@
 use dotenv;
+use std::str::FromStr;
 
 /// Инициализация переменных окружения из файла `.env`.
 ///
@@ -10,7 +11,7 @@ use dotenv;
 /// # Паника
 /// При ошибке чтения `.env` файла будет вызван `expect(...)`.
 pub fn init() {
-    dotenv::dotenv().ok().expect("Failed to load .env file");
+    dotenv::dotenv().expect("Failed to load .env file");
 }
 
 /// Получение значения переменной окружения по имени.
@@ -29,7 +30,117 @@ pub fn init() {
 /// # Паника
 /// Если переменная `parameter` не определена в окружении, приложение завершится с ошибкой.
 pub fn get(parameter: &str) -> String {
-    let env_parameter = std::env::var(parameter)
-        .expect(&format!("{} is not defined in the environment.", parameter));
-    env_parameter
+    std::env::var(parameter)
+        .unwrap_or_else(|_| panic!("{} is not defined in the environment.", parameter))
+}
+
+/// Получение необязательной переменной окружения по имени.
+///
+/// В отличие от `get`, не паникует при отсутствии переменной.
+///
+/// **<u>:param parameter</u>**: Имя переменной окружения.
+/// **<u>return</u>**: `Some(значение)`, если переменная определена, иначе `None`.
+pub fn get_optional(parameter: &str) -> Option<String> {
+    std::env::var(parameter).ok()
+}
+
+/// Получение необязательной положительной числовой переменной окружения.
+///
+/// **<u>:param parameter</u>**: Имя переменной окружения.
+/// **<u>:param default</u>**: Значение, если переменная не определена.
+/// **<u>:return</u>**: Значение переменной или `default`.
+///
+/// # Паника
+/// Если переменная определена, но не является положительным числом.
+pub fn get_positive_or<T>(parameter: &str, default: T) -> T
+where
+    T: FromStr + PartialOrd + Default,
+{
+    positive_or(parameter, get_optional(parameter), default)
+}
+
+/// Получение необязательной неотрицательной числовой переменной окружения.
+///
+/// **<u>:param parameter</u>**: Имя переменной окружения.
+/// **<u>:param default</u>**: Значение, если переменная не определена.
+/// **<u>:return</u>**: Значение переменной или `default`.
+///
+/// # Паника
+/// Если переменная определена, но не является неотрицательным числом.
+pub fn get_non_negative_or<T>(parameter: &str, default: T) -> T
+where
+    T: FromStr + PartialOrd + Default,
+{
+    non_negative_or(parameter, get_optional(parameter), default)
+}
+
+fn positive_or<T>(parameter: &str, value: Option<String>, default: T) -> T
+where
+    T: FromStr + PartialOrd + Default,
+{
+    number_or(parameter, value, default, |number| *number > T::default(), "a positive number")
+}
+
+fn non_negative_or<T>(parameter: &str, value: Option<String>, default: T) -> T
+where
+    T: FromStr + PartialOrd + Default,
+{
+    number_or(parameter, value, default, |number| *number >= T::default(), "a non-negative number")
+}
+
+/// Разбор значения числовой переменной с проверкой.
+///
+/// Паникует с сообщением `<parameter> must be <expected>`, если значение
+/// не разбирается или не проходит проверку `is_valid`.
+fn number_or<T: FromStr>(
+    parameter: &str,
+    value: Option<String>,
+    default: T,
+    is_valid: impl Fn(&T) -> bool,
+    expected: &str,
+) -> T {
+    match value {
+        Some(value) => value
+            .trim()
+            .parse::<T>()
+            .ok()
+            .filter(|number| is_valid(number))
+            .unwrap_or_else(|| panic!("{} must be {}", parameter, expected)),
+        None => default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_value_falls_back_to_default() {
+        assert_eq!(positive_or("TTL", None, 42_i64), 42);
+        assert_eq!(non_negative_or("CACHE", None, 7_u64), 7);
+    }
+
+    #[test]
+    fn reads_valid_numbers() {
+        assert_eq!(non_negative_or("CACHE", Some(" 15 ".to_string()), 1_u64), 15);
+        assert_eq!(non_negative_or("CACHE", Some("0".to_string()), 30_u64), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "CACHE must be a non-negative number")]
+    fn rejects_negative_numbers() {
+        non_negative_or("CACHE", Some("-5".to_string()), 1_i64);
+    }
+
+    #[test]
+    #[should_panic(expected = "TTL must be a positive number")]
+    fn rejects_zero_for_positive() {
+        positive_or("TTL", Some("0".to_string()), 1_u64);
+    }
+
+    #[test]
+    #[should_panic(expected = "CACHE must be a non-negative number")]
+    fn rejects_non_ascii_garbage() {
+        non_negative_or("CACHE", Some("тридцать".to_string()), 30_u64);
+    }
 }
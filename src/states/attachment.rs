@@ -0,0 +1,23 @@
+use crate::db::db::Database;
+use crate::services::attachment::AttachmentService;
+use std::sync::Arc;
+
+/// Состояние для модуля вложений (`AttachmentState`).
+///
+/// - `attachment_service` — загрузка, скачивание и удаление вложений задач.
+#[derive(Clone)]
+pub struct AttachmentState {
+    pub attachment_service: AttachmentService,
+}
+
+impl AttachmentState {
+    /// Создаёт новый экземпляр `AttachmentState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `AttachmentState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            attachment_service: AttachmentService::new(db_conn),
+        }
+    }
+}
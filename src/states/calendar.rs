@@ -0,0 +1,23 @@
+use crate::db::db::Database;
+use crate::services::calendar::CalendarService;
+use std::sync::Arc;
+
+/// Состояние для модуля календарной ленты (`CalendarState`).
+///
+/// - `calendar_service` — управление лентой и её содержимое в формате iCalendar.
+#[derive(Clone)]
+pub struct CalendarState {
+    pub calendar_service: CalendarService,
+}
+
+impl CalendarState {
+    /// Создаёт новый экземпляр `CalendarState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `CalendarState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            calendar_service: CalendarService::new(db_conn),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::db::db::Database;
+use crate::services::column::ColumnService;
+use std::sync::Arc;
+
+/// Состояние для модуля доски проекта (`ColumnState`).
+///
+/// - `column_service` — бизнес-логика колонок доски.
+#[derive(Clone)]
+pub struct ColumnState {
+    pub column_service: ColumnService,
+}
+
+impl ColumnState {
+    /// Создаёт новый экземпляр `ColumnState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `ColumnState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            column_service: ColumnService::new(db_conn),
+        }
+    }
+}
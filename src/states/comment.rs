@@ -0,0 +1,23 @@
+use crate::db::db::Database;
+use crate::services::comment::CommentService;
+use std::sync::Arc;
+
+/// Состояние для модуля комментариев (`CommentState`).
+///
+/// - `comment_service` — бизнес-логика комментариев и проверка прав доступа.
+#[derive(Clone)]
+pub struct CommentState {
+    pub comment_service: CommentService,
+}
+
+impl CommentState {
+    /// Создаёт новый экземпляр `CommentState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `CommentState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            comment_service: CommentService::new(db_conn),
+        }
+    }
+}
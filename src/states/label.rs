@@ -0,0 +1,23 @@
+use crate::db::db::Database;
+use crate::services::label::LabelService;
+use std::sync::Arc;
+
+/// Состояние для модуля меток (`LabelState`).
+///
+/// - `label_service` — бизнес-логика меток и проверка прав доступа.
+#[derive(Clone)]
+pub struct LabelState {
+    pub label_service: LabelService,
+}
+
+impl LabelState {
+    /// Создаёт новый экземпляр `LabelState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `LabelState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            label_service: LabelService::new(db_conn),
+        }
+    }
+}
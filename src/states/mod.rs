@@ -1 +1,11 @@
-pub mod user;
\ No newline at end of file
+pub mod user;
+pub mod task;
+pub mod label;
+pub mod comment;
+pub mod attachment;
+pub mod project;
+pub mod column;
+pub mod search;
+pub mod transfer;
+pub mod calendar;
+pub mod time_entry;
\ No newline at end of file
@@ -0,0 +1,23 @@
+use crate::db::db::Database;
+use crate::services::project::ProjectService;
+use std::sync::Arc;
+
+/// Состояние для модуля проектов (`ProjectState`).
+///
+/// - `project_service` — бизнес-логика проектов и участников.
+#[derive(Clone)]
+pub struct ProjectState {
+    pub project_service: ProjectService,
+}
+
+impl ProjectState {
+    /// Создаёт новый экземпляр `ProjectState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `ProjectState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            project_service: ProjectService::new(db_conn),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::db::db::Database;
+use crate::services::search::SearchService;
+use std::sync::Arc;
+
+/// Состояние для модуля поиска (`SearchState`).
+///
+/// - `search_service` — полнотекстовый поиск по задачам и комментариям.
+#[derive(Clone)]
+pub struct SearchState {
+    pub search_service: SearchService,
+}
+
+impl SearchState {
+    /// Создаёт новый экземпляр `SearchState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `SearchState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            search_service: SearchService::new(db_conn),
+        }
+    }
+}
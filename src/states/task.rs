@@ -0,0 +1,25 @@
+use crate::db::db::Database;
+use crate::services::task::TaskService;
+use std::sync::Arc;
+
+/// Состояние для модуля задач (`TaskState`).
+///
+/// Содержит зависимости, необходимые для работы handler'ов задач.
+///
+/// - `task_service` — бизнес-логика задач и проверка прав доступа.
+#[derive(Clone)]
+pub struct TaskState {
+    pub task_service: TaskService,
+}
+
+impl TaskState {
+    /// Создаёт новый экземпляр `TaskState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `TaskState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            task_service: TaskService::new(db_conn),
+        }
+    }
+}
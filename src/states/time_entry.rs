@@ -0,0 +1,23 @@
+use crate::db::db::Database;
+use crate::services::time_entry::TimeEntryService;
+use std::sync::Arc;
+
+/// Состояние для модуля учёта времени (`TimeEntryState`).
+///
+/// - `time_entry_service` — таймеры, ручные записи и отчёт по учтённому времени.
+#[derive(Clone)]
+pub struct TimeEntryState {
+    pub time_entry_service: TimeEntryService,
+}
+
+impl TimeEntryState {
+    /// Создаёт новый экземпляр `TimeEntryState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `TimeEntryState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            time_entry_service: TimeEntryService::new(db_conn),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::db::db::Database;
+use crate::services::transfer::TransferService;
+use std::sync::Arc;
+
+/// Состояние для модуля импорта и экспорта задач (`TransferState`).
+///
+/// - `transfer_service` — импорт и экспорт задач в CSV, JSON Lines и iCalendar.
+#[derive(Clone)]
+pub struct TransferState {
+    pub transfer_service: TransferService,
+}
+
+impl TransferState {
+    /// Создаёт новый экземпляр `TransferState`.
+    ///
+    /// :param db_conn: Обёртка над пулом подключения к базе (`Arc<Database>`).
+    /// :return: Готовое состояние `TransferState`.
+    pub fn new(db_conn: &Arc<Database>) -> Self {
+        Self {
+            transfer_service: TransferService::new(db_conn),
+        }
+    }
+}
@@ -1,6 +1,11 @@
 use crate::db::db::Database;
 use crate::repositories::user::{UserRepositoryTrait, UserRepository};
+use crate::services::email_verification::EmailVerificationService;
+use crate::services::password_reset::PasswordResetService;
+use crate::services::refresh_token::RefreshTokenService;
+use crate::services::session::{SessionService, TrustedProxies};
 use crate::services::token::{TokenService, TokenServiceTrait};
+use crate::services::token_revocation::TokenRevocationService;
 use crate::services::user::UserService;
 use std::sync::Arc;
 
@@ -8,14 +13,20 @@ use std::sync::Arc;
 ///
 /// Объединяет все зависимости, необходимые для работы handler'ов аутентификации и авторизации.
 ///
-/// - `token_service` — сервис генерации и проверки JWT-токенов.
+/// - `refresh_token_service` — выдача и ротация пар JWT и refresh-токена.
 /// - `user_repo` — репозиторий для работы с пользователями.
 /// - `user_service` — бизнес-логика работы с пользователями.
+/// - `password_reset_service` — сброс пароля по токену из письма.
+/// - `email_verification_service` — подтверждение email и повторная отправка письма.
+/// - `trusted_proxies` — прокси, чьему `X-Forwarded-For` можно верить.
 #[derive(Clone)]
 pub struct AuthState {
-    pub(crate) token_service: TokenService,
+    pub(crate) refresh_token_service: RefreshTokenService,
     pub(crate) user_repo: UserRepository,
     pub(crate) user_service: UserService,
+    pub(crate) password_reset_service: PasswordResetService,
+    pub(crate) email_verification_service: EmailVerificationService,
+    pub(crate) trusted_proxies: TrustedProxies,
 }
 
 impl AuthState {
@@ -25,9 +36,12 @@ impl AuthState {
     /// :return: Инициализированное состояние авторизации.
     pub fn new(db_conn: &Arc<Database>) -> AuthState {
         Self {
-            token_service: TokenService::new(),
+            refresh_token_service: RefreshTokenService::new(db_conn),
             user_service: UserService::new(db_conn),
             user_repo: UserRepository::new(db_conn),
+            password_reset_service: PasswordResetService::new(db_conn),
+            email_verification_service: EmailVerificationService::new(db_conn),
+            trusted_proxies: TrustedProxies::from_settings(),
         }
     }
 }
@@ -38,10 +52,12 @@ impl AuthState {
 ///
 /// - `user_service` — бизнес-логика пользователей.
 /// - `user_repo` — репозиторий для работы с таблицей пользователей.
+/// - `email_verification_service` — письмо подтверждения после регистрации.
 #[derive(Clone)]
 pub struct UserState {
     pub user_service: UserService,
     pub user_repo: UserRepository,
+    pub email_verification_service: EmailVerificationService,
 }
 
 impl UserState {
@@ -53,6 +69,7 @@ impl UserState {
         Self {
             user_service: UserService::new(db_conn),
             user_repo: UserRepository::new(db_conn),
+            email_verification_service: EmailVerificationService::new(db_conn),
         }
     }
 }
@@ -63,10 +80,14 @@ impl UserState {
 ///
 /// - `token_service` — сервис генерации и декодирования JWT.
 /// - `user_repo` — доступ к данным пользователей (например, для проверки при refresh).
+/// - `revocation_service` — отзыв токенов; кэш общий для всех клонов состояния.
+/// - `session_service` — список сессий и отметка их активности.
 #[derive(Clone)]
 pub struct TokenState {
     pub token_service: TokenService,
     pub user_repo: UserRepository,
+    pub revocation_service: TokenRevocationService,
+    pub session_service: SessionService,
 }
 
 impl TokenState {
@@ -78,6 +99,8 @@ impl TokenState {
         Self {
             token_service: TokenService::new(),
             user_repo: UserRepository::new(db_conn),
+            revocation_service: TokenRevocationService::new(db_conn),
+            session_service: SessionService::new(db_conn),
         }
     }
 }
@@ -0,0 +1,85 @@
+use crate::errors::storage::StorageError;
+use crate::storage::storage::{ByteStream, FileStorage, ObjectWriter};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::StreamExt;
+use std::path::PathBuf;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
+
+/// Хранилище вложений в локальной файловой системе (`LocalStorage`).
+///
+/// Ключ объекта используется как относительный путь внутри корневого каталога.
+#[derive(Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// Создание хранилища.
+    ///
+    /// :param root: корневой каталог (создаётся при первой записи).
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Путь к файлу объекта.
+    ///
+    /// Ключи формирует сервер, но компоненты `..` всё равно отбрасываются,
+    /// чтобы путь не вышел за пределы корневого каталога.
+    fn path(&self, key: &str) -> PathBuf {
+        key.split('/')
+            .filter(|part| !part.is_empty() && *part != "." && *part != "..")
+            .fold(self.root.clone(), |path, part| path.join(part))
+    }
+}
+
+#[async_trait]
+impl FileStorage for LocalStorage {
+    async fn writer(&self, key: &str) -> Result<Box<dyn ObjectWriter>, StorageError> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let file = fs::File::create(&path).await?;
+        Ok(Box::new(LocalObjectWriter { file, path }))
+    }
+
+    async fn read(&self, key: &str) -> Result<ByteStream, StorageError> {
+        let file = fs::File::open(self.path(key)).await?;
+        Ok(ReaderStream::new(file).boxed())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        match fs::remove_file(self.path(key)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Запись объекта в файл (`LocalObjectWriter`).
+struct LocalObjectWriter {
+    file: fs::File,
+    path: PathBuf,
+}
+
+#[async_trait]
+impl ObjectWriter for LocalObjectWriter {
+    async fn write(&mut self, chunk: Bytes) -> Result<(), StorageError> {
+        Ok(self.file.write_all(&chunk).await?)
+    }
+
+    async fn finish(&mut self) -> Result<(), StorageError> {
+        Ok(self.file.shutdown().await?)
+    }
+
+    async fn abort(&mut self) -> Result<(), StorageError> {
+        match fs::remove_file(&self.path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod storage;
+pub mod local;
+pub mod s3;
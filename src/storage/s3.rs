@@ -0,0 +1,223 @@
+use crate::errors::storage::StorageError;
+use crate::storage::storage::{ByteStream, FileStorage, ObjectWriter};
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::{StreamExt, TryStreamExt};
+use object_store::aws::AmazonS3Builder;
+use object_store::path::Path;
+use object_store::{ObjectStore, WriteMultipart};
+use std::sync::Arc;
+
+/// Размер части multipart upload — 10 МиБ.
+///
+/// Объекты меньшего размера загружаются одним запросом.
+const PART_SIZE: usize = 10 * 1024 * 1024;
+
+/// Максимальное число одновременно загружаемых частей.
+const MAX_CONCURRENCY: usize = 8;
+
+/// Хранилище вложений в S3-совместимом сервисе (`S3Storage`).
+///
+/// Работает с AWS S3 и совместимыми реализациями (MinIO и т. п.).
+/// Крупные объекты загружаются через multipart upload.
+#[derive(Clone)]
+pub struct S3Storage {
+    store: Arc<dyn ObjectStore>,
+    part_size: usize,
+}
+
+impl S3Storage {
+    /// Создание хранилища.
+    ///
+    /// Параметры подключения (регион, ключи, `AWS_ENDPOINT`, `AWS_ALLOW_HTTP`)
+    /// читаются из переменных окружения `AWS_*`.
+    ///
+    /// :param bucket: имя бакета.
+    /// :return: хранилище либо `StorageError`, если конфигурация неполная.
+    pub fn new(bucket: &str) -> Result<Self, StorageError> {
+        let store = AmazonS3Builder::from_env()
+            .with_bucket_name(bucket)
+            .build()?;
+
+        Ok(Self {
+            store: Arc::new(store),
+            part_size: PART_SIZE,
+        })
+    }
+}
+
+#[cfg(test)]
+impl S3Storage {
+    /// Хранилище в памяти с тем же протоколом записи, что и S3.
+    ///
+    /// :param part_size: размер части multipart upload.
+    pub(crate) fn in_memory(part_size: usize) -> Self {
+        Self {
+            store: Arc::new(object_store::memory::InMemory::new()),
+            part_size,
+        }
+    }
+
+    /// Хранилище для интеграционного теста против реального S3-сервиса (MinIO).
+    ///
+    /// :param endpoint: адрес сервиса (`S3_TEST_ENDPOINT`).
+    /// :param bucket: существующий бакет.
+    /// :param part_size: размер части multipart upload (для S3 не меньше 5 МиБ).
+    pub(crate) fn with_endpoint(endpoint: &str, bucket: &str, part_size: usize) -> Result<Self, StorageError> {
+        let store = AmazonS3Builder::from_env()
+            .with_endpoint(endpoint)
+            .with_allow_http(true)
+            .with_bucket_name(bucket)
+            .build()?;
+
+        Ok(Self {
+            store: Arc::new(store),
+            part_size,
+        })
+    }
+}
+
+#[async_trait]
+impl FileStorage for S3Storage {
+    async fn writer(&self, key: &str) -> Result<Box<dyn ObjectWriter>, StorageError> {
+        Ok(Box::new(S3ObjectWriter {
+            store: Arc::clone(&self.store),
+            path: Path::from(key),
+            part_size: self.part_size,
+            buffer: BytesMut::new(),
+            upload: None,
+        }))
+    }
+
+    async fn read(&self, key: &str) -> Result<ByteStream, StorageError> {
+        let result = self.store.get(&Path::from(key)).await?;
+        Ok(result.into_stream().map_err(std::io::Error::other).boxed())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        match self.store.delete(&Path::from(key)).await {
+            Err(e) if !matches!(e, object_store::Error::NotFound { .. }) => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Запись объекта в S3 (`S3ObjectWriter`).
+///
+/// Содержимое копится в буфере; как только оно превышает размер части,
+/// начинается multipart upload. Незавершённый upload отменяется в `abort()`,
+/// иначе его части остались бы в бакете.
+struct S3ObjectWriter {
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    part_size: usize,
+    buffer: BytesMut,
+    upload: Option<WriteMultipart>,
+}
+
+#[async_trait]
+impl ObjectWriter for S3ObjectWriter {
+    async fn write(&mut self, chunk: Bytes) -> Result<(), StorageError> {
+        if self.upload.is_none() {
+            if self.buffer.len() + chunk.len() < self.part_size {
+                self.buffer.extend_from_slice(&chunk);
+                return Ok(());
+            }
+
+            let upload = self.store.put_multipart(&self.path).await?;
+            let mut upload = WriteMultipart::new_with_chunk_size(upload, self.part_size);
+            upload.put(self.buffer.split().freeze());
+            self.upload = Some(upload);
+        }
+
+        let upload = self.upload.as_mut().expect("multipart upload is started");
+        upload.wait_for_capacity(MAX_CONCURRENCY).await?;
+        upload.put(chunk);
+        Ok(())
+    }
+
+    async fn finish(&mut self) -> Result<(), StorageError> {
+        let Some(mut upload) = self.upload.take() else {
+            let payload = self.buffer.split().freeze();
+            self.store.put(&self.path, payload.into()).await?;
+            return Ok(());
+        };
+
+        // Ошибка загрузки частей не отменяет upload сама, в отличие от ошибки `complete`
+        if let Err(e) = upload.wait_for_capacity(0).await {
+            let _ = upload.abort().await;
+            return Err(e.into());
+        }
+        upload.finish().await?;
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<(), StorageError> {
+        self.buffer.clear();
+        match self.upload.take() {
+            Some(upload) => Ok(upload.abort().await?),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Минимальный размер части multipart upload в S3.
+    const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+    /// Хранилище из `S3_TEST_ENDPOINT` и `S3_TEST_BUCKET` (по умолчанию `attachments-test`);
+    /// ключи доступа и регион — из `AWS_*`. Без `S3_TEST_ENDPOINT` тест пропускается.
+    fn test_storage() -> Option<S3Storage> {
+        let endpoint = std::env::var("S3_TEST_ENDPOINT").ok()?;
+        let bucket = std::env::var("S3_TEST_BUCKET").unwrap_or_else(|_| "attachments-test".to_string());
+        Some(S3Storage::with_endpoint(&endpoint, &bucket, MIN_PART_SIZE).expect("valid S3 test configuration"))
+    }
+
+    async fn put(storage: &S3Storage, key: &str, chunks: Vec<Bytes>) {
+        let mut writer = storage.writer(key).await.unwrap();
+        for chunk in chunks {
+            writer.write(chunk).await.unwrap();
+        }
+        writer.finish().await.unwrap();
+    }
+
+    async fn get(storage: &S3Storage, key: &str) -> Result<Vec<u8>, StorageError> {
+        let parts: Vec<Bytes> = storage.read(key).await?.try_collect().await?;
+        Ok(parts.concat())
+    }
+
+    #[tokio::test]
+    async fn puts_gets_and_deletes_objects() {
+        let Some(storage) = test_storage() else {
+            eprintln!("S3_TEST_ENDPOINT is not set, skipping");
+            return;
+        };
+        let key = format!("tests/{}", uuid::Uuid::new_v4());
+
+        put(&storage, &key, vec![Bytes::from_static(b"hello "), Bytes::from_static(b"world")]).await;
+        assert_eq!(get(&storage, &key).await.unwrap(), b"hello world");
+
+        storage.delete(&key).await.unwrap();
+        assert!(matches!(get(&storage, &key).await, Err(StorageError::NotFound)));
+        storage.delete(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn puts_large_objects_with_multipart_upload() {
+        let Some(storage) = test_storage() else {
+            eprintln!("S3_TEST_ENDPOINT is not set, skipping");
+            return;
+        };
+        let key = format!("tests/{}", uuid::Uuid::new_v4());
+        let content: Vec<u8> = (0..MIN_PART_SIZE + 1024).map(|i| (i % 251) as u8).collect();
+
+        put(&storage, &key, content.chunks(64 * 1024).map(Bytes::copy_from_slice).collect()).await;
+        assert_eq!(get(&storage, &key).await.unwrap(), content);
+
+        storage.delete(&key).await.unwrap();
+        assert!(matches!(get(&storage, &key).await, Err(StorageError::NotFound)));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::errors::storage::StorageError;
+use crate::settings::settings;
+use crate::storage::{local::LocalStorage, s3::S3Storage};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use std::sync::Arc;
+
+/// Поток байтов объекта, читаемого из хранилища.
+pub type ByteStream = BoxStream<'static, Result<Bytes, std::io::Error>>;
+
+/// Трейт `ObjectWriter` — потоковая запись объекта в хранилище.
+///
+/// Объект становится доступен для чтения только после успешного `finish()`.
+/// Если запись не завершена (ошибка записи, превышение размера и т. п.),
+/// вызывающий обязан вызвать `abort()`, чтобы освободить частично записанные данные.
+#[async_trait]
+pub trait ObjectWriter: Send {
+    /// Запись очередного фрагмента.
+    ///
+    /// :param chunk: фрагмент содержимого.
+    /// :return: `()` либо `StorageError`.
+    async fn write(&mut self, chunk: Bytes) -> Result<(), StorageError>;
+
+    /// Завершение записи.
+    ///
+    /// :return: `()` либо `StorageError`.
+    async fn finish(&mut self) -> Result<(), StorageError>;
+
+    /// Отмена записи и удаление частично записанных данных.
+    ///
+    /// Безопасно вызывать после неудачного `write()` или `finish()`.
+    ///
+    /// :return: `()` либо `StorageError`.
+    async fn abort(&mut self) -> Result<(), StorageError>;
+}
+
+/// Трейт `FileStorage` — интерфейс хранилища файлов вложений.
+///
+/// Объекты адресуются строковым ключом вида `tasks/<task_id>/<uuid>`.
+///
+/// - `writer` — потоковая запись объекта.
+/// - `read` — потоковое чтение объекта.
+/// - `delete` — удаление объекта (отсутствующий объект не считается ошибкой).
+#[async_trait]
+pub trait FileStorage: Send + Sync {
+    /// Открытие объекта на запись.
+    ///
+    /// :param key: ключ объекта.
+    /// :return: поток записи либо `StorageError`.
+    async fn writer(&self, key: &str) -> Result<Box<dyn ObjectWriter>, StorageError>;
+
+    /// Открытие объекта на чтение.
+    ///
+    /// :param key: ключ объекта.
+    /// :return: поток байтов, `StorageError::NotFound` или `StorageError::Backend`.
+    async fn read(&self, key: &str) -> Result<ByteStream, StorageError>;
+
+    /// Удаление объекта.
+    ///
+    /// :param key: ключ объекта.
+    /// :return: `()` либо `StorageError`.
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+}
+
+/// Создание хранилища по настройкам окружения.
+///
+/// - `ATTACHMENT_STORAGE` — `local` (по умолчанию) или `s3`.
+/// - `ATTACHMENT_DIR` — каталог для `local` (по умолчанию `attachments`).
+/// - `ATTACHMENT_S3_BUCKET` — бакет для `s3`; остальные параметры подключения
+///   берутся из стандартных переменных `AWS_*` (`AWS_ENDPOINT`, `AWS_ALLOW_HTTP`
+///   для MinIO и т. п.).
+///
+/// # Паника
+/// Если `ATTACHMENT_STORAGE` имеет неизвестное значение или S3 не настроен.
+pub fn from_settings() -> Arc<dyn FileStorage> {
+    match settings::get_optional("ATTACHMENT_STORAGE").as_deref() {
+        None | Some("local") => Arc::new(LocalStorage::new(
+            settings::get_optional("ATTACHMENT_DIR").unwrap_or_else(|| "attachments".to_string()),
+        )),
+        Some("s3") => Arc::new(
+            S3Storage::new(&settings::get("ATTACHMENT_S3_BUCKET"))
+                .unwrap_or_else(|e| panic!("S3 storage is misconfigured: {}", e)),
+        ),
+        Some(other) => panic!("ATTACHMENT_STORAGE `{}` is not supported", other),
+    }
+}